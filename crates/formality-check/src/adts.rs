@@ -1,13 +1,21 @@
 use std::collections::HashSet;
 
 use anyhow::bail;
+use fn_error_context::context;
+use formality_core::{visit::CoreVisit, Map, Upcast};
 use formality_prove::Env;
-use formality_rust::grammar::{Adt, AdtBoundData, Field, Variant};
-use formality_types::grammar::Fallible;
+use formality_rust::{
+    grammar::{Adt, AdtBoundData, DefaultArg, Field, StructBoundData, Union, Variant, WhereClause},
+    prove::ToWcs,
+};
+use formality_types::grammar::{
+    AdtId, Fallible, Parameter, RigidName, RigidTy, Substitution, TraitId, Ty, TyData,
+    UniversalVar, Variable, Wcs,
+};
 
 impl super::Check<'_> {
     pub(super) fn check_adt(&self, adt: &Adt) -> Fallible<()> {
-        let Adt { id: _, binder } = adt;
+        let Adt { binder, .. } = adt;
 
         // names is used to check that there are no name conflicts
         let mut names = HashSet::new();
@@ -23,15 +31,25 @@ impl super::Check<'_> {
             }
         }
 
-        let mut env = Env::default();
+        self.check_finite_size(adt)?;
+
+        let (env, vars) = Env::default().universal_substitution(binder);
 
         let AdtBoundData {
+            defaults,
             where_clauses,
             variants,
-        } = env.instantiate_universally(binder);
+        } = binder.instantiate_with(&vars).unwrap();
 
         self.prove_where_clauses_well_formed(&env, &where_clauses, &where_clauses)?;
 
+        for default in &defaults {
+            self.check_adt_default(&env, &vars, default, &where_clauses)?;
+        }
+
+        // A field that refers back to the ADT (e.g., `next: Option<Box<List<T>>>`)
+        // does not recurse here: an ADT is well-formed if its where-clauses
+        // hold, without looking at its own fields.
         for Variant { name: _, fields } in &variants {
             for Field { name: _, ty } in fields {
                 self.prove_goal(&env, &where_clauses, ty.well_formed())?;
@@ -40,4 +58,142 @@ impl super::Check<'_> {
 
         Ok(())
     }
+
+    /// An ADT that contains itself by value, directly or through the fields of
+    /// other ADTs, tuples, arrays, or slices, would have infinite size, so it
+    /// must be behind an indirection such as a reference instead. An ADT whose
+    /// fields do not contain its parameters by value (like `Box<T>` here)
+    /// counts as an indirection too.
+    fn check_finite_size(&self, adt: &Adt) -> Fallible<()> {
+        let adts: Map<AdtId, Adt> = self
+            .program
+            .adts_from_all_crates()
+            .map(|a| (a.id.clone(), a))
+            .collect();
+        let (_, vars) = Env::default().universal_substitution(&adt.binder);
+        let AdtBoundData { variants, .. } = adt.binder.instantiate_with(&vars).unwrap();
+        for Variant { name: _, fields } in &variants {
+            for Field { name, ty } in fields {
+                if contains_by_value(&adts, &adt.id, ty, &mut vec![]) {
+                    bail!(
+                        "recursive type `{:?}` has infinite size: field `{name:?}` contains it without an indirection",
+                        adt.id
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A default must only refer to the parameters declared before its own, be
+    /// well-formed, and satisfy the where-clauses that mention its parameter,
+    /// assuming the remaining where-clauses.
+    #[context("check_adt_default({default:?})")]
+    fn check_adt_default(
+        &self,
+        env: &Env,
+        vars: &[UniversalVar],
+        default: &DefaultArg,
+        where_clauses: &[WhereClause],
+    ) -> Fallible<()> {
+        let DefaultArg { param, value } = default;
+        let vars: Vec<Variable> = vars.iter().map(|v| v.upcast()).collect();
+        let Some(index) = vars.iter().position(|&v| {
+            let v: Parameter = v.upcast();
+            v == *param
+        }) else {
+            bail!("`{param:?}` is not a generic parameter")
+        };
+
+        if let Some(v) = value
+            .free_variables()
+            .into_iter()
+            .find(|v| !vars[..index].contains(v))
+        {
+            bail!("the default refers to `{v:?}`, which is not declared before `{param:?}`")
+        }
+
+        let (bounds, others): (Vec<&WhereClause>, Vec<&WhereClause>) = where_clauses
+            .iter()
+            .partition(|wc| wc.free_variables().contains(&vars[index]));
+        let subst: Substitution = [(vars[index], value.clone())].into_iter().collect();
+        let assumptions: Wcs = others.iter().flat_map(|wc| wc.to_wcs()).collect();
+        let bounds: Wcs = bounds
+            .iter()
+            .flat_map(|wc| subst.apply(*wc).to_wcs())
+            .collect();
+        self.prove_goal(env, assumptions, (value.well_formed(), bounds))
+    }
+
+    /// Unions are checked like other ADTs, except that since their fields are
+    /// never dropped, each field must also be `Copy`. A program that does not
+    /// declare `Copy` cannot state that, so the requirement is then skipped.
+    pub(super) fn check_union(&self, union: &Union) -> Fallible<()> {
+        self.check_adt(&union.to_adt())?;
+
+        if !self.decls.has_trait_decl(&TraitId::copy()) {
+            return Ok(());
+        }
+
+        let mut env = Env::default();
+        let StructBoundData {
+            defaults: _,
+            where_clauses,
+            fields,
+        } = env.instantiate_universally(&union.binder);
+        for Field { name: _, ty } in &fields {
+            self.prove_goal(
+                &env,
+                &where_clauses,
+                TraitId::copy().with(ty, ()).is_implemented(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// True if a value of type `ty` contains a value of the ADT `root`. The ADTs
+/// in `stack` are being looked into already, so they are not expanded again.
+fn contains_by_value(
+    adts: &Map<AdtId, Adt>,
+    root: &AdtId,
+    ty: &Ty,
+    stack: &mut Vec<AdtId>,
+) -> bool {
+    let TyData::RigidTy(RigidTy { name, parameters }) = ty.data() else {
+        return false;
+    };
+    match name {
+        RigidName::AdtId(id) if id == root => true,
+        RigidName::AdtId(id) => {
+            if stack.contains(id) {
+                return false;
+            }
+            let Some(adt) = adts.get(id) else {
+                return false;
+            };
+            let Ok(AdtBoundData { variants, .. }) = adt.binder.instantiate_with(parameters) else {
+                return false;
+            };
+            stack.push(id.clone());
+            let found = variants
+                .iter()
+                .flat_map(|v| &v.fields)
+                .any(|f| contains_by_value(adts, root, &f.ty, stack));
+            stack.pop();
+            found
+        }
+        RigidName::Tuple(_) | RigidName::Array | RigidName::Slice => {
+            parameters.iter().any(|p| match p {
+                Parameter::Ty(ty) => contains_by_value(adts, root, ty, stack),
+                _ => false,
+            })
+        }
+        RigidName::ScalarId(_)
+        | RigidName::Ref(_)
+        | RigidName::Never
+        | RigidName::FnPtr(_)
+        | RigidName::FnDef(_) => false,
+    }
 }
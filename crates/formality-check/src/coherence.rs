@@ -1,13 +1,176 @@
 use anyhow::bail;
 use fn_error_context::context;
-use formality_core::Downcasted;
-use formality_prove::Env;
-use formality_rust::grammar::{Crate, NegTraitImpl, TraitImpl};
-use formality_types::grammar::{Fallible, Wc, Wcs};
+use formality_core::{Downcasted, Map, Upcast};
+use formality_prove::{Bias, Defaultness, Env};
+use formality_rust::{
+    grammar::{Crate, CrateItem, NegTraitImpl, Program, TraitImpl, WhereClause},
+    prove::ToWcs,
+};
+use formality_types::grammar::{Fallible, Relation, TraitId, TraitRef, Ty, Wc, Wcs};
 use itertools::Itertools;
 
 use crate::Check;
 
+/// Every coherence violation in a program, as found by [`check_all_coherence`].
+#[derive(Debug, Default)]
+pub struct CoherenceReport {
+    pub violations: Vec<CoherenceViolation>,
+}
+
+#[derive(Debug)]
+pub enum CoherenceViolation {
+    /// The impl (positive or negative) fails the orphan rules of its crate.
+    Orphan {
+        item: CrateItem,
+        error: anyhow::Error,
+    },
+
+    /// The two impls may apply to the same types. `impl_a` is from a crate at
+    /// least as late as `impl_b`; each pair is reported once.
+    Overlap {
+        impl_a: TraitImpl,
+        impl_b: TraitImpl,
+        error: anyhow::Error,
+    },
+}
+
+/// Runs the orphan check on every impl and the overlap check on every pair of
+/// impls of the same trait, collecting all failures rather than stopping at the
+/// first. Like [`crate::check_all_crates`], crates are checked in order, each
+/// against the prefix of the program that it can see.
+pub fn check_all_coherence(program: &Program) -> CoherenceReport {
+    let Program { crates } = &program.elaborate_sized();
+    let mut report = CoherenceReport::default();
+
+    let mut prefix_program = Program { crates: vec![] };
+    let mut impls_by_trait: Map<TraitId, Vec<TraitImpl>> = Map::new();
+    for c in crates {
+        prefix_program.crates.push(c.clone());
+        let decls = prefix_program.to_prove_decls();
+        let check = Check {
+            program: &prefix_program,
+            decls: &decls,
+        };
+
+        for item in &c.items {
+            let result = match item {
+                CrateItem::TraitImpl(impl_a) => check.orphan_check(impl_a),
+                CrateItem::NegTraitImpl(impl_a) => check.orphan_check_neg(impl_a),
+                _ => continue,
+            };
+            if let Err(error) = result {
+                report.violations.push(CoherenceViolation::Orphan {
+                    item: item.clone(),
+                    error,
+                });
+            }
+        }
+
+        // Comparing each impl only with those before it (in this crate or an
+        // earlier one) visits every unordered pair exactly once.
+        for impl_a in c.items.iter().downcasted::<TraitImpl>() {
+            let earlier = impls_by_trait.entry(impl_a.trait_id().clone()).or_default();
+            for impl_b in earlier.iter() {
+                if let Err(error) = check.overlap_check(&impl_a, impl_b) {
+                    report.violations.push(CoherenceViolation::Overlap {
+                        impl_a: impl_a.clone(),
+                        impl_b: impl_b.clone(),
+                        error,
+                    });
+                }
+            }
+            earlier.push(impl_a);
+        }
+    }
+
+    report
+}
+
+/// Whether two impls of the same trait may apply to the same types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Overlap {
+    /// The impls provably never apply to the same types.
+    No,
+
+    /// The impls may both apply, e.g., to `witness`, which may still contain
+    /// inference variables for the parts that either impl accepts.
+    Yes { witness: Ty },
+}
+
+/// Decides whether `impl_a` and `impl_b`, two impls of the same trait from
+/// `program`, overlap (see [`Overlap`]). Specialization is not taken into account.
+///
+/// The where-clauses of the impls are proven with the implicit `Sized` bounds
+/// of `program` elaborated, but `impl_a` and `impl_b` are taken as given, so
+/// pass impls of [`Program::elaborate_sized`] for their own bounds to count.
+pub fn impls_overlap(program: &Program, impl_a: &TraitImpl, impl_b: &TraitImpl) -> Overlap {
+    let program = &program.elaborate_sized();
+    let decls = program.to_prove_decls();
+    Check {
+        program,
+        decls: &decls,
+    }
+    .impls_overlap(impl_a, impl_b)
+}
+
+/// True if no type can implement both `trait_a` and `trait_b` in `program`:
+/// for every impl of the one and every impl of the other, their self types
+/// provably cannot be equated or their where-clauses cannot both hold (e.g.,
+/// because a negative impl contradicts one of them), as the overlap check
+/// decides for two impls of the same trait.
+///
+/// Only impls are considered, so a trait without any is exclusive with every
+/// trait. Traits that are exclusive for some types but not others (e.g.,
+/// for `Vec<T>` only when `T: Marker`, with `Marker` undecided) are not.
+pub fn mutually_exclusive(program: &Program, trait_a: &TraitId, trait_b: &TraitId) -> bool {
+    let program = &program.elaborate_sized();
+    let decls = program.to_prove_decls();
+    let check = Check {
+        program,
+        decls: &decls,
+    };
+    let impls_of = |trait_id: &TraitId| -> Vec<TraitImpl> {
+        program
+            .items_from_all_crates()
+            .downcasted::<TraitImpl>()
+            .filter(|i| i.trait_id() == trait_id)
+            .collect()
+    };
+    let impls_b = impls_of(trait_b);
+    impls_of(trait_a).iter().all(|impl_a| {
+        impls_b
+            .iter()
+            .all(|impl_b| check.self_tys_disjoint(impl_a, impl_b))
+    })
+}
+
+/// Picks the impl from `program` that supplies `trait_ref`. Of the impls that
+/// apply, this is the one that specializes all of the others; e.g., for
+/// `[u8; 0]: Foo`, `impl Foo for [u8; 0]` is chosen over
+/// `default impl<const N> Foo for [u8; N]`. Returns `None` if no impl applies or
+/// if no single one is the most specific.
+pub fn select_impl(program: &Program, trait_ref: &TraitRef) -> Option<TraitImpl> {
+    let program = &program.elaborate_sized();
+    let decls = program.to_prove_decls();
+    let check = Check {
+        program,
+        decls: &decls,
+    };
+    let applicable: Vec<TraitImpl> = program
+        .items_from_all_crates()
+        .downcasted::<TraitImpl>()
+        .filter(|i| check.impl_applies(i, trait_ref))
+        .collect();
+    applicable
+        .iter()
+        .find(|&special| {
+            applicable
+                .iter()
+                .all(|general| general == special || check.specializes(special, general))
+        })
+        .cloned()
+}
+
 impl Check<'_> {
     pub(crate) fn check_coherence(&self, current_crate: &Crate) -> Fallible<()> {
         let all_crate_impls: Vec<TraitImpl> =
@@ -75,6 +238,24 @@ impl Check<'_> {
 
     #[tracing::instrument(level = "Debug", skip(self))]
     fn overlap_check(&self, impl_a: &TraitImpl, impl_b: &TraitImpl) -> Fallible<()> {
+        let Overlap::Yes { witness } = self.impls_overlap(impl_a, impl_b) else {
+            return Ok(());
+        };
+
+        // Overlap is permitted when one impl is a `default impl` and the other
+        // specializes it.
+        if self.specializes(impl_a, impl_b) || self.specializes(impl_b, impl_a) {
+            return Ok(());
+        }
+
+        bail!(
+            "impls may overlap:\n{impl_a:?}\n{impl_b:?}\nboth apply to `{:?}`",
+            witness.debug_with_holes()
+        )
+    }
+
+    #[tracing::instrument(level = "Debug", skip(self), ret)]
+    fn impls_overlap(&self, impl_a: &TraitImpl, impl_b: &TraitImpl) -> Overlap {
         let mut env = Env::default();
 
         // Example:
@@ -94,29 +275,47 @@ impl Check<'_> {
 
         assert_eq!(trait_ref_a.trait_id, trait_ref_b.trait_id);
 
+        let equated = Wcs::all_eq(&trait_ref_a.parameters, &trait_ref_b.parameters);
+        if self.disjoint(&env, equated, &a.where_clauses, &b.where_clauses) {
+            return Overlap::No;
+        }
+
+        Overlap::Yes {
+            witness: self.overlap_witness(impl_a, impl_b),
+        }
+    }
+
+    /// True if `impl_a` and `impl_b`, impls of possibly different traits,
+    /// provably never apply to the same self type.
+    fn self_tys_disjoint(&self, impl_a: &TraitImpl, impl_b: &TraitImpl) -> bool {
+        let mut env = Env::default();
+        let a = env.instantiate_universally(&impl_a.binder);
+        let b = env.instantiate_universally(&impl_b.binder);
+        let equated: Wcs = Relation::equals(&a.self_ty, &b.self_ty).upcast();
+        self.disjoint(&env, equated, &a.where_clauses, &b.where_clauses)
+    }
+
+    /// True if, for all values of the placeholders in `env`, two impls with the
+    /// where-clauses `where_clauses_a` and `where_clauses_b` cannot apply at once
+    /// given that their headers are `equated`.
+    fn disjoint(
+        &self,
+        env: &Env,
+        equated: Wcs,
+        where_clauses_a: &[WhereClause],
+        where_clauses_b: &[WhereClause],
+    ) -> bool {
         // If we can prove that the parameters cannot be equated *or* the where-clauses don't hold,
         // in coherence mode, then they do not overlap.
         //
         // ∀P_a, ∀P_b. ⌐ (coherence_mode => (Ts_a = Ts_b && WC_a && WC_b))
-        if let Ok(()) = self.prove_not_goal(
-            &env,
-            (),
-            (
-                Wcs::all_eq(&trait_ref_a.parameters, &trait_ref_b.parameters),
-                &a.where_clauses,
-                &b.where_clauses,
-            ),
-        ) {
+        if let Ok(()) = self.prove_not_goal(env, (), (&equated, where_clauses_a, where_clauses_b)) {
             tracing::debug!(
                 "proved not {:?}",
-                (
-                    Wcs::all_eq(&trait_ref_a.parameters, &trait_ref_b.parameters),
-                    &a.where_clauses,
-                    &b.where_clauses,
-                )
+                (&equated, where_clauses_a, where_clauses_b)
             );
 
-            return Ok(());
+            return true;
         }
 
         // If we can disprove the where clauses, then they do not overlap.
@@ -125,20 +324,15 @@ impl Check<'_> {
         // if `T: Debug` is in `Wc_a`, then `Wc_i` might be `T: !Debug`.
         //
         // If we can prove `∀P_a, ∀P_b, (T_a = T_b, Wc_a, Wc_b) => Wc_i`, then contradiction, no overlap.
-        let inverted: Vec<Wc> = a
-            .where_clauses
+        let inverted: Vec<Wc> = where_clauses_a
             .iter()
-            .chain(&b.where_clauses)
+            .chain(where_clauses_b)
             .flat_map(|wc| wc.invert())
             .collect();
         if let Some(inverted_wc) = inverted.iter().find(|inverted_wc| {
             self.prove_goal(
-                &env,
-                (
-                    Wcs::all_eq(&trait_ref_a.parameters, &trait_ref_b.parameters),
-                    &a.where_clauses,
-                    &b.where_clauses,
-                ),
+                env,
+                (&equated, where_clauses_a, where_clauses_b),
                 inverted_wc,
             )
             .is_ok()
@@ -146,15 +340,87 @@ impl Check<'_> {
             tracing::debug!(
                 "proved {:?} assuming {:?}",
                 &inverted_wc,
-                (
-                    Wcs::all_eq(&trait_ref_a.parameters, &trait_ref_b.parameters),
-                    &a.where_clauses,
-                    &b.where_clauses,
-                )
+                (&equated, where_clauses_a, where_clauses_b)
             );
 
-            return Ok(());
+            return true;
+        }
+
+        false
+    }
+
+    /// A self type that both impls apply to: the self type of `impl_a`, with the
+    /// impl parameters that are determined by equating the two impl headers (and
+    /// proving their where-clauses, in coherence mode) filled in. Parameters that
+    /// are not determined remain inference variables.
+    fn overlap_witness(&self, impl_a: &TraitImpl, impl_b: &TraitImpl) -> Ty {
+        let env = Env::new_with_bias(Bias::Completeness);
+        let (env, subst_a) = env.existential_substitution(&impl_a.binder);
+        let a = impl_a.binder.instantiate_with(&subst_a).unwrap();
+        let (env, subst_b) = env.existential_substitution(&impl_b.binder);
+        let b = impl_b.binder.instantiate_with(&subst_b).unwrap();
+
+        let goal = (
+            Wcs::all_eq(&a.trait_ref().parameters, &b.trait_ref().parameters),
+            &a.where_clauses,
+            &b.where_clauses,
+        )
+            .to_wcs();
+        let solutions = formality_prove::prove(self.decls, env, (), goal);
+        // Prefer a solution that is known to hold over ambiguous ones, which
+        // typically determine less.
+        let witness = match solutions
+            .iter()
+            .find(|c| c.known_true)
+            .or_else(|| solutions.iter().next())
+        {
+            Some(c) => c.substitution().apply(&a.self_ty),
+            None => a.self_ty,
+        };
+        witness
+    }
+
+    /// True if `impl_a` is an impl of `trait_ref` whose where-clauses hold.
+    fn impl_applies(&self, impl_a: &TraitImpl, trait_ref: &TraitRef) -> bool {
+        if impl_a.trait_id() != &trait_ref.trait_id {
+            return false;
+        }
+
+        let env = Env::default();
+        let (env, subst) = env.existential_substitution(&impl_a.binder);
+        let a = impl_a.binder.instantiate_with(&subst).unwrap();
+
+        let goal = (
+            Wcs::all_eq(&a.trait_ref().parameters, &trait_ref.parameters),
+            &a.where_clauses,
+        )
+            .to_wcs();
+        formality_prove::prove(self.decls, env, (), goal)
+            .iter()
+            .any(|c| c.clone().pop_subst(&subst).unconditionally_true())
+    }
+
+    /// True if `general` is a `default impl` and `special` is at least as specific,
+    /// i.e., whenever `special` applies, so does `general`:
+    ///
+    /// ∀P_s. (Wc_s => ∃P_g. (Ts_s = Ts_g && Wc_g))
+    pub(crate) fn specializes(&self, special: &TraitImpl, general: &TraitImpl) -> bool {
+        if general.defaultness != Defaultness::Default {
+            return false;
         }
-        bail!("impls may overlap:\n{impl_a:?}\n{impl_b:?}")
+
+        let mut env = Env::default();
+        let s = env.instantiate_universally(&special.binder);
+        let (env, subst) = env.existential_substitution(&general.binder);
+        let g = general.binder.instantiate_with(&subst).unwrap();
+
+        let goal = (
+            Wcs::all_eq(&s.trait_ref().parameters, &g.trait_ref().parameters),
+            &g.where_clauses,
+        )
+            .to_wcs();
+        formality_prove::prove(self.decls, env, s.where_clauses.to_wcs(), goal)
+            .iter()
+            .any(|c| c.clone().pop_subst(&subst).unconditionally_true())
     }
 }
@@ -0,0 +1,166 @@
+use anyhow::bail;
+use fn_error_context::context;
+use formality_core::{visit::CoreVisit, Upcast};
+use formality_prove::Env;
+use formality_rust::grammar::{
+    Autoness, CrateItem, Fn, FnBoundData, Program, Trait, TraitBoundData, TraitItem, WhereClause,
+    WhereClauseData,
+};
+use formality_types::grammar::{
+    DynTy, Fallible, FnId, ParameterKind, RefKind, RigidName, TraitId, Ty, TyData,
+};
+
+/// Checks that the trait object type `dyn_ty` is well-formed: its principal
+/// trait is dyn compatible (see [`check_dyn_compatible`]) and each of the
+/// other traits it carries is an auto trait.
+#[context("check_dyn_ty({dyn_ty:?})")]
+pub fn check_dyn_ty(program: &Program, dyn_ty: &DynTy) -> Fallible<()> {
+    check_dyn_compatible(program, &dyn_ty.principal_id)?;
+    for trait_id in &dyn_ty.auto_traits {
+        if find_trait(program, trait_id)?.autoness != Autoness::Auto {
+            bail!("`{trait_id:?}` is not an auto trait");
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `dyn Trait` is a valid type for the trait `trait_id`, i.e. that
+/// the trait is dyn compatible (also known as object safe). The trait must not
+/// require `Self: Sized`, and each of its methods must either be callable on a
+/// trait object or be excluded from it with `where Self: Sized`.
+///
+/// A method is callable on a trait object if it has no type parameters, takes
+/// `self`, `&self`, or `&mut self` as its first argument, and does not mention
+/// `Self` anywhere else.
+#[context("check_dyn_compatible({trait_id:?})")]
+pub fn check_dyn_compatible(program: &Program, trait_id: &TraitId) -> Fallible<()> {
+    let (
+        env,
+        self_ty,
+        TraitBoundData {
+            where_clauses,
+            trait_items,
+        },
+    ) = instantiate_trait(program, trait_id)?;
+
+    if where_clauses.iter().any(|wc| is_self_sized(&self_ty, wc)) {
+        bail!("the trait requires `Self: Sized`");
+    }
+
+    for f in trait_fns(&trait_items) {
+        check_dyn_compatible_fn(&env, &self_ty, f)?;
+    }
+    Ok(())
+}
+
+/// The methods of `trait_id` that can be called on `dyn Trait`. Methods gated
+/// by `where Self: Sized` are not among them, as trait objects are unsized.
+pub fn dyn_callable_methods(program: &Program, trait_id: &TraitId) -> Fallible<Vec<FnId>> {
+    let (mut env, self_ty, TraitBoundData { trait_items, .. }) =
+        instantiate_trait(program, trait_id)?;
+    Ok(trait_fns(&trait_items)
+        .filter(|f| {
+            let FnBoundData { where_clauses, .. } = env.instantiate_universally(&f.binder);
+            !where_clauses.iter().any(|wc| is_self_sized(&self_ty, wc))
+        })
+        .map(|f| f.id.clone())
+        .collect())
+}
+
+#[context("check_dyn_compatible_fn({:?})", f.id)]
+fn check_dyn_compatible_fn(env: &Env, self_ty: &Ty, f: &Fn) -> Fallible<()> {
+    let mut env = env.clone();
+    let FnBoundData {
+        input_tys,
+        output_ty,
+        where_clauses,
+        body: _,
+    } = env.instantiate_universally(&f.binder);
+
+    if where_clauses.iter().any(|wc| is_self_sized(self_ty, wc)) {
+        return Ok(());
+    }
+
+    if f.binder.kinds().contains(&ParameterKind::Ty) {
+        bail!("the method has type parameters; add `where Self: Sized` to exclude it from trait objects");
+    }
+
+    let Some((receiver, arg_tys)) = input_tys.split_first() else {
+        bail!(
+            "the method has no receiver; add `where Self: Sized` to exclude it from trait objects"
+        );
+    };
+    if !is_receiver(self_ty, receiver) {
+        bail!("the method's first argument is not `self`, `&self`, or `&mut self`");
+    }
+
+    // Report the position rather than the type, which would print `Self` as
+    // the placeholder it was instantiated with.
+    for (i, ty) in arg_tys.iter().enumerate() {
+        if mentions(ty, self_ty) {
+            bail!("argument {} of the method mentions `Self`; add `where Self: Sized` to exclude it from trait objects", i + 2);
+        }
+    }
+    if mentions(&output_ty, self_ty) {
+        bail!("the return type of the method mentions `Self`; add `where Self: Sized` to exclude it from trait objects");
+    }
+    Ok(())
+}
+
+fn find_trait<'p>(program: &'p Program, trait_id: &TraitId) -> Fallible<&'p Trait> {
+    let Some(t) = program.items_from_all_crates().find_map(|item| match item {
+        CrateItem::Trait(t) if t.id == *trait_id => Some(t),
+        _ => None,
+    }) else {
+        bail!("no trait named `{trait_id:?}`");
+    };
+    Ok(t)
+}
+
+fn instantiate_trait(program: &Program, trait_id: &TraitId) -> Fallible<(Env, Ty, TraitBoundData)> {
+    let Trait { binder, .. } = find_trait(program, trait_id)?;
+    let (env, vars) = Env::default().universal_substitution(&binder.explicit_binder);
+    let data = binder.instantiate_with(&vars)?;
+    let self_ty: Ty = vars[0].upcast();
+    Ok((env, self_ty, data))
+}
+
+fn trait_fns(trait_items: &[TraitItem]) -> impl Iterator<Item = &Fn> {
+    trait_items.iter().filter_map(|item| match item {
+        TraitItem::Fn(f) => Some(f),
+        TraitItem::AssociatedTy(_) => None,
+    })
+}
+
+/// True if `wc` is `Self: Sized`.
+fn is_self_sized(self_ty: &Ty, wc: &WhereClause) -> bool {
+    match wc.data() {
+        WhereClauseData::IsImplemented(ty, trait_id, _) => {
+            ty == self_ty && *trait_id == TraitId::sized()
+        }
+        _ => false,
+    }
+}
+
+/// True if `ty` is `Self`, `&Self`, or `&mut Self`.
+fn is_receiver(self_ty: &Ty, ty: &Ty) -> bool {
+    if ty == self_ty {
+        return true;
+    }
+    match ty.data() {
+        TyData::RigidTy(r) => match r.name {
+            RigidName::Ref(RefKind::Shared | RefKind::Mut) => {
+                r.parameters[1].as_ty() == Some(self_ty)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn mentions(ty: &Ty, self_ty: &Ty) -> bool {
+    let Some(self_var) = self_ty.as_variable() else {
+        return false;
+    };
+    ty.free_variables().contains(&self_var)
+}
@@ -0,0 +1,54 @@
+use anyhow::bail;
+use fn_error_context::context;
+use formality_core::Downcasted;
+use formality_prove::Env;
+use formality_rust::grammar::{Fn, FnBoundData, Program};
+use formality_types::grammar::{Fallible, FnId, TraitId, Wcs};
+
+use crate::Check;
+
+/// Checks that the free function `fn_id` can be the entry point of the program,
+/// as `main` is: it takes no arguments, has no generics or where-clauses, and
+/// returns a type implementing the program's `Termination` trait.
+#[context("check_entry({fn_id:?})")]
+pub fn check_entry(program: &Program, fn_id: &FnId) -> Fallible<()> {
+    let program = program.elaborate_sized();
+    let Some(f) = program
+        .items_from_all_crates()
+        .downcasted::<Fn>()
+        .find(|f| f.id == *fn_id)
+    else {
+        bail!("no function named `{fn_id:?}`");
+    };
+
+    if !f.binder.kinds().is_empty() {
+        bail!("the entry function must not be generic");
+    }
+    let mut env = Env::default();
+    let FnBoundData {
+        input_tys,
+        output_ty,
+        where_clauses,
+        body: _,
+    } = env.instantiate_universally(&f.binder);
+    if !input_tys.is_empty() {
+        bail!("the entry function must not take arguments");
+    }
+    if !where_clauses.is_empty() {
+        bail!("the entry function must not have where-clauses");
+    }
+
+    let decls = program.to_prove_decls();
+    if !decls.has_trait_decl(&TraitId::termination()) {
+        bail!("no trait named `Termination`");
+    }
+    Check {
+        program: &program,
+        decls: &decls,
+    }
+    .prove_goal(
+        &env,
+        Wcs::t(),
+        TraitId::termination().with(&output_ty, ()).is_implemented(),
+    )
+}
@@ -2,12 +2,12 @@ use anyhow::bail;
 
 use fn_error_context::context;
 use formality_core::Downcasted;
-use formality_prove::{Env, Safety};
+use formality_prove::{Defaultness, Env, Safety};
 use formality_rust::{
     grammar::{
-        AssociatedTy, AssociatedTyBoundData, AssociatedTyValue, AssociatedTyValueBoundData, Fn,
-        FnBoundData, ImplItem, NegTraitImpl, NegTraitImplBoundData, Trait, TraitBoundData,
-        TraitImpl, TraitImplBoundData, TraitItem,
+        AssociatedTy, AssociatedTyBoundData, AssociatedTyValue, AssociatedTyValueBoundData,
+        CrateItem, Fn, FnBoundData, ImplItem, MaybeFnBody, NegTraitImpl, NegTraitImplBoundData,
+        Trait, TraitBoundData, TraitImpl, TraitImplBoundData, TraitItem,
     },
     prove::ToWcs,
 };
@@ -19,7 +19,7 @@ use formality_types::{
 impl super::Check<'_> {
     #[context("check_trait_impl({trait_impl:?})")]
     pub(super) fn check_trait_impl(&self, trait_impl: &TraitImpl) -> Fallible<()> {
-        let TraitImpl { binder, safety: _ } = trait_impl;
+        let TraitImpl { binder, .. } = trait_impl;
 
         let mut env = Env::default();
 
@@ -51,6 +51,59 @@ impl super::Check<'_> {
             self.check_trait_impl_item(&env, &where_clauses, &trait_items, impl_item)?;
         }
 
+        self.check_impl_complete(trait_impl, &trait_items)?;
+
+        Ok(())
+    }
+
+    /// Validate that the impl provides every item of the trait that has no default.
+    /// A `default impl` may be partial; an impl that specializes it inherits its items.
+    fn check_impl_complete(
+        &self,
+        trait_impl: &TraitImpl,
+        trait_items: &[TraitItem],
+    ) -> Fallible<()> {
+        if trait_impl.defaultness == Defaultness::Default {
+            return Ok(());
+        }
+
+        let specialized_impls: Vec<&TraitImpl> = self
+            .program
+            .items_from_all_crates()
+            .filter_map(|item| match item {
+                CrateItem::TraitImpl(general) => Some(general),
+                _ => None,
+            })
+            .filter(|general| {
+                general.trait_id() == trait_impl.trait_id() && self.specializes(trait_impl, general)
+            })
+            .collect();
+        let provides = |trait_item: &TraitItem| {
+            std::iter::once(trait_impl)
+                .chain(specialized_impls.iter().copied())
+                .flat_map(|i| &i.binder.peek().impl_items)
+                .any(|impl_item| match (trait_item, impl_item) {
+                    (TraitItem::Fn(t), ImplItem::Fn(i)) => t.id == i.id,
+                    (TraitItem::AssociatedTy(t), ImplItem::AssociatedTyValue(i)) => t.id == i.id,
+                    _ => false,
+                })
+        };
+
+        let missing: Vec<String> = trait_items
+            .iter()
+            .filter(|trait_item| !has_default(trait_item) && !provides(trait_item))
+            .map(|trait_item| match trait_item {
+                TraitItem::Fn(f) => format!("fn `{:?}`", f.id),
+                TraitItem::AssociatedTy(t) => format!("type `{:?}`", t.id),
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            bail!(
+                "not all trait items implemented, missing: {}",
+                missing.join(", ")
+            );
+        }
         Ok(())
     }
 
@@ -280,3 +333,11 @@ impl super::Check<'_> {
         ))
     }
 }
+
+/// True if the impl may omit `trait_item` because the trait supplies a default.
+fn has_default(trait_item: &TraitItem) -> bool {
+    match trait_item {
+        TraitItem::Fn(f) => f.binder.peek().body != MaybeFnBody::NoFnBody,
+        TraitItem::AssociatedTy(_) => false,
+    }
+}
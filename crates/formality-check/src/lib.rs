@@ -13,7 +13,7 @@ use formality_types::grammar::{Fallible, Wcs};
 /// Check all crates in the program. The crates must be in dependency order
 /// such that any prefix of the crates is a complete program.
 pub fn check_all_crates(program: &Program) -> Fallible<()> {
-    let Program { crates } = program;
+    let Program { crates } = &program.elaborate_sized();
     let mut crates: VecDeque<_> = crates.iter().cloned().collect();
 
     let mut prefix_program = Program { crates: vec![] };
@@ -37,11 +37,33 @@ fn check_current_crate(program: &Program) -> Fallible<()> {
 
 mod adts;
 mod coherence;
+mod dyn_compat;
+mod entry;
 mod fns;
 mod impls;
+mod methods;
+mod perfect_derive;
 mod traits;
+mod unreachable_impls;
+mod wf_report;
+mod what_if;
 mod where_clauses;
 
+pub use coherence::{
+    check_all_coherence, impls_overlap, mutually_exclusive, select_impl, CoherenceReport,
+    CoherenceViolation, Overlap,
+};
+pub use dyn_compat::{check_dyn_compatible, check_dyn_ty, dyn_callable_methods};
+pub use entry::check_entry;
+pub use methods::{
+    deref_chain, dyn_method_candidate, method_candidates, DerefChain, MethodCandidate,
+    MAX_AUTODEREFS,
+};
+pub use perfect_derive::{perfect_derive, PerfectDerive};
+pub use unreachable_impls::lint_unreachable_impls;
+pub use wf_report::{check_program_wf, WfFailure, WfReport};
+pub use what_if::{what_if, WhatIf};
+
 struct Check<'p> {
     program: &'p Program,
     decls: &'p Decls,
@@ -75,12 +97,16 @@ impl Check<'_> {
         Ok(())
     }
 
+    // FIXME: trait objects are not yet types (see `DynTy`), so no item can
+    // mention one; once they can, each `dyn` type in an item must pass
+    // `check_dyn_ty`.
     fn check_crate_item(&self, c: &CrateItem) -> Fallible<()> {
         match c {
             CrateItem::Trait(v) => self.check_trait(v),
             CrateItem::TraitImpl(v) => self.check_trait_impl(v),
             CrateItem::Struct(s) => self.check_adt(&s.to_adt()),
             CrateItem::Enum(e) => self.check_adt(&e.to_adt()),
+            CrateItem::Union(u) => self.check_union(u),
             CrateItem::Fn(f) => self.check_free_fn(f),
             CrateItem::NegTraitImpl(i) => self.check_neg_trait_impl(i),
             CrateItem::Test(t) => self.check_test(t),
@@ -0,0 +1,273 @@
+use formality_core::{Set, Upcast};
+use formality_prove::Env;
+use formality_rust::{
+    grammar::{CrateItem, FnBoundData, Program, Trait, TraitBoundData, TraitItem},
+    prove::ToWcs,
+};
+use formality_types::grammar::{
+    AliasTy, AssociatedItemId, DynTy, Fallible, FnId, Lt, ParameterKind, RefKind, Relation,
+    RigidName, TraitId, TraitRef, Ty, TyData, Variable, Wcs,
+};
+use itertools::Itertools;
+
+use crate::dyn_compat::{check_dyn_ty, dyn_callable_methods};
+
+/// A trait method that a method call could resolve to, found by [`method_candidates`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodCandidate {
+    /// The number of times the receiver is dereferenced.
+    pub autoderefs: usize,
+
+    /// The reference taken to the dereferenced receiver, if any.
+    pub autoref: Option<RefKind>,
+
+    /// The trait ref that the call relies on, which holds under the given assumptions.
+    /// It may still contain inference variables for trait parameters that the
+    /// receiver does not determine.
+    pub trait_ref: TraitRef,
+
+    /// True if the solver could not tell whether `trait_ref` holds, e.g.
+    /// because it depends on inference variables not yet known; the candidate
+    /// may then turn out not to apply.
+    pub ambiguous: bool,
+}
+
+/// The types that a receiver dereferences to, found by [`deref_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerefChain {
+    /// The types, in order and starting with the receiver itself.
+    pub tys: Vec<Ty>,
+
+    /// True if the last type may dereference further, but to a type that is
+    /// not known (yet), e.g. because `Deref` for it is ambiguous.
+    pub ambiguous: bool,
+}
+
+/// The trait methods named `method_name` that apply to a receiver of type
+/// `receiver_ty`, in the order method resolution probes them: for each step
+/// of dereferencing the receiver (through `&` and `&mut`), first the receiver
+/// itself, then `&` of it, then `&mut` of it. A method applies if its first
+/// argument type can be equated with the adjusted receiver and its trait is
+/// implemented for the resulting `Self` type; the first candidate is the one
+/// a call would pick, unless it is ambiguous.
+///
+/// Inherent methods are not modeled, so only trait methods are considered.
+pub fn method_candidates(
+    program: &Program,
+    env: &Env,
+    assumptions: impl ToWcs,
+    receiver_ty: &Ty,
+    method_name: &FnId,
+) -> Vec<MethodCandidate> {
+    let decls = program.to_prove_decls();
+    let assumptions: Wcs = assumptions.to_wcs();
+    let traits: Vec<&Trait> = program
+        .items_from_all_crates()
+        .filter_map(|item| match item {
+            CrateItem::Trait(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+
+    let mut candidates = vec![];
+    for (autoderefs, ty) in deref_chain_in(&decls, env, &assumptions, receiver_ty)
+        .tys
+        .into_iter()
+        .enumerate()
+    {
+        for autoref in [None, Some(RefKind::Shared), Some(RefKind::Mut)] {
+            for trait_decl in &traits {
+                for (trait_ref, ambiguous) in applicable_trait_refs(
+                    &decls,
+                    env,
+                    &assumptions,
+                    trait_decl,
+                    &ty,
+                    &autoref,
+                    method_name,
+                ) {
+                    let candidate = MethodCandidate {
+                        autoderefs,
+                        autoref: autoref.clone(),
+                        trait_ref,
+                        ambiguous,
+                    };
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// The trait method named `method_name` that a call on a receiver of type
+/// `&dyn_ty` (or `&mut dyn_ty`) resolves to, if any: the method of that name
+/// on the principal trait, provided it can be called on a trait object (see
+/// [`dyn_callable_methods`]). Fails if `dyn_ty` is not well-formed.
+///
+/// Trait objects are not yet types (see [`DynTy`]), so [`method_candidates`]
+/// cannot find these. Methods of supertraits are not considered.
+pub fn dyn_method_candidate(
+    program: &Program,
+    dyn_ty: &DynTy,
+    method_name: &FnId,
+) -> Fallible<Option<TraitId>> {
+    check_dyn_ty(program, dyn_ty)?;
+    let methods = dyn_callable_methods(program, &dyn_ty.principal_id)?;
+    Ok(methods
+        .contains(method_name)
+        .then(|| dyn_ty.principal_id.clone()))
+}
+
+/// The types that `ty` dereferences to, in order and starting with `ty` itself:
+/// through `&` and `&mut`, and through `<T as Deref>::Target` if the program
+/// has a `Deref` trait. The chain ends at a type that does not dereference
+/// (or whose target is not known, see [`DerefChain::ambiguous`]), when a type
+/// would repeat, or after
+/// [`MAX_AUTODEREFS`] steps, as a `Deref` impl may keep expanding the type.
+pub fn deref_chain(program: &Program, env: &Env, assumptions: impl ToWcs, ty: &Ty) -> DerefChain {
+    deref_chain_in(&program.to_prove_decls(), env, &assumptions.to_wcs(), ty)
+}
+
+/// The most times [`deref_chain`] dereferences a type.
+pub const MAX_AUTODEREFS: usize = 8;
+
+fn deref_chain_in(
+    decls: &formality_prove::Decls,
+    env: &Env,
+    assumptions: &Wcs,
+    ty: &Ty,
+) -> DerefChain {
+    let mut tys = vec![ty.clone()];
+    while tys.len() <= MAX_AUTODEREFS {
+        match deref(decls, env, assumptions, tys.last().unwrap()) {
+            Deref::To(next) if !tys.contains(&next) => tys.push(next),
+            Deref::To(_) | Deref::Nothing => break,
+            Deref::Unknown => {
+                return DerefChain {
+                    tys,
+                    ambiguous: true,
+                }
+            }
+        }
+    }
+    DerefChain {
+        tys,
+        ambiguous: false,
+    }
+}
+
+/// If `trait_decl` has a method `method_name` that takes `ty` (after applying
+/// `autoref`) as its receiver, the trait refs under which it does so, each with
+/// whether it only may hold.
+fn applicable_trait_refs(
+    decls: &formality_prove::Decls,
+    env: &Env,
+    assumptions: &Wcs,
+    trait_decl: &Trait,
+    ty: &Ty,
+    autoref: &Option<RefKind>,
+    method_name: &FnId,
+) -> Vec<(TraitRef, bool)> {
+    let (env, trait_vars) = env.existential_substitution(&trait_decl.binder.explicit_binder);
+    let TraitBoundData {
+        where_clauses: _,
+        trait_items,
+    } = trait_decl.binder.instantiate_with(&trait_vars).unwrap();
+    let Some(method) = trait_items.into_iter().find_map(|item| match item {
+        TraitItem::Fn(f) if f.id == *method_name => Some(f),
+        _ => None,
+    }) else {
+        return vec![];
+    };
+
+    let (mut env, fn_vars) = env.existential_substitution(&method.binder);
+    let FnBoundData { input_tys, .. } = method.binder.instantiate_with(&fn_vars).unwrap();
+    let Some(receiver) = input_tys.first() else {
+        return vec![];
+    };
+
+    let adjusted = match autoref {
+        None => ty.clone(),
+        Some(kind) => {
+            let lt: Lt = env.fresh_existential(ParameterKind::Lt).upcast();
+            match kind {
+                RefKind::Shared => ty.ref_ty(lt),
+                RefKind::Mut => ty.ref_mut_ty(lt),
+            }
+        }
+    };
+
+    let trait_ref = trait_decl.id.with(&trait_vars[0], &trait_vars[1..]);
+    let goal = (
+        Relation::equals(receiver, &adjusted),
+        trait_ref.is_implemented(),
+    )
+        .to_wcs();
+    formality_prove::prove(decls, env, assumptions, goal)
+        .iter()
+        // The solution binds the variables introduced here, fixing `Self`.
+        .map(|c| (c.substitution().apply(&trait_ref), !c.known_true))
+        .collect()
+}
+
+/// What a type dereferences to, found by [`deref`].
+enum Deref {
+    To(Ty),
+    Nothing,
+    Unknown,
+}
+
+/// The type that `ty` dereferences to: its referent if it is a reference, or
+/// otherwise the one type `<ty as Deref>::Target` normalizes to, if any.
+fn deref(decls: &formality_prove::Decls, env: &Env, assumptions: &Wcs, ty: &Ty) -> Deref {
+    if let TyData::RigidTy(r) = ty.data() {
+        if matches!(r.name, RigidName::Ref(_)) {
+            return match r.parameters[1].as_ty() {
+                Some(referent) => Deref::To(referent.clone()),
+                None => Deref::Nothing,
+            };
+        }
+    }
+
+    if !decls.has_trait_decl(&TraitId::deref()) {
+        return Deref::Nothing;
+    }
+    let mut env = env.clone();
+    let target: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let alias: Ty = AliasTy::associated_ty(
+        TraitId::deref(),
+        AssociatedItemId::deref_target(),
+        0,
+        vec![ty],
+    )
+    .upcast();
+    let goal = (
+        TraitId::deref().with(ty, ()).is_implemented(),
+        Relation::equals(&alias, &target),
+    )
+        .to_wcs();
+    let solutions = formality_prove::prove(decls, env, assumptions, goal);
+    if !solutions.is_proven() {
+        return Deref::Nothing;
+    }
+
+    // The alias itself is always a solution, which says nothing. Otherwise, a
+    // target is only known if the solution is, and if it is not unconstrained.
+    let targets: Set<Option<Ty>> = solutions
+        .iter()
+        .map(|c| (c, c.substitution().apply(&target)))
+        .filter(|(_, t)| *t != alias)
+        .map(|(c, t)| {
+            let unconstrained = matches!(t.as_variable(), Some(Variable::ExistentialVar(_)));
+            (c.known_true && !unconstrained).then_some(t)
+        })
+        .collect();
+    match targets.into_iter().at_most_one() {
+        Ok(None) => Deref::Nothing,
+        Ok(Some(Some(t))) => Deref::To(t),
+        _ => Deref::Unknown,
+    }
+}
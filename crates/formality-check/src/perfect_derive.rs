@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use anyhow::bail;
+use fn_error_context::context;
+use formality_core::Upcast;
+use formality_prove::Env;
+use formality_rust::{
+    grammar::{
+        AdtBoundData, Field, Program, TraitImpl, TraitImplBoundData, Variant, WhereClause,
+        WhereClauseData,
+    },
+    prove::ToWcs,
+};
+use formality_types::grammar::{
+    AdtId, Binder, Fallible, Parameter, ParameterKind, TraitId, Ty, Wcs,
+};
+
+/// The impls that `#[derive(Trait)]` could generate for an ADT, found by
+/// [`perfect_derive`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerfectDerive {
+    /// The impl `#[derive]` generates: each type parameter must implement the trait.
+    pub conservative: TraitImpl,
+
+    /// The "perfect derive" impl: the type of each field must implement the trait.
+    pub perfect: TraitImpl,
+
+    /// The conservative impl keeping only the parameter bounds without which
+    /// the field types cannot be shown to implement the trait. If it equals
+    /// `conservative`, every bound of the conservative impl is necessary.
+    pub minimal: TraitImpl,
+}
+
+/// Compares the conservative and the "perfect derive" impls of `trait_id` for
+/// the ADT `adt_id`. Both keep the ADT's own where-clauses, including the
+/// implicit `Sized` bounds if the program declares `Sized`. A bound `T: Trait`
+/// on a type parameter is necessary if the field types' bounds cannot be
+/// proven without it.
+#[context("perfect_derive({adt_id:?}, {trait_id:?})")]
+pub fn perfect_derive(
+    program: &Program,
+    adt_id: &AdtId,
+    trait_id: &TraitId,
+) -> Fallible<PerfectDerive> {
+    let program = program.elaborate_sized();
+    program.trait_named(trait_id)?;
+    let Some(adt) = program.adts_from_all_crates().find(|adt| adt.id == *adt_id) else {
+        bail!("no ADT named `{adt_id:?}`");
+    };
+
+    let (env, vars) = Env::default().universal_substitution(&adt.binder);
+    let AdtBoundData {
+        defaults: _,
+        where_clauses,
+        variants,
+    } = adt.binder.instantiate_with(&vars)?;
+
+    let bound = |ty: Ty| WhereClause {
+        data: Arc::new(WhereClauseData::IsImplemented(ty, trait_id.clone(), vec![])),
+    };
+    let param_bounds: Vec<WhereClause> = vars
+        .iter()
+        .filter(|v| v.kind == ParameterKind::Ty)
+        .map(|&v| bound(v.upcast()))
+        .collect();
+    let mut field_bounds: Vec<WhereClause> = vec![];
+    for Variant { name: _, fields } in &variants {
+        for Field { name: _, ty } in fields {
+            let wc = bound(ty.clone());
+            if !field_bounds.contains(&wc) {
+                field_bounds.push(wc);
+            }
+        }
+    }
+
+    let decls = program.to_prove_decls();
+    let field_goals: Wcs = field_bounds.to_wcs();
+    let necessary: Vec<WhereClause> = param_bounds
+        .iter()
+        .filter(|&b| {
+            let others: Vec<WhereClause> =
+                param_bounds.iter().filter(|o| *o != b).cloned().collect();
+            let assumptions: Wcs = (&where_clauses, &others).to_wcs();
+            !formality_prove::prove(&decls, &env, assumptions, &field_goals)
+                .iter()
+                .any(|c| c.unconditionally_true())
+        })
+        .cloned()
+        .collect();
+
+    let self_ty = Ty::rigid(
+        adt_id,
+        vars.iter().map(|&v| v.upcast()).collect::<Vec<Parameter>>(),
+    );
+    let derived_impl = |bounds: Vec<WhereClause>| TraitImpl {
+        defaultness: Default::default(),
+        safety: Default::default(),
+        binder: Binder::new(
+            &vars,
+            TraitImplBoundData {
+                trait_id: trait_id.clone(),
+                self_ty: self_ty.clone(),
+                trait_parameters: vec![],
+                where_clauses: where_clauses.iter().cloned().chain(bounds).collect(),
+                impl_items: vec![],
+            },
+        ),
+    };
+
+    Ok(PerfectDerive {
+        conservative: derived_impl(param_bounds),
+        perfect: derived_impl(field_bounds),
+        minimal: derived_impl(necessary),
+    })
+}
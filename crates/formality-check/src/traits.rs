@@ -12,6 +12,7 @@ impl super::Check<'_> {
     pub(super) fn check_trait(&self, t: &Trait) -> Fallible<()> {
         let Trait {
             safety: _,
+            autoness: _,
             id: _,
             binder,
         } = t;
@@ -0,0 +1,30 @@
+use formality_core::Downcasted;
+use formality_prove::Env;
+use formality_rust::{
+    grammar::{Program, TraitImpl, TraitImplBoundData},
+    prove::ToWcs,
+};
+
+/// The impls of `program` that can never apply because no instantiation of
+/// their generics satisfies their where-clauses, such as an impl requiring
+/// `T: Red` and `T: Blue` when no type implements both.
+///
+/// Like [`trait_satisfiable`](formality_prove::trait_satisfiable), this is
+/// advisory: nothing in the checker rejects such an impl.
+///
+/// Implicit `Sized` bounds count (see [`Program::elaborate_sized`]), and the
+/// impls are returned with them elaborated.
+pub fn lint_unreachable_impls(program: &Program) -> Vec<TraitImpl> {
+    let program = program.elaborate_sized();
+    let decls = program.to_prove_decls();
+    program
+        .items_from_all_crates()
+        .downcasted::<TraitImpl>()
+        .filter(|trait_impl| {
+            let (env, vars) = Env::default().existential_substitution(&trait_impl.binder);
+            let TraitImplBoundData { where_clauses, .. } =
+                trait_impl.binder.instantiate_with(&vars).unwrap();
+            !formality_prove::prove(&decls, env, (), where_clauses.to_wcs()).is_proven()
+        })
+        .collect()
+}
@@ -0,0 +1,55 @@
+use formality_rust::grammar::{CrateItem, Program};
+use formality_types::grammar::CrateId;
+
+use crate::Check;
+
+/// Every ill-formed item in a program, as found by [`check_program_wf`].
+#[derive(Debug, Default)]
+pub struct WfReport {
+    pub failures: Vec<WfFailure>,
+}
+
+/// An item of crate `crate_id` that failed its check with `error`.
+#[derive(Debug)]
+pub struct WfFailure {
+    pub crate_id: CrateId,
+    pub item: CrateItem,
+    pub error: anyhow::Error,
+}
+
+/// Checks every item (ADTs, traits, impls, and fns) of every crate in
+/// `program`, collecting all failures rather than stopping at the first. As
+/// with [`crate::check_all_crates`], each crate is checked against the prefix
+/// of the program that it can see. Items are checked independently, so an
+/// item that relies on an ill-formed one is only reported if it is itself at
+/// fault. Coherence is checked separately, by [`crate::check_all_coherence`],
+/// and `test` items are not declarations, so they are skipped.
+pub fn check_program_wf(program: &Program) -> WfReport {
+    let Program { crates } = &program.elaborate_sized();
+    let mut report = WfReport::default();
+
+    let mut prefix_program = Program { crates: vec![] };
+    for c in crates {
+        prefix_program.crates.push(c.clone());
+        let decls = prefix_program.to_prove_decls();
+        let check = Check {
+            program: &prefix_program,
+            decls: &decls,
+        };
+
+        for item in &c.items {
+            if let CrateItem::Test(_) = item {
+                continue;
+            }
+            if let Err(error) = check.check_crate_item(item) {
+                report.failures.push(WfFailure {
+                    crate_id: c.id.clone(),
+                    item: item.clone(),
+                    error,
+                });
+            }
+        }
+    }
+
+    report
+}
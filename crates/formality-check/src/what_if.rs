@@ -0,0 +1,62 @@
+use formality_core::{ProvenSet, Upcast};
+use formality_prove::{Constraints, Env};
+use formality_rust::grammar::{Program, TraitImpl};
+use formality_types::grammar::Wcs;
+
+use crate::Check;
+
+/// The outcome of a [`what_if`] query.
+#[derive(Debug)]
+pub struct WhatIf {
+    /// Solutions to the goal in the original program.
+    pub before: ProvenSet<Constraints>,
+
+    /// Solutions to the goal once the impls have been added.
+    pub after: ProvenSet<Constraints>,
+
+    /// Set if the program with the added impls fails the coherence (orphan and
+    /// overlap) checks for its last crate.
+    pub coherence_error: Option<anyhow::Error>,
+}
+
+/// Proves `goal` against `program` both as it is and with `added` impls appended
+/// to its last crate, for comparing how the impls would change the answer.
+/// The original program is left untouched.
+pub fn what_if(
+    program: &Program,
+    added: Vec<TraitImpl>,
+    env: impl Upcast<Env>,
+    assumptions: impl Upcast<Wcs>,
+    goal: impl Upcast<Wcs>,
+) -> WhatIf {
+    let env: Env = env.upcast();
+    let assumptions: Wcs = assumptions.upcast();
+    let goal: Wcs = goal.upcast();
+
+    let mut extended = program.clone();
+    if let Some(last_crate) = extended.crates.last_mut() {
+        last_crate.items.extend(added.iter().map(|i| i.upcast()));
+    }
+    let program = program.elaborate_sized();
+    let extended = extended.elaborate_sized();
+
+    let before = formality_prove::prove(program.to_prove_decls(), &env, &assumptions, &goal);
+
+    let decls = extended.to_prove_decls();
+    let after = formality_prove::prove(&decls, &env, &assumptions, &goal);
+
+    let check = Check {
+        program: &extended,
+        decls: &decls,
+    };
+    let coherence_error = extended
+        .crates
+        .last()
+        .and_then(|c| check.check_coherence(c).err());
+
+    WhatIf {
+        before,
+        after,
+        coherence_error,
+    }
+}
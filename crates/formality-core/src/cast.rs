@@ -1,3 +1,4 @@
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::collections::Set;
@@ -51,8 +52,46 @@ pub trait Downcast: Sized {
     {
         self.downcast::<T>().is_some()
     }
+
+    /// Like [`Downcast::downcast`], but on failure says what was found instead.
+    fn try_downcast<T>(&self) -> Result<T, DowncastError>
+    where
+        T: DowncastFrom<Self>,
+        Self: Debug,
+    {
+        self.downcast::<T>().ok_or_else(|| DowncastError {
+            expected: std::any::type_name::<T>(),
+            actual: std::any::type_name::<Self>(),
+            value: format!("{self:?}"),
+        })
+    }
 }
 
+/// The error from [`Downcast::try_downcast`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DowncastError {
+    /// The type that was asked for.
+    pub expected: &'static str,
+
+    /// The type of the value that was downcast.
+    pub actual: &'static str,
+
+    /// The value that was downcast, as printed by `Debug`.
+    pub value: String,
+}
+
+impl std::fmt::Display for DowncastError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected a `{}`, found `{}` of type `{}`",
+            self.expected, self.value, self.actual
+        )
+    }
+}
+
+impl std::error::Error for DowncastError {}
+
 impl<U> Downcast for U {
     fn downcast<T>(&self) -> Option<T>
     where
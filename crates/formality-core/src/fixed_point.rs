@@ -1,11 +1,20 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::thread::LocalKey;
 
+use crate::scoped::with_scoped;
+use crate::Map;
+
 mod stack;
 pub use stack::FixedPointStack;
 
+/// Computes the fixed point of `next_value` for `args`.
+///
+/// This has no way to report giving up, so it ignores the iteration limit
+/// installed with [`with_iteration_limit`], which therefore only applies to
+/// judgments (and other users of [`fixed_point_with_limit`]), not to functions
+/// marked `#[fixed_point]`.
 pub fn fixed_point<Input, Output>(
     tracing_span: impl Fn(&Input) -> tracing::Span,
     storage: &'static LocalKey<RefCell<FixedPointStack<Input, Output>>>,
@@ -17,24 +26,151 @@ where
     Input: Value,
     Output: Value,
 {
-    stacker::maybe_grow(32 * 1024, 1024 * 1024, || {
-        FixedPoint {
-            tracing_span,
-            storage,
-            default_value,
-            next_value,
+    let fixed_point = FixedPoint {
+        name: std::any::type_name::<Input>(),
+        tracing_span,
+        storage,
+        recursion_limit: None,
+        iteration_limit: None,
+        default_value,
+        next_value,
+    };
+    match fixed_point.run(args) {
+        Ok(output) => output,
+        Err(limit) => unreachable!("no limit was set, yet {limit:?} was reached"),
+    }
+}
+
+/// Why [`fixed_point_with_limit`] gave up on an input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FixedPointLimit {
+    /// The input was not already being computed and `limit` others were.
+    Recursion { limit: usize },
+
+    /// The output was still changing after `limit` iterations, the limit
+    /// installed with [`with_iteration_limit`].
+    Iterations { limit: usize },
+}
+
+thread_local! {
+    static ITERATION_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Counters describing the work done computing fixed points, see [`with_stats`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of times the next value was computed, counting each re-execution
+    /// needed to reach a fixed point.
+    pub iterations: u64,
+
+    /// Number of calls whose input was already being computed, and which
+    /// therefore yielded the current value rather than recursing.
+    pub cycle_hits: u64,
+
+    /// Number of calls that started a new computation.
+    pub cache_misses: u64,
+
+    /// The largest number of inputs under computation at once.
+    pub max_depth: usize,
+
+    /// Number of calls that gave up because a recursion or iteration limit was
+    /// reached (see [`FixedPointLimit`]).
+    pub limits_reached: u64,
+}
+
+thread_local! {
+    /// The stats recorded by the innermost [`with_stats`], if any.
+    static STATS: RefCell<Option<Map<&'static str, Stats>>> = const { RefCell::new(None) };
+
+    static CYCLE_HITS: Cell<u64> = const { Cell::new(0) };
+    static LIMITS_REACHED: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The number of calls on this thread so far whose input was already being
+/// computed, and which therefore yielded a provisional value (see
+/// [`Stats::cycle_hits`]). A computation during which this does not change
+/// depends on no provisional value, so its result is final and may be reused.
+pub fn cycle_hits() -> u64 {
+    CYCLE_HITS.with(|c| c.get())
+}
+
+/// The number of calls on this thread so far that gave up because a recursion
+/// or iteration limit was reached (see [`Stats::limits_reached`]). A
+/// computation during which this changes may have missed results that the
+/// calls which gave up would have produced, so its result overflowed even if
+/// it is not a failure.
+pub fn limits_reached() -> u64 {
+    LIMITS_REACHED.with(|c| c.get())
+}
+
+fn record_stats(name: &'static str, op: impl FnOnce(&mut Stats)) {
+    STATS.with(|s| {
+        if let Some(stats) = s.borrow_mut().as_mut() {
+            op(stats.entry(name).or_default())
         }
-        .apply(args)
     })
 }
 
+/// Runs `op`, returning its result along with the [`Stats`] of the fixed points
+/// it computed on this thread, keyed by name (for judgments, the name of the
+/// judgment). Stats are only recorded within this scope; those of an inner
+/// `with_stats` are not also counted by the outer one.
+pub fn with_stats<R>(op: impl FnOnce() -> R) -> (R, Map<&'static str, Stats>) {
+    with_scoped(&STATS, Some(Map::new()), || {
+        let result = op();
+        let stats = STATS.with(|s| s.borrow_mut().take()).unwrap_or_default();
+        (result, stats)
+    })
+}
+
+/// Runs `op` such that any fixed point computed on this thread with
+/// [`fixed_point_with_limit`] (e.g., any judgment) gives up once it
+/// has run `limit` iterations for one input without its output settling,
+/// restoring the previous limit (if any) afterwards. This turns rules that
+/// accidentally diverge into errors rather than an endless loop.
+pub fn with_iteration_limit<R>(limit: usize, op: impl FnOnce() -> R) -> R {
+    with_scoped(&ITERATION_LIMIT, Some(limit), op)
+}
+
+/// Like [`fixed_point`], but returns an error rather than recursing if `args` is not
+/// already being computed and `storage` already holds `recursion_limit` entries,
+/// or if the iteration limit (see [`with_iteration_limit`]) is exceeded.
+/// Within [`with_stats`], the work done is recorded in the [`Stats`] for `name`.
+pub fn fixed_point_with_limit<Input, Output>(
+    name: &'static str,
+    tracing_span: impl Fn(&Input) -> tracing::Span,
+    storage: &'static LocalKey<RefCell<FixedPointStack<Input, Output>>>,
+    recursion_limit: Option<usize>,
+    args: Input,
+    default_value: impl Fn(&Input) -> Output,
+    next_value: impl FnMut(Input) -> Output,
+) -> Result<Output, FixedPointLimit>
+where
+    Input: Value,
+    Output: Value,
+{
+    FixedPoint {
+        name,
+        tracing_span,
+        storage,
+        recursion_limit,
+        iteration_limit: ITERATION_LIMIT.with(|l| l.get()),
+        default_value,
+        next_value,
+    }
+    .run(args)
+}
+
 struct FixedPoint<Input, Output, DefaultValue, NextValue, TracingSpan>
 where
     Input: Value,
     Output: Value,
 {
+    name: &'static str,
     tracing_span: TracingSpan,
     storage: &'static LocalKey<RefCell<FixedPointStack<Input, Output>>>,
+    recursion_limit: Option<usize>,
+    iteration_limit: Option<usize>,
     default_value: DefaultValue,
     next_value: NextValue,
 }
@@ -51,30 +187,64 @@ where
     NextValue: FnMut(Input) -> Output,
     TracingSpan: Fn(&Input) -> tracing::Span,
 {
-    fn apply(&mut self, input: Input) -> Output {
+    fn run(mut self, input: Input) -> Result<Output, FixedPointLimit> {
+        stacker::maybe_grow(32 * 1024, 1024 * 1024, || self.apply(input))
+    }
+
+    fn apply(&mut self, input: Input) -> Result<Output, FixedPointLimit> {
         if let Some(r) = self.with_stack(|stack| stack.search(&input)) {
             tracing::debug!("recursive call to {:?}, yielding {:?}", input, r);
-            return r;
+            CYCLE_HITS.with(|c| c.set(c.get() + 1));
+            record_stats(self.name, |stats| stats.cycle_hits += 1);
+            return Ok(r);
+        }
+
+        if let Some(limit) = self.recursion_limit {
+            if self.with_stack(|stack| stack.depth()) >= limit {
+                tracing::debug!("recursion limit of {} reached for {:?}", limit, input);
+                LIMITS_REACHED.with(|c| c.set(c.get() + 1));
+                record_stats(self.name, |stats| stats.limits_reached += 1);
+                return Err(FixedPointLimit::Recursion { limit });
+            }
         }
 
-        self.with_stack(|stack| {
+        let mut iterations = 0;
+
+        let depth = self.with_stack(|stack| {
             let default_value = (self.default_value)(&input);
             stack.push(&input, default_value);
+            stack.depth()
+        });
+        record_stats(self.name, |stats| {
+            stats.cache_misses += 1;
+            stats.max_depth = stats.max_depth.max(depth);
         });
 
         loop {
             let span = (self.tracing_span)(&input);
             let _guard = span.enter();
             let output = (self.next_value)(input.clone());
+            record_stats(self.name, |stats| stats.iterations += 1);
             tracing::debug!(?output);
             if !self.with_stack(|stack| stack.update_output(&input, output)) {
                 break;
             } else {
                 tracing::debug!("output is different from previous iteration, re-executing until fixed point is reached");
             }
+
+            iterations += 1;
+            if let Some(limit) = self.iteration_limit {
+                if iterations >= limit {
+                    tracing::debug!("iteration limit of {} reached for {:?}", limit, input);
+                    self.with_stack(|stack| stack.pop(&input));
+                    LIMITS_REACHED.with(|c| c.set(c.get() + 1));
+                    record_stats(self.name, |stats| stats.limits_reached += 1);
+                    return Err(FixedPointLimit::Iterations { limit });
+                }
+            }
         }
 
-        self.with_stack(|stack| stack.pop(&input))
+        Ok(self.with_stack(|stack| stack.pop(&input)))
     }
 
     fn with_stack<R>(&self, f: impl FnOnce(&mut FixedPointStack<Input, Output>) -> R) -> R {
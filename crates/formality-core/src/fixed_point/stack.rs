@@ -56,6 +56,11 @@ where
         None
     }
 
+    /// Number of inputs currently being evaluated.
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+
     /// Push an entry onto the stack, indicating it is currently being evaluated.
     /// There must not already be an entry for `input`.
     pub fn push(&mut self, input: &Input, output: Output) {
@@ -1,15 +1,125 @@
 use std::cell::RefCell;
 
-use crate::{fixed_point::FixedPointStack, Set};
+use crate::{fixed_point::FixedPointStack, Set, Upcast};
 
 mod proven_set;
 pub use proven_set::{FailedJudgment, FailedRule, ProvenSet, RuleFailureCause, TryIntoIter};
 
+mod chunks;
+pub use chunks::ChunkBy;
+
+mod collector;
+pub use collector::{
+    current_judgment_depth, failure_collector_installed, matched_rules, rule_matched,
+    with_failure_collector, CollectedFailure, FailureCollector, JudgmentInProgress,
+};
+
+mod filter;
+pub use filter::{rule_allowed, with_rule_filter, RuleFilter};
+
+mod derivation;
+pub use derivation::{
+    recorded_derivations, to_dot, with_derivation_recording, Premise, RuleApplication,
+    RuleDerivation,
+};
+
+mod fuel;
+pub use fuel::{consume_fuel, ran_out_of_fuel, with_fuel};
+
+mod hooks;
+pub use hooks::hooks_installed;
+
+mod render;
+pub use render::{with_failure_renderer, DefaultFailureRenderer, FailureRenderer};
+
+mod shuffle;
+pub use shuffle::{
+    force_selection, record_selection, shuffle_seed, shuffled, with_shuffle_seed, SelectionTrace,
+    Shuffled,
+};
+
+mod test_and_then;
+mod test_chunks;
+mod test_context;
+mod test_cut;
+mod test_cycles;
+mod test_depth;
+mod test_derivation;
+mod test_failure_collector;
+mod test_failure_renderer;
 mod test_filtered;
+mod test_for;
+mod test_fuel;
+mod test_into_result;
+mod test_iteration_limit;
+mod test_minimize;
+mod test_not;
+mod test_pattern_mismatch;
 mod test_reachable;
+mod test_recursion_limit;
+mod test_require;
+mod test_rule_categories;
+mod test_selection_trace;
+mod test_serde;
+mod test_shuffle;
+mod test_snapshot;
+mod test_stats;
+mod test_trivial_each;
+mod test_trivial_tagged;
+mod test_unlabeled_rules;
 
 pub type JudgmentStack<J, O> = RefCell<FixedPointStack<J, Set<O>>>;
 
+/// Describes a judgment as declared in `judgment_fn!`.
+///
+/// For a judgment `foo`, the macro generates `foo_metadata()` returning this.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JudgmentMetadata {
+    /// Name of the judgment fn.
+    pub name: &'static str,
+
+    /// Names of all of its inputs, in order.
+    pub input_names: &'static [&'static str],
+
+    /// Names of the inputs listed in `debug(...)`, in that order.
+    pub debug_input_names: &'static [&'static str],
+
+    /// Number of rules (not counting `trivial` cases).
+    pub rule_count: usize,
+}
+
+/// Converts the value in a rule's conclusion into the output type `O` of judgment `J`.
+///
+/// This is just [`Upcast`], but the `judgment_fn!` macro goes through it so that
+/// a conclusion of the wrong type produces an error naming the judgment:
+///
+/// ```compile_fail,E0277
+/// formality_core::judgment_fn! {
+///     fn is_even(n: u32) => bool {
+///         debug(n)
+///
+///         (
+///             (if n % 2 == 0)
+///             ------------------------- ("even")
+///             (is_even(n) => "yes")
+///         )
+///     }
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "the conclusion of judgment `{J}` yields `{Self}`, which does not upcast to its output type `{O}`",
+    label = "expected a value that upcasts to `{O}`"
+)]
+pub trait JudgmentOutput<J, O> {
+    fn upcast_output(self) -> O;
+}
+
+impl<J, O, T: Upcast<O>> JudgmentOutput<J, O> for T {
+    fn upcast_output(self) -> O {
+        self.upcast()
+    }
+}
+
 /// `judgment_fn!` allows construction of inference rules using a more logic-like notation.
 ///
 /// The macro input looks like so:
@@ -27,39 +137,180 @@ pub type JudgmentStack<J, O> = RefCell<FixedPointStack<J, Set<O>>>;
 /// The conditions can be the following
 ///
 /// * `(<expr> => <binding>)` -- used to apply judgments, but really `<expr>` can be anything with an `into_iter` method.
+/// * `(for <pat> in <expr>)` -- continues with each element of `<expr>`, an ordinary collection (not a judgment).
 /// * `(if <expr>)`
 /// * `(if let <pat> = <expr>)`
 /// * `(let <binding> = <expr>)`
+/// * `(not <expr>)` -- holds if `<expr>`, a [`ProvenSet`], failed; see below.
+/// * `(assert <expr>)` -- panics unless `<expr>` holds; for true invariants.
+/// * `(require <expr>)` -- like `assert`, but for preconditions that bad input
+///   may violate: if `<expr>` is false, the rule fails with
+///   [`RuleFailureCause::RequirementFailed`] and the other rules are still tried.
 ///
 /// The conclusions can be the following
 ///
 /// * `(<pat> => <binding>)
 ///
+/// Alongside the judgment `foo`, the macro generates `foo_metadata()`,
+/// which returns a [`JudgmentMetadata`] describing the declaration.
+///
+/// ## Rule names
+///
+/// The dashes are usually followed by a label naming the rule, as in
+/// `--- ("subtype-refl")`. Failures and tracing spans refer to the rule by
+/// that label. A rule without one is named by its index among the judgment's
+/// rules, starting from `"0"`.
+///
+/// ## Negative conditions
+///
+/// A `(not <expr>)` condition expresses negation as failure: it holds if the
+/// judgment `<expr>` cannot be proven, and otherwise the rule fails with
+/// [`RuleFailureCause::NegativeConditionHeld`]. Only a conclusive failure
+/// counts: if `<expr>` failed because of a limit or a cycle (see
+/// [`FailedJudgment::is_inconclusive`]), the rule fails with
+/// [`RuleFailureCause::NegationInconclusive`] instead. This is only meaningful if the
+/// negated judgment is *stratified* below the one being defined, i.e. it does
+/// not depend (even indirectly) on the judgment whose rule contains it. Within
+/// a cycle, the negated judgment would see the partial results of the fixed
+/// point, and as those grow the condition may flip from holding to not, so the
+/// iteration may not converge or may yield an unsound result.
+///
+/// ## Trivial cases
+///
+/// A `trivial(expr => result)` case, listed after `debug(...)`, skips the
+/// rules when `expr` holds and yields just `result`. A fast path that has
+/// several results can instead give an iterator of them, written
+/// `trivial(expr => each results)`; if it yields none, the judgment carries on
+/// with the later cases and the rules as if `expr` did not hold.
+///
+/// ## Tagged trivial cases
+///
+/// Like `trivial(expr => result)`, a `trivial_tagged(expr => result, tag: "...")`
+/// case short-circuits the rules when `expr` holds. The result also records the
+/// tag in its [`ProvenSet::provenance`], so that [`ProvenSet::explain`] shows why
+/// the fast path applied. Tagged cases are checked after the plain ones.
+///
+/// ## Recursion limits
+///
+/// An optional `recursion_limit(N)` may follow `debug(...)`. It bounds how many
+/// invocations of this judgment may be in progress at once. Once `N` are on the
+/// stack, a further call (with a new input) fails with an overflow rather than
+/// recursing.
+///
+/// Separately, [`with_iteration_limit`](crate::fixed_point::with_iteration_limit)
+/// bounds how many times the rules are re-run for one input while looking for a
+/// fixed point. A judgment that exceeds it fails with
+/// [`RuleFailureCause::IterationLimit`].
+///
+/// Neither failure shows that the judgment cannot hold
+/// ([`FailedJudgment::is_inconclusive`] is true), and a judgment whose rules
+/// succeed in spite of such a failure within them may be missing results. Either
+/// way, [`limits_reached`](crate::fixed_point::limits_reached) changes, so that
+/// callers can report the result as an overflow.
+///
+/// ## Context inputs
+///
+/// Leading inputs such as a database may be marked `#[context]`, as in
+/// `fn prove(#[context] db: Db, env: Env, goal: Goal) => Solution`. They are
+/// passed to the rules like any other input, but are not part of the key used to
+/// detect cycles: two invocations whose other inputs are equal are the same
+/// judgment, whatever their context. A context input therefore need not
+/// implement `Eq`, `Ord`, or `Hash`, but should be fixed for a whole proof.
+///
 /// ## Failure reporting and match commit points
 ///
 /// When we fail to prove a judgment, we'll produce a failure that includes
 /// all rules that partially matched. By default this lits includes every
-/// rule that matches the patterns in its conclusion.
+/// rule that matches the patterns in its conclusion. If no rule does, it
+/// lists instead the rules with a conclusion pattern `x: T` whose input could
+/// not be downcast to `T` ([`RuleFailureCause::ConclusionPatternMismatch`]).
 /// Sometimes this is annoyingly verbose.
 /// You can place a `!` after a condition to mark it as a "match commit point".
 /// Rules that fail before reaching the match commit point will not be included
 /// in the failure result.
+///
+/// ## Derivations
+///
+/// Within [`with_derivation_recording`], each output a rule produces is
+/// recorded along with the outputs of the `(expr => pat)` steps it relied on.
+/// [`to_dot`] then renders the derivation of a result as a Graphviz graph.
+///
+/// ## Rule categories
+///
+/// A rule name may be followed by a category, as in
+/// `------ ("impl") [category: "user-impl"]`. Installing a [`RuleFilter`] with
+/// [`with_rule_filter`] restricts which categories are tried, and failures
+/// record the category of the rule that failed
+/// (see [`FailedJudgment::failed_rules_by_category`]).
+///
+/// ## Cuts
+///
+/// A rule marked `[cut]`, after its name and category (if any), as in
+/// `------ ("exact") [cut]`, is authoritative: once it has produced an output
+/// for an input, the rules after it are not tried for that input. Unlike a
+/// `!`, which only affects failure reporting, this changes what is proven, so
+/// it suits judgments whose later rules would only find the same answers the
+/// slow way. A cut rule that recurses into its own judgment may produce nothing
+/// on the first round of the fixed point and cut the later rules only on the
+/// next, so such judgments may not converge.
 #[macro_export]
 macro_rules! judgment_fn {
     (
         $(#[$attr:meta])*
-        $v:vis fn $name:ident($($input_name:ident : $input_ty:ty),* $(,)?) => $output:ty {
+        $v:vis fn $name:ident($($(#[$input_attr:ident])? $input_name:ident : $input_ty:ty),* $(,)?) => $output:ty {
             debug($($debug_input_name:ident),*)
+            $(recursion_limit($recursion_limit:expr))?
             $(assert($assert_expr:expr))*
-            $(trivial($trivial_expr:expr => $trivial_result:expr))*
+            $(trivial($trivial_expr:expr => $($trivial_result:tt)*))*
+            $(trivial_tagged($tagged_expr:expr => $tagged_result:expr, tag: $tag:expr))*
             $(($($rule:tt)*))*
         }
     ) => {
         $(#[$attr])*
         $v fn $name($($input_name : impl $crate::Upcast<$input_ty>),*) -> $crate::ProvenSet<$output> {
-            #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone)]
+            // Names the judgment in type errors (see `JudgmentOutput`); being a braced
+            // struct, it does not shadow the fn itself.
+            #[allow(non_camel_case_types, dead_code)]
+            struct $name {}
+
+            #[derive(Clone)]
             struct __JudgmentStruct($($input_ty),*);
 
+            impl __JudgmentStruct {
+                /// The inputs that identify this judgment, i.e., all but the `#[context]` ones,
+                /// which are compared as `()`.
+                fn key(&self) -> impl Ord + std::hash::Hash + '_ {
+                    let __JudgmentStruct($($input_name),*) = self;
+                    ($($crate::judgment_key_input!($(#[$input_attr])? $input_name),)*)
+                }
+            }
+
+            impl PartialEq for __JudgmentStruct {
+                fn eq(&self, other: &Self) -> bool {
+                    self.key() == other.key()
+                }
+            }
+
+            impl Eq for __JudgmentStruct {}
+
+            impl PartialOrd for __JudgmentStruct {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for __JudgmentStruct {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    self.key().cmp(&other.key())
+                }
+            }
+
+            impl std::hash::Hash for __JudgmentStruct {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.key().hash(state)
+                }
+            }
+
             $crate::cast_impl!(__JudgmentStruct);
 
             impl std::fmt::Debug for __JudgmentStruct {
@@ -84,16 +335,29 @@ macro_rules! judgment_fn {
                 // Trivial cases are an (important) optimization that lets
                 // you cut out all the normal rules.
                 if $trivial_expr {
-                    return $crate::ProvenSet::proven(std::iter::once($trivial_result).collect());
+                    $crate::judgment_trivial_results!($($trivial_result)*);
                 }
             )*
 
+            $(
+                // Like trivial cases, but the result records why it applied.
+                if $tagged_expr {
+                    return $crate::ProvenSet::proven(std::iter::once($tagged_result).collect())
+                        .with_provenance($tag);
+                }
+            )*
+
+            let in_progress = $crate::judgment::JudgmentInProgress::enter();
             let mut failed_rules = $crate::set![];
             let input = __JudgmentStruct($($input_name),*);
-            let output = $crate::fixed_point::fixed_point::<
+            let recursion_limit: Option<usize> = None $(.or(Some($recursion_limit)))?;
+            let output = $crate::fixed_point::fixed_point_with_limit::<
                 __JudgmentStruct,
                 $crate::Set<$output>,
             >(
+                // Name, for stats:
+                stringify!($name),
+
                 // Tracing span:
                 |input| {
                     let __JudgmentStruct($($input_name),*) = input;
@@ -111,6 +375,9 @@ macro_rules! judgment_fn {
                     &R
                 },
 
+                // Recursion limit:
+                recursion_limit,
+
                 // Input:
                 input.clone(),
 
@@ -123,6 +390,7 @@ macro_rules! judgment_fn {
 
                     failed_rules.clear();
 
+                    let matched_before = $crate::judgment::matched_rules();
                     $crate::push_rules!(
                         $name,
                         &input,
@@ -132,31 +400,118 @@ macro_rules! judgment_fn {
                         $(($($rule)*))*
                     );
 
+                    // A conclusion pattern that did not match only explains the
+                    // failure if no rule got past its patterns.
+                    if $crate::judgment::matched_rules() != matched_before {
+                        failed_rules.retain(|rule: &$crate::judgment::FailedRule| {
+                            !matches!(
+                                rule.cause,
+                                $crate::judgment::RuleFailureCause::ConclusionPatternMismatch { .. }
+                            )
+                        });
+                    }
+
                     output
                 },
             );
 
-            if !output.is_empty() {
+            let output = match output {
+                Ok(output) => output,
+                Err(limit) => {
+                    let cause = match limit {
+                        $crate::fixed_point::FixedPointLimit::Recursion { limit } => {
+                            $crate::judgment::RuleFailureCause::Overflow { limit }
+                        }
+                        $crate::fixed_point::FixedPointLimit::Iterations { limit } => {
+                            $crate::judgment::RuleFailureCause::IterationLimit {
+                                judgment: stringify!($name).to_string(),
+                                input: format!("{input:?}"),
+                                limit,
+                            }
+                        }
+                    };
+                    return $crate::ProvenSet::failed_rules(
+                        &input,
+                        $crate::set![$crate::judgment::FailedRule::new(cause)],
+                    );
+                }
+            };
+
+            let result = if !output.is_empty() {
                 $crate::ProvenSet::proven(output)
             } else {
                 $crate::ProvenSet::failed_rules(&input, failed_rules)
-            }
+            };
+            in_progress.finish(stringify!($name), &result);
+            result
         }
+
+        $crate::concat_ident!($name _metadata (
+            #[doc = concat!("Describes the declaration of the judgment `", stringify!($name), "`.")]
+            #[allow(dead_code)]
+            $v fn __concat_ident() -> $crate::judgment::JudgmentMetadata {
+                let rules: &[&str] = &[$(stringify!($($rule)*)),*];
+                $crate::judgment::JudgmentMetadata {
+                    name: stringify!($name),
+                    input_names: &[$(stringify!($input_name)),*],
+                    debug_input_names: &[$(stringify!($debug_input_name)),*],
+                    rule_count: rules.len(),
+                }
+            }
+        ));
     }
 }
 
+/// Used by `judgment_fn!` to build the key of a judgment's inputs:
+/// a `#[context]` input contributes `()`, any other input itself.
+#[macro_export]
+macro_rules! judgment_key_input {
+    (#[context] $input_name:ident) => {{
+        let _ = $input_name;
+    }};
+    ($input_name:ident) => {
+        $input_name
+    };
+}
+
+/// Used by `judgment_fn!` to return the results of a `trivial` case:
+/// `each results` returns every item of `results` (or nothing, if there are
+/// none), any other value itself.
+#[macro_export]
+macro_rules! judgment_trivial_results {
+    (each $results:expr) => {
+        let results: $crate::Set<_> = $results.into_iter().collect();
+        if !results.is_empty() {
+            return $crate::ProvenSet::proven(results);
+        }
+    };
+    ($result:expr) => {
+        return $crate::ProvenSet::proven(std::iter::once($result).collect())
+    };
+}
+
 #[macro_export]
 macro_rules! push_rules {
     ($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty, $($rule:tt)*) => {
-        $($crate::push_rules!(@rule ($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty) $rule);)*
+        $crate::push_rules!(@rules ($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty) 0; $($rule)*);
+    };
+
+    // `@rules (builder) index; rules` phase: numbers the rules, which names the
+    // ones that have no label of their own.
+
+    (@rules $builder:tt $index:expr;) => {};
+
+    (@rules $builder:tt $index:expr; $rule:tt $($rules:tt)*) => {
+        $crate::push_rules!(@rule $builder $index; $rule);
+        $crate::push_rules!(@rules $builder $index + 1; $($rules)*);
     };
 
-    // `@rule (builder) rule` phase: invoked for each rule, emits `push_rule` call
+    // `@rule (builder) index; rule` phase: invoked for each rule, emits `push_rule` call
 
-    (@rule ($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty) ($($m:tt)*)) => {
+    (@rule ($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty) $index:expr; ($($m:tt)*)) => {
         // Start accumulating.
         $crate::push_rules!(@accum
-            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty)
+            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty; $index)
             accum((1-1); 0;)
             input($($m)*)
         );
@@ -169,14 +524,81 @@ macro_rules! push_rules {
     // at 0. The `current_index` is also expected to start as the expression `0`.
 
     (@accum
-        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, ($($input_names:ident),*) => $output_ty:ty)
+        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty; $index:expr)
+        accum($match_index:expr; $current_index:expr; $($m:tt)*)
+        input(
+            ---$(-)* $([category: $category:literal])?
+            ($conclusion_name:ident($($patterns:tt)*) => $v:expr)
+        )
+    ) => {
+        // Found the conclusion of a rule without a label; it is named by its index.
+        $crate::push_rules!(@conclusion
+            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty)
+            accum($match_index; $current_index; $($m)*)
+            rule($index; $($category)?; cut: false)
+            conclusion($conclusion_name($($patterns)*) => $v)
+        );
+    };
+
+    (@accum
+        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty; $index:expr)
+        accum($match_index:expr; $current_index:expr; $($m:tt)*)
+        input(
+            ---$(-)* ($n:literal) $([category: $category:literal])?
+            ($conclusion_name:ident($($patterns:tt)*) => $v:expr)
+        )
+    ) => {
+        // Found the conclusion of a labeled rule.
+        $crate::push_rules!(@conclusion
+            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty)
+            accum($match_index; $current_index; $($m)*)
+            rule($n; $($category)?; cut: false)
+            conclusion($conclusion_name($($patterns)*) => $v)
+        );
+    };
+
+    // The same two forms, for a rule marked `[cut]`. The `[cut]` is matched as
+    // literal tokens, as an optional fragment would be ambiguous with the
+    // category that may precede it.
+
+    (@accum
+        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty; $index:expr)
+        accum($match_index:expr; $current_index:expr; $($m:tt)*)
+        input(
+            ---$(-)* $([category: $category:literal])? [cut]
+            ($conclusion_name:ident($($patterns:tt)*) => $v:expr)
+        )
+    ) => {
+        $crate::push_rules!(@conclusion
+            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty)
+            accum($match_index; $current_index; $($m)*)
+            rule($index; $($category)?; cut: true)
+            conclusion($conclusion_name($($patterns)*) => $v)
+        );
+    };
+
+    (@accum
+        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, $input_names:tt => $output_ty:ty; $index:expr)
         accum($match_index:expr; $current_index:expr; $($m:tt)*)
         input(
-            ---$(-)* ($n:literal)
+            ---$(-)* ($n:literal) $([category: $category:literal])? [cut]
             ($conclusion_name:ident($($patterns:tt)*) => $v:expr)
         )
     ) => {
-        // Found the conclusion.
+        $crate::push_rules!(@conclusion
+            args($judgment_name, $input_value, $output, $failed_rules, $input_names => $output_ty)
+            accum($match_index; $current_index; $($m)*)
+            rule($n; $($category)?; cut: true)
+            conclusion($conclusion_name($($patterns)*) => $v)
+        );
+    };
+
+    (@conclusion
+        args($judgment_name:ident, $input_value:expr, $output:expr, $failed_rules:expr, ($($input_names:ident),*) => $output_ty:ty)
+        accum($match_index:expr; $current_index:expr; $($m:tt)*)
+        rule($n:expr; $($category:literal)?; cut: $cut:literal)
+        conclusion($conclusion_name:ident($($patterns:tt)*) => $v:expr)
+    ) => {
         {
             $crate::respan!(
                 $conclusion_name
@@ -193,18 +615,33 @@ macro_rules! push_rules {
                 )
             );
 
-            if let Some(__JudgmentStruct($($input_names),*)) = Some($input_value) {
+            let category: Option<&'static str> = None $(.or(Some($category)))?;
+            let outputs_before = $output.len();
+            // The rule filter, fuel and derivation recording are hooks; with
+            // none installed, there is nothing to check.
+            let hooks = $crate::judgment::hooks_installed();
+            if hooks && !$crate::judgment::rule_allowed(category) {
+                tracing::trace!("rule {:?} excluded by the rule filter", $n);
+            } else if let Some(cause) = hooks.then($crate::judgment::consume_fuel).flatten() {
+                $crate::push_rules!(@record_failure ($failed_rules, 0, (), $n, category); 0, $n; cause);
+            } else if let Some(__JudgmentStruct($($input_names),*)) = Some($input_value) {
+                let _derivation = hooks.then(|| $crate::judgment::RuleDerivation::enter($input_value, $n));
                 $crate::push_rules!(@match
                     $conclusion_name
                     inputs($($input_names)*)
                     patterns($($patterns)*,)
                     args(@body
-                        ($judgment_name; $n; $v; $output);
-                        ($failed_rules, $match_index, ($($input_names),*), $n);
+                        ($judgment_name; $n; $v; $output; $output_ty);
+                        ($failed_rules, $match_index, ($($input_names),*), $n, category);
                         $($m)*
                     )
                 );
             }
+
+            if $cut && $output.len() > outputs_before {
+                tracing::debug!("rule {:?} produced an output, skipping the remaining rules", $n);
+                return $output;
+            }
         }
     };
 
@@ -238,9 +675,10 @@ macro_rules! push_rules {
     // Matching phase: peel off the patterns one by one and match them against the values
     // extracted from the input. For anything that is not an identity pattern, invoke `downcast`.
 
-    (@match $conclusion_name:ident inputs() patterns() args(@body ($judgment_name:ident; $n:literal; $v:expr; $output:expr); $inputs:tt; $($m:tt)*)) => {
+    (@match $conclusion_name:ident inputs() patterns() args(@body ($judgment_name:ident; $n:expr; $v:expr; $output:expr; $output_ty:ty); $inputs:tt; $($m:tt)*)) => {
+        $crate::judgment::rule_matched();
         tracing::trace_span!("matched rule", rule = $n, judgment = stringify!($judgment_name)).in_scope(|| {
-            $crate::push_rules!(@body ($judgment_name, $n, $v, $output); $inputs; 0; $($m)*);
+            $crate::push_rules!(@body ($judgment_name, $n, $v, $output, $output_ty); $inputs; 0; $($m)*);
         });
     };
 
@@ -249,17 +687,19 @@ macro_rules! push_rules {
     };
 
     (@match $conclusion_name:ident inputs() patterns ($pattern0:tt $($pattern1:tt)*) args $args:tt) => {
-        $crate::respan!($pattern0 (compile_error!("more patterns in rule than arguments on fn")))
+        $crate::respan!($pattern0 (compile_error!(concat!("more patterns in rule than arguments on fn `", stringify!($conclusion_name), "`"))))
     };
 
     (@match $conclusion_name:ident inputs $inputs:tt patterns() args $args:tt) => {
-        $crate::respan!($conclusion_name (compile_error!("fewer patterns in rule than arguments on fn")))
+        $crate::respan!($conclusion_name (compile_error!(concat!("fewer patterns in rule than arguments on fn `", stringify!($conclusion_name), "`"))))
     };
 
     (@match $conclusion_name:ident inputs($in0:ident $($inputs:tt)*) patterns($pat0:ident : $ty0:ty, $($pats:tt)*) args $args:tt) => {
         {
             if let Some($pat0) = $crate::Downcast::downcast::<$ty0>($in0) {
                 $crate::push_rules!(@match $conclusion_name inputs($($inputs)*) patterns($($pats)*) args $args);
+            } else {
+                $crate::push_rules!(@pattern_mismatch $args; $conclusion_name; stringify!($pat0 : $ty0), std::any::type_name::<$ty0>(), $in0);
             }
         }
     };
@@ -277,6 +717,17 @@ macro_rules! push_rules {
         }
     };
 
+    // A conclusion pattern `x: T` whose input could not be downcast to `T` fails
+    // the rule before its first step. (Other patterns are routinely used to pick out
+    // a variant, so a mismatch there just means that the rule is not applicable.)
+    (@pattern_mismatch (@body $body:tt; $inputs:tt; $($m:tt)*); $conclusion_name:ident; $pattern:expr, $expected:expr, $value:expr) => {
+        $crate::push_rules!(@record_failure $inputs; 0, $conclusion_name; $crate::judgment::RuleFailureCause::ConclusionPatternMismatch {
+            pattern: $pattern.to_string(),
+            expected: $expected.to_string(),
+            value: format!("{:?}", $value),
+        });
+    };
+
     // (@match (($arg0:ident @ $pat0:pat) $($args:tt)*) ($n:literal; $v:expr; $output:ident) $($m:tt)*) => {
     //     if let Some($pat0) = $crate::cast::Downcast::downcast(&$arg0) {
     //         $crate::push_rules!(@match ($($args)*) ($n; $v; $output) $($m)*);
@@ -381,17 +832,65 @@ macro_rules! push_rules {
         }
     };
 
+    (@body $args:tt; $inputs:tt; $step_index:expr; (not $e:expr) $($m:tt)*) => {
+        let result: $crate::ProvenSet<_> = $e;
+        match result.failure() {
+            Some(failure) if !failure.is_inconclusive() => {
+                $crate::push_rules!(@body $args; $inputs; $step_index + 1; $($m)*);
+            }
+            Some(_) => {
+                $crate::push_rules!(@record_failure $inputs; $step_index, $e; $crate::judgment::RuleFailureCause::NegationInconclusive {
+                    expr: stringify!($e).to_string(),
+                });
+            }
+            None => {
+                $crate::push_rules!(@record_failure $inputs; $step_index, $e; $crate::judgment::RuleFailureCause::NegativeConditionHeld {
+                    expr: stringify!($e).to_string(),
+                });
+            }
+        }
+    };
+
     (@body $args:tt; $inputs:tt; $step_index:expr; (assert $c:expr) $($m:tt)*) => {
         assert!($c);
         $crate::push_rules!(@body $args; $inputs; $step_index + 1; $($m)*);
     };
 
+    (@body $args:tt; $inputs:tt; $step_index:expr; (require $c:expr) $($m:tt)*) => {
+        if $c {
+            $crate::push_rules!(@body $args; $inputs; $step_index + 1; $($m)*);
+        } else {
+            $crate::push_rules!(@record_failure $inputs; $step_index, $c; $crate::judgment::RuleFailureCause::RequirementFailed {
+                expr: stringify!($c).to_string(),
+            });
+        }
+    };
+
+    // Unlike `=>`, `for` does not accept a `ProvenSet`, so it cannot be mistaken
+    // for applying a judgment; an empty collection still fails the rule.
+    (@body $args:tt; $inputs:tt; $step_index:expr; (for $p:pat in $i:expr) $($m:tt)*) => {
+        let mut i = std::iter::IntoIterator::into_iter($i).peekable();
+        if i.peek().is_some() {
+            for $p in $crate::judgment::shuffled(i) {
+                $crate::push_rules!(@body $args; $inputs; $step_index + 1; $($m)*);
+            }
+        } else {
+            $crate::push_rules!(@record_failure $inputs; $step_index, $i; $crate::judgment::RuleFailureCause::EmptyCollection {
+                expr: stringify!($i).to_string(),
+            });
+        }
+    };
+
     (@body $args:tt; $inputs:tt; $step_index:expr; ($i:expr => $p:pat) $($m:tt)*) => {
-        // Explicitly calling `into_iter` silences some annoying lints
-        // in the case where `$i` is an `Option` or a `Result`
+        // Iterating via `shuffled` lets an installed shuffle seed permute the
+        // alternatives; it also silences some annoying lints in the case
+        // where `$i` is an `Option` or a `Result`
+        let mark = $crate::judgment::RuleDerivation::mark();
         match $crate::judgment::TryIntoIter::try_into_iter($i, || stringify!($i).to_string()) {
             Ok(i) => {
-                for $p in std::iter::IntoIterator::into_iter(i) {
+                for item in $crate::judgment::shuffled(i) {
+                    let _premise = $crate::judgment::RuleDerivation::premise(mark, &item);
+                    let $p = item;
                     $crate::push_rules!(@body $args; $inputs; $step_index + 1; $($m)*);
                 }
             }
@@ -408,17 +907,20 @@ macro_rules! push_rules {
         }
     };
 
-    (@body ($judgment_name:ident, $rule_name:literal, $v:expr, $output:expr); $inputs:tt; $step_index:expr;) => {
+    (@body ($judgment_name:ident, $rule_name:expr, $v:expr, $output:expr, $output_ty:ty); $inputs:tt; $step_index:expr;) => {
         {
-            let result = $crate::Upcast::upcast($v);
+            // Routed through `JudgmentOutput` so that a conclusion of the wrong type
+            // is reported against the judgment rather than as a bare `Upcast` error.
+            let result = $crate::judgment::JudgmentOutput::<$judgment_name, $output_ty>::upcast_output($v);
             tracing::debug!("produced {:?} from rule {:?} in judgment {:?}", result, $rule_name, stringify!($judgment_name));
+            $crate::judgment::RuleDerivation::conclude(&result);
             $output.insert(result)
         }
     };
 
     //
 
-    (@record_failure ($failed_rules:expr, $match_index:expr, $inputs:tt, $rule_name:literal); $step_index:expr, $step_expr:expr; $cause:expr) => {
+    (@record_failure ($failed_rules:expr, $match_index:expr, $inputs:tt, $rule_name:expr, $category:expr); $step_index:expr, $step_expr:expr; $cause:expr) => {
         let file = $crate::respan!($step_expr (file!()));
         let line = $crate::respan!($step_expr (line!()));
         let column = $crate::respan!($step_expr (column!()));
@@ -435,6 +937,7 @@ macro_rules! push_rules {
             $failed_rules.insert(
                 $crate::judgment::FailedRule {
                     rule_name_index: Some(($rule_name.to_string(), $step_index)),
+                    category: $category.map(|c: &str| c.to_string()),
                     file: file.to_string(),
                     line: line,
                     column: column,
@@ -0,0 +1,146 @@
+//! Rendering a [`FailedJudgment`] incrementally.
+//!
+//! Some failure reports run to many thousands of lines. Rather than building
+//! the whole report with `to_string`, [`FailedJudgment::render_chunks`] walks
+//! the failure tree lazily and yields the report a piece at a time, so that a
+//! pager or web UI can show the start of the report right away. Joining the
+//! chunks with `"\n"` gives the same text as the `Display` impl.
+//!
+//! Nested failed judgments are rendered structurally, so a
+//! [`FailureRenderer`](super::FailureRenderer) that overrides `failed_judgment`
+//! is not consulted; the other causes are rendered as usual.
+
+use std::collections::VecDeque;
+
+use super::{FailedJudgment, FailedRule, RuleFailureCause};
+
+/// Where [`FailedJudgment::render_chunks`] splits the report.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChunkBy {
+    /// Start a new chunk at each failed rule nested at most `max_depth`
+    /// judgments deep (the rules of the top-level judgment are at depth 0).
+    Rule { max_depth: usize },
+
+    /// Start a new chunk every `n` lines.
+    Lines(usize),
+}
+
+impl FailedJudgment {
+    /// Renders this failure as a sequence of chunks split according to `by`.
+    /// Each chunk holds one or more whole lines.
+    pub fn render_chunks(&self, by: ChunkBy) -> impl Iterator<Item = String> + '_ {
+        Chunks {
+            lines: Lines {
+                stack: vec![(Frame::Judgment(self, 0), 0)],
+                pending: VecDeque::new(),
+            },
+            next: None,
+            by,
+        }
+    }
+}
+
+/// A node of the failure tree still to be rendered, with its judgment depth.
+enum Frame<'a> {
+    Judgment(&'a FailedJudgment, usize),
+    Rule(&'a FailedRule, usize),
+}
+
+struct Line {
+    /// The text, including indentation.
+    text: String,
+
+    /// If this line is the header of a failed rule, the rule's judgment depth.
+    rule_depth: Option<usize>,
+}
+
+/// The lines of a report, produced lazily by walking the failure tree.
+/// Each level of nesting (a judgment's rules, or a rule's cause) indents by
+/// two spaces, exactly as the `Display` impls do.
+struct Lines<'a> {
+    /// Frames paired with their indentation level, in reverse order.
+    stack: Vec<(Frame<'a>, usize)>,
+    pending: VecDeque<Line>,
+}
+
+impl<'a> Lines<'a> {
+    fn push_line(&mut self, level: usize, text: &str, rule_depth: Option<usize>) {
+        let mut text = format!("{:width$}{text}", "", width = 2 * level);
+        if level > 0 {
+            text.truncate(text.trim_end().len());
+        }
+        self.pending.push_back(Line { text, rule_depth });
+    }
+
+    fn expand(&mut self, frame: Frame<'a>, level: usize) {
+        match frame {
+            Frame::Judgment(judgment, depth) => {
+                self.push_line(level, &judgment.header(), None);
+                for rule in judgment.failed_rules.iter().rev() {
+                    self.stack.push((Frame::Rule(rule, depth), level + 1));
+                }
+            }
+            Frame::Rule(rule, depth) => {
+                self.push_line(level, &rule.header(), Some(depth));
+                match &rule.cause {
+                    RuleFailureCause::FailedJudgment(judgment) => {
+                        self.stack
+                            .push((Frame::Judgment(judgment, depth + 1), level + 1));
+                    }
+                    cause => {
+                        for line in cause.to_string().lines() {
+                            self.push_line(level + 1, line, None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Lines<'_> {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        loop {
+            if let Some(line) = self.pending.pop_front() {
+                return Some(line);
+            }
+            let (frame, level) = self.stack.pop()?;
+            self.expand(frame, level);
+        }
+    }
+}
+
+struct Chunks<'a> {
+    lines: Lines<'a>,
+
+    /// The first line of the next chunk, if it has already been read.
+    next: Option<Line>,
+
+    by: ChunkBy,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let first = self.next.take().or_else(|| self.lines.next())?;
+        let mut chunk = first.text;
+        let mut count = 1;
+        for line in self.lines.by_ref() {
+            let starts_chunk = match self.by {
+                ChunkBy::Rule { max_depth } => line.rule_depth.is_some_and(|d| d <= max_depth),
+                ChunkBy::Lines(n) => count >= n,
+            };
+            if starts_chunk {
+                self.next = Some(line);
+                break;
+            }
+            chunk.push('\n');
+            chunk.push_str(&line.text);
+            count += 1;
+        }
+        Some(chunk)
+    }
+}
@@ -0,0 +1,152 @@
+//! Collection of top-level judgment failures.
+//!
+//! Unlike the tracing logs, which record everything, a [`FailureCollector`]
+//! keeps one entry per failed top-level judgment: what was being proven and
+//! the cause that best explains why it failed (see
+//! [`FailedJudgment::most_relevant_cause`]). This is meant for building up a
+//! corpus of interesting failing cases.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use super::{FailedJudgment, ProvenSet};
+use crate::scoped::with_scoped;
+
+/// A failure recorded by a [`FailureCollector`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CollectedFailure {
+    /// The name of the judgment fn that failed, e.g. `prove_wc_list`.
+    pub judgment_name: String,
+
+    /// The judgment's debug inputs, as they appear in failure messages.
+    pub input: String,
+
+    /// The most relevant cause of the failure, rendered.
+    pub cause: String,
+}
+
+/// Accumulates the failures of top-level judgments made while it is installed
+/// (see [`with_failure_collector`]). Clones share the same entries, so you can
+/// keep a handle to inspect after installing it.
+#[derive(Clone, Default)]
+pub struct FailureCollector {
+    entries: Rc<RefCell<Vec<CollectedFailure>>>,
+}
+
+impl FailureCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The failures recorded so far, oldest first.
+    pub fn entries(&self) -> Vec<CollectedFailure> {
+        self.entries.borrow().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear()
+    }
+
+    fn record(&self, judgment_name: &str, failure: &FailedJudgment) {
+        let cause = match failure.most_relevant_cause() {
+            Some(cause) => cause.to_string(),
+            None => "no applicable rules".to_string(),
+        };
+        self.entries.borrow_mut().push(CollectedFailure {
+            judgment_name: judgment_name.to_string(),
+            input: failure.judgment.clone(),
+            cause,
+        });
+    }
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<FailureCollector>> = const { RefCell::new(None) };
+
+    /// The number of judgments currently executing on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Runs `op` with `collector` recording the top-level judgment failures on
+/// this thread, restoring the previous collector afterwards.
+pub fn with_failure_collector<R>(collector: &FailureCollector, op: impl FnOnce() -> R) -> R {
+    with_scoped(&COLLECTOR, Some(collector.clone()), op)
+}
+
+/// True if a [`FailureCollector`] is installed on this thread.
+pub fn failure_collector_installed() -> bool {
+    COLLECTOR.with(|c| c.borrow().is_some())
+}
+
+/// The number of judgments executing on this thread, counting the caller if it
+/// is itself a judgment's rule: 1 within the rules of a top-level judgment, 2
+/// within those of a judgment it invokes, and so on. Judgments settled by a
+/// trivial case are not counted.
+///
+/// Meant for strategies that adapt to how deep a proof has gone, or for
+/// reporting progress.
+pub fn current_judgment_depth() -> usize {
+    DEPTH.with(|d| d.get())
+}
+
+thread_local! {
+    static MATCHED_RULES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts a rule whose conclusion patterns matched its input.
+/// Meant to be used from the judgment macro.
+pub fn rule_matched() {
+    MATCHED_RULES.with(|m| m.set(m.get() + 1));
+}
+
+/// The number of rules on this thread so far whose conclusion patterns matched
+/// their input (see [`rule_matched`]). Meant to be used from the judgment macro.
+pub fn matched_rules() -> u64 {
+    MATCHED_RULES.with(|m| m.get())
+}
+
+/// Tracks that a judgment is executing, so that only failures of the outermost
+/// judgment are collected. Created by the `judgment_fn!` macro.
+pub struct JudgmentInProgress {
+    top_level: bool,
+}
+
+impl JudgmentInProgress {
+    pub fn enter() -> Self {
+        let depth = DEPTH.with(|d| d.replace(d.get() + 1));
+        Self {
+            top_level: depth == 0,
+        }
+    }
+
+    /// Reports `result` to the installed collector if it is the failure of a top-level judgment.
+    pub fn finish<T: Ord + Debug>(&self, judgment_name: &str, result: &ProvenSet<T>) {
+        if !self.top_level {
+            return;
+        }
+        let Some(failure) = result.failure() else {
+            return;
+        };
+        // Clone the collector out so that no borrow is held while rendering the cause.
+        if let Some(collector) = COLLECTOR.with(|c| c.borrow().clone()) {
+            collector.record(judgment_name, failure);
+        }
+    }
+}
+
+impl Drop for JudgmentInProgress {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
@@ -0,0 +1,239 @@
+//! Recording of the derivations of proven judgments.
+//!
+//! While [`with_derivation_recording`] is running, every rule that produces an
+//! output records a [`RuleApplication`]: the judgment and its input, the rule,
+//! the output, and the applications whose outputs were bound by the rule's
+//! `(expr => pat)` steps. Afterwards, [`to_dot`] renders the derivations of the
+//! items of a [`ProvenSet`] as a Graphviz graph.
+//!
+//! A premise is linked only if the judgment producing it ran its rules during
+//! that step. Results obtained from a cycle or reused from a cache (such as the
+//! one `formality-prove` keeps for subgoals) have no recorded derivation there.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{Debug, Write},
+};
+
+use crate::{Map, Set};
+
+use super::{current_judgment_depth, hooks::with_hook, ProvenSet};
+
+/// An application of a rule that produced an output, as recorded by
+/// [`with_derivation_recording`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RuleApplication {
+    /// The judgment and its debug inputs, as they appear in failure messages.
+    pub judgment: String,
+
+    /// The name of the rule.
+    pub rule: String,
+
+    /// The output of the rule.
+    pub output: String,
+
+    /// The indices (in [`recorded_derivations`]) of the applications that
+    /// produced the outputs this one relies on, in the order the rule bound them.
+    pub premises: Vec<usize>,
+
+    /// The [`current_judgment_depth`] of the judgment, 1 for a top-level one.
+    pub depth: usize,
+}
+
+#[derive(Default)]
+struct Recording {
+    applications: Vec<RuleApplication>,
+    indices: Map<RuleApplication, usize>,
+
+    /// The index of each application in the order they were concluded,
+    /// including repetitions.
+    log: Vec<usize>,
+}
+
+/// A rule being attempted while recording.
+struct Frame {
+    judgment: String,
+    rule: String,
+    premises: Vec<usize>,
+}
+
+thread_local! {
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+    static RECORDING: RefCell<Recording> = RefCell::new(Recording::default());
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+/// Runs `op`, recording the derivation of every judgment output produced on
+/// this thread. The recording replaces the previous one and remains available
+/// to [`to_dot`] and [`recorded_derivations`] after `op` returns.
+pub fn with_derivation_recording<R>(op: impl FnOnce() -> R) -> R {
+    RECORDING.with(|r| *r.borrow_mut() = Recording::default());
+    with_hook(&ACTIVE, true, op)
+}
+
+/// The rule applications recorded by the last [`with_derivation_recording`],
+/// each recorded once no matter how often it was repeated.
+pub fn recorded_derivations() -> Vec<RuleApplication> {
+    RECORDING.with(|r| r.borrow().applications.clone())
+}
+
+/// Renders the recorded derivations of the items of `set` (which must come from
+/// a top-level judgment run during the last [`with_derivation_recording`]) in
+/// Graphviz's DOT format. Each rule application is a node, with an edge to each
+/// of its premises.
+pub fn to_dot<O: Ord + Debug>(set: &ProvenSet<O>) -> String {
+    let applications = recorded_derivations();
+    let outputs: Set<String> = set.iter().map(|o| format!("{o:?}")).collect();
+
+    let mut stack: Vec<usize> = (0..applications.len())
+        .filter(|&i| applications[i].depth == 1 && outputs.contains(&applications[i].output))
+        .rev()
+        .collect();
+    let mut reachable = Set::new();
+    while let Some(i) = stack.pop() {
+        if reachable.insert(i) {
+            stack.extend(applications[i].premises.iter().rev());
+        }
+    }
+
+    let mut dot = String::from("digraph derivation {\n");
+    writeln!(dot, "  node [shape=box];").unwrap();
+    for &i in &reachable {
+        let RuleApplication {
+            judgment,
+            rule,
+            output,
+            ..
+        } = &applications[i];
+        let label = escape(&format!("{judgment}\n({rule:?})\n=> {output}"));
+        writeln!(dot, "  n{i} [label=\"{label}\"];").unwrap();
+    }
+    for &i in &reachable {
+        for premise in &applications[i].premises {
+            writeln!(dot, "  n{i} -> n{premise};").unwrap();
+        }
+    }
+    dot.push('}');
+    dot
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Tracks the premises of a rule while it is attempted, so that the outputs it
+/// produces can be recorded. Created by the `judgment_fn!` macro.
+pub struct RuleDerivation {
+    active: bool,
+}
+
+impl RuleDerivation {
+    pub fn enter(judgment: &impl Debug, rule: impl ToString) -> Self {
+        let active = ACTIVE.with(|a| a.get());
+        if active {
+            FRAMES.with(|f| {
+                f.borrow_mut().push(Frame {
+                    judgment: format!("{judgment:?}"),
+                    rule: rule.to_string(),
+                    premises: vec![],
+                })
+            });
+        }
+        Self { active }
+    }
+
+    /// The point from which the applications recorded by an `(expr => pat)`
+    /// step are its candidate premises.
+    pub fn mark() -> Option<usize> {
+        ACTIVE
+            .with(|a| a.get())
+            .then(|| RECORDING.with(|r| r.borrow().log.len()))
+    }
+
+    /// Records that the current rule binds `item`, one of the results of the
+    /// step that began at `mark`, until the returned guard is dropped.
+    pub fn premise(mark: Option<usize>, item: &impl Debug) -> Premise {
+        let Some(mark) = mark else {
+            return Premise { count: 0 };
+        };
+        let item = format!("{item:?}");
+        let depth = current_judgment_depth() + 1;
+        let premises: Vec<usize> = RECORDING.with(|r| {
+            let r = r.borrow();
+            let mut premises: Vec<usize> = r.log[mark..]
+                .iter()
+                .copied()
+                .filter(|&i| r.applications[i].depth == depth && r.applications[i].output == item)
+                .collect();
+            premises.sort();
+            premises.dedup();
+            premises
+        });
+        let count = premises.len();
+        FRAMES.with(|f| match f.borrow_mut().last_mut() {
+            Some(frame) => {
+                frame.premises.extend(premises);
+                Premise { count }
+            }
+            None => Premise { count: 0 },
+        })
+    }
+
+    /// Records that the current rule produced `output`.
+    pub fn conclude(output: &impl Debug) {
+        if !ACTIVE.with(|a| a.get()) {
+            return;
+        }
+        let Some(application) = FRAMES.with(|f| {
+            f.borrow().last().map(|frame| RuleApplication {
+                judgment: frame.judgment.clone(),
+                rule: frame.rule.clone(),
+                output: format!("{output:?}"),
+                premises: frame.premises.clone(),
+                depth: current_judgment_depth(),
+            })
+        }) else {
+            return;
+        };
+        RECORDING.with(|r| {
+            let r = &mut *r.borrow_mut();
+            let index = match r.indices.get(&application) {
+                Some(&index) => index,
+                None => {
+                    let index = r.applications.len();
+                    r.indices.insert(application.clone(), index);
+                    r.applications.push(application);
+                    index
+                }
+            };
+            r.log.push(index);
+        });
+    }
+}
+
+impl Drop for RuleDerivation {
+    fn drop(&mut self) {
+        if self.active {
+            FRAMES.with(|f| f.borrow_mut().pop());
+        }
+    }
+}
+
+/// A binding made by an `(expr => pat)` step, see [`RuleDerivation::premise`].
+pub struct Premise {
+    count: usize,
+}
+
+impl Drop for Premise {
+    fn drop(&mut self) {
+        if self.count > 0 {
+            FRAMES.with(|f| {
+                let mut f = f.borrow_mut();
+                let premises = &mut f.last_mut().unwrap().premises;
+                premises.truncate(premises.len() - self.count);
+            });
+        }
+    }
+}
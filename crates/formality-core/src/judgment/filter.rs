@@ -0,0 +1,67 @@
+//! Restricting which rules of a judgment are tried.
+//!
+//! A rule may be tagged with a category in `judgment_fn!`:
+//!
+//! ```ignore
+//! --------------------------------------- ("name") [category: "builtin"]
+//! ```
+//!
+//! With a [`RuleFilter`] installed via [`with_rule_filter`], only the rules
+//! it allows are attempted; the others behave as if their conclusion did not
+//! match, so they neither contribute results nor appear in failures.
+
+use std::cell::RefCell;
+
+use super::hooks::with_hook;
+use crate::Set;
+
+/// Decides which rules are attempted, based on their category.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuleFilter {
+    /// If `Some`, only rules tagged with one of these categories are tried
+    /// (untagged rules are skipped). If `None`, all rules are tried.
+    categories: Option<Set<String>>,
+}
+
+impl RuleFilter {
+    /// A filter that allows every rule.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// A filter that allows only the rules tagged with one of `categories`.
+    pub fn only_categories(categories: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self {
+            categories: Some(categories.into_iter().map(|c| c.to_string()).collect()),
+        }
+    }
+
+    /// True if a rule tagged with `category` (or untagged, if `None`) may be tried.
+    pub fn allows(&self, category: Option<&str>) -> bool {
+        match (&self.categories, category) {
+            (None, _) => true,
+            (Some(categories), Some(category)) => categories.contains(category),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+thread_local! {
+    static FILTER: RefCell<Option<RuleFilter>> = const { RefCell::new(None) };
+}
+
+/// Runs `op` with `filter` restricting the rules tried by judgments on this
+/// thread, restoring the previous filter afterwards.
+pub fn with_rule_filter<R>(filter: RuleFilter, op: impl FnOnce() -> R) -> R {
+    with_hook(&FILTER, Some(filter), op)
+}
+
+/// True if the installed filter (if any) allows a rule tagged with `category`.
+/// Meant to be used from the judgment macro.
+pub fn rule_allowed(category: Option<&str>) -> bool {
+    FILTER.with(|f| {
+        f.borrow()
+            .as_ref()
+            .map_or(true, |filter| filter.allows(category))
+    })
+}
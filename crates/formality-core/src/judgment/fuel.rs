@@ -0,0 +1,67 @@
+//! A budget on the total work done by a proof.
+//!
+//! Recursion limits bound how deeply one judgment may nest, but not how much
+//! work is done overall. With a budget installed via [`with_fuel`], every rule
+//! attempted by any judgment on this thread consumes one unit of fuel; once it
+//! runs out, each further rule fails with [`RuleFailureCause::OutOfFuel`].
+//! A judgment can still succeed with the results of the rules it tried before
+//! that, which may then be only some of its results; clients that give such
+//! results a meaning (e.g., as a complete set of solutions) should check
+//! [`ran_out_of_fuel`].
+
+use std::cell::Cell;
+
+use super::{hooks::with_hook, RuleFailureCause};
+
+#[derive(Copy, Clone)]
+struct Fuel {
+    budget: u64,
+    remaining: u64,
+    exhausted: bool,
+}
+
+thread_local! {
+    static FUEL: Cell<Option<Fuel>> = const { Cell::new(None) };
+}
+
+/// Runs `op` with a budget of `budget` rule attempts shared by all judgments on
+/// this thread, restoring the previous budget (if any) afterwards. Returns the
+/// result of `op` along with the fuel left over, which is useful for tuning.
+pub fn with_fuel<R>(budget: u64, op: impl FnOnce() -> R) -> (R, u64) {
+    let fuel = Fuel {
+        budget,
+        remaining: budget,
+        exhausted: false,
+    };
+    with_hook(&FUEL, Some(fuel), || {
+        let result = op();
+        let remaining = FUEL.with(|f| f.get()).map_or(0, |fuel| fuel.remaining);
+        (result, remaining)
+    })
+}
+
+/// Consumes one unit of the installed fuel (if any), returning the cause to
+/// report if there is none left. Meant to be used from the judgment macro.
+pub fn consume_fuel() -> Option<RuleFailureCause> {
+    FUEL.with(|f| {
+        let mut fuel = f.get()?;
+        if fuel.remaining == 0 {
+            f.set(Some(Fuel {
+                exhausted: true,
+                ..fuel
+            }));
+            return Some(RuleFailureCause::OutOfFuel {
+                budget: fuel.budget,
+            });
+        }
+        fuel.remaining -= 1;
+        f.set(Some(fuel));
+        None
+    })
+}
+
+/// True if the fuel installed with [`with_fuel`] ran out, i.e., a rule failed
+/// with [`RuleFailureCause::OutOfFuel`], since it was installed.
+pub fn ran_out_of_fuel() -> bool {
+    FUEL.with(|f| f.get().is_some_and(|fuel| fuel.exhausted))
+}
@@ -0,0 +1,31 @@
+//! The settings that judgments consult for every rule they try (a rule filter,
+//! fuel, derivation recording, a shuffle seed, or a selection to record or
+//! replay) are installed as hooks, so that with none installed, trying a rule
+//! costs a single check of [`hooks_installed`].
+
+use std::cell::Cell;
+use std::thread::LocalKey;
+
+use crate::scoped::{with_scoped, ScopedCell};
+
+thread_local! {
+    /// The number of hooks installed on this thread.
+    static HOOKS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Like [`with_scoped`], also counting the setting as an installed hook for
+/// the extent of `op`.
+pub(super) fn with_hook<C: ScopedCell, R>(
+    key: &'static LocalKey<C>,
+    value: C::Value,
+    op: impl FnOnce() -> R,
+) -> R {
+    let hooks = HOOKS.with(|h| h.get());
+    with_scoped(&HOOKS, hooks + 1, || with_scoped(key, value, op))
+}
+
+/// True if any hook is installed on this thread. Meant to be used from the
+/// judgment macro.
+pub fn hooks_installed() -> bool {
+    HOOKS.with(|h| h.get() > 0)
+}
@@ -1,4 +1,4 @@
-use crate::{set, Set};
+use crate::{set, Map, Set};
 use std::{
     fmt::Debug,
     hash::{Hash, Hasher},
@@ -10,6 +10,10 @@ use std::{
 #[must_use]
 pub struct ProvenSet<T> {
     data: Data<T>,
+
+    /// Tags recording why the items were proven, e.g., which `trivial_tagged`
+    /// case of a judgment applied. Empty unless something attached a tag.
+    provenance: Set<String>,
 }
 
 #[derive(Clone)]
@@ -20,7 +24,10 @@ enum Data<T> {
 
 impl<T> From<Data<T>> for ProvenSet<T> {
     fn from(data: Data<T>) -> Self {
-        ProvenSet { data }
+        ProvenSet {
+            data,
+            provenance: Set::new(),
+        }
     }
 }
 
@@ -55,6 +62,38 @@ impl<T: Ord + Debug> ProvenSet<T> {
         .into()
     }
 
+    /// Creates a judgment set that failed because `description` refers to `name`,
+    /// which was never declared.
+    #[track_caller]
+    pub fn unknown_predicate(
+        description: impl std::fmt::Display,
+        name: impl std::fmt::Display,
+    ) -> Self {
+        FailedJudgment::new(
+            description.to_string(),
+            set![FailedRule::new(RuleFailureCause::UnknownPredicate {
+                name: name.to_string()
+            })],
+        )
+        .into()
+    }
+
+    /// Creates a judgment set that failed because the negative impl `impl_origin`
+    /// shows that `description` can never hold.
+    #[track_caller]
+    pub fn disproven_by_negative_impl(
+        description: impl std::fmt::Display,
+        impl_origin: impl std::fmt::Display,
+    ) -> Self {
+        FailedJudgment::new(
+            description.to_string(),
+            set![FailedRule::new(RuleFailureCause::DisprovenByNegativeImpl {
+                impl_origin: impl_origin.to_string()
+            })],
+        )
+        .into()
+    }
+
     /// Creates a judgment set that resulted from a failed judgment.
     /// Meant to be used from the judgment macro, probably annoying to call manually.
     pub fn failed_rules(judgment: impl std::fmt::Debug, failed_rules: Set<FailedRule>) -> Self {
@@ -73,6 +112,14 @@ impl<T: Ord + Debug> ProvenSet<T> {
         }
     }
 
+    /// The reason the judgment failed, if it did.
+    pub fn failure(&self) -> Option<&FailedJudgment> {
+        match &self.data {
+            Data::Failure(e) => Some(e),
+            Data::Success(_) => None,
+        }
+    }
+
     pub fn check_proven(self) -> Result<(), Box<FailedJudgment>> {
         match self.data {
             Data::Failure(e) => Err(e),
@@ -80,6 +127,29 @@ impl<T: Ord + Debug> ProvenSet<T> {
         }
     }
 
+    /// Records `tag` as part of why the items of this set were proven.
+    pub fn with_provenance(mut self, tag: impl std::fmt::Display) -> Self {
+        self.provenance.insert(tag.to_string());
+        self
+    }
+
+    /// The tags recorded with [`Self::with_provenance`].
+    pub fn provenance(&self) -> &Set<String> {
+        &self.provenance
+    }
+
+    /// Renders the result like `Display`, followed by its provenance (if any).
+    pub fn explain(&self) -> String {
+        let mut s = self.to_string();
+        if !self.provenance.is_empty() {
+            s.push_str("because:\n");
+            for tag in &self.provenance {
+                s.push_str(&format!("  {tag}\n"));
+            }
+        }
+        s
+    }
+
     /// Convert to a non-empty set of proven results (if ok) or an error (otherwise).
     pub fn into_set(self) -> Result<Set<T>, Box<FailedJudgment>> {
         match self.data {
@@ -91,6 +161,13 @@ impl<T: Ord + Debug> ProvenSet<T> {
         }
     }
 
+    /// Like [`Self::into_set`], but without boxing the error, so that callers
+    /// can propagate the failure with `?` into any error type that has a
+    /// `From<FailedJudgment>` conversion (such as `anyhow::Error`).
+    pub fn into_result(self) -> Result<Set<T>, FailedJudgment> {
+        self.into_set().map_err(|e| *e)
+    }
+
     /// Iterate through all solutions.
     pub fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
         match &self.data {
@@ -110,10 +187,9 @@ impl<T: Ord + Debug> ProvenSet<T> {
         I: TryIntoIter<Item = U>,
         U: Ord + Debug,
     {
-        match self.data {
-            Data::Failure(e) => ProvenSet {
-                data: Data::Failure(e),
-            },
+        let ProvenSet { data, provenance } = self;
+        match data {
+            Data::Failure(e) => Data::Failure(e).into(),
             Data::Success(set) => {
                 let mut items = set![];
                 let mut failures = set![];
@@ -129,7 +205,10 @@ impl<T: Ord + Debug> ProvenSet<T> {
                 }
 
                 if !items.is_empty() {
-                    ProvenSet::proven(items)
+                    ProvenSet {
+                        data: Data::Success(items),
+                        provenance,
+                    }
                 } else {
                     ProvenSet::failed_rules("flat_map", failures)
                 }
@@ -149,6 +228,53 @@ impl<T: Ord + Debug> ProvenSet<T> {
         self.flat_map(|elem| set![op(elem)])
     }
 
+    /// For each item `t` that was proven, invoke `op(t)` to prove something
+    /// further and collect the results. Unlike [`Self::flat_map`], the
+    /// provenance of each successful `op(t)` is kept. If every `op(t)` fails,
+    /// the result fails with each of those failures.
+    #[track_caller]
+    pub fn and_then<U>(self, mut op: impl FnMut(T) -> ProvenSet<U>) -> ProvenSet<U>
+    where
+        U: Ord + Debug,
+    {
+        let ProvenSet {
+            data,
+            mut provenance,
+        } = self;
+        match data {
+            Data::Failure(e) => Data::Failure(e).into(),
+            Data::Success(set) => {
+                let mut items = set![];
+                let mut failures = set![];
+
+                for item in set {
+                    let ProvenSet {
+                        data,
+                        provenance: item_provenance,
+                    } = op(item);
+                    match data {
+                        Data::Success(s) => {
+                            items.extend(s);
+                            provenance.extend(item_provenance);
+                        }
+                        Data::Failure(e) => {
+                            failures.insert(FailedRule::new(RuleFailureCause::FailedJudgment(e)));
+                        }
+                    }
+                }
+
+                if !items.is_empty() {
+                    ProvenSet {
+                        data: Data::Success(items),
+                        provenance,
+                    }
+                } else {
+                    ProvenSet::failed_rules("and_then", failures)
+                }
+            }
+        }
+    }
+
     /// Convenience function for tests: asserts that the proven set is ok and that the debug value is as expected.
     #[track_caller]
     pub fn assert_ok(&self, expect: expect_test::Expect) {
@@ -197,7 +323,10 @@ impl<T: PartialEq> PartialEq for Data<T> {
 
 impl<T: std::fmt::Debug> std::fmt::Debug for ProvenSet<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let Self { data } = self;
+        let Self {
+            data,
+            provenance: _,
+        } = self;
         std::fmt::Debug::fmt(data, f)
     }
 }
@@ -248,7 +377,10 @@ impl<T: Hash> Hash for Data<T> {
     }
 }
 
+/// With the `serde` feature, failures can be serialized (e.g., to JSON) for
+/// tools that present them without parsing the rendered text.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FailedJudgment {
     /// Trying to prove this judgment...
     pub judgment: String,
@@ -276,19 +408,111 @@ impl FailedJudgment {
         // Strip cycles out from the set of failed rules. Note that this algorithm
         // has an O(n^2) character, as the set of failed rules will get recursively
         // simplified as we progress up the stack, but .. who cares?
-        let (failed_rules, _) = Self::strip_cycles(set![&judgment], failed_rules);
+        let (failed_rules, _) = Self::strip_cycles(vec![&judgment], failed_rules);
         Self {
             judgment,
             failed_rules,
         }
     }
 
+    /// The cause that best explains this failure. Starting from this judgment,
+    /// we follow the rule that got furthest (the one that failed at the latest
+    /// step) down through nested judgment failures until we reach a cause that
+    /// is not itself a failed judgment with rules to explore.
+    /// Returns `None` if no rule applied at all.
+    pub fn most_relevant_cause(&self) -> Option<&RuleFailureCause> {
+        // `max_by_key` keeps the last maximum, so reverse to break ties in favor of the first rule.
+        let furthest = self.failed_rules.iter().rev().max_by_key(|rule| {
+            rule.rule_name_index
+                .as_ref()
+                .map_or(0, |(_, step_index)| *step_index)
+        })?;
+        match &furthest.cause {
+            RuleFailureCause::FailedJudgment(judgment) if !judgment.failed_rules.is_empty() => {
+                judgment.most_relevant_cause()
+            }
+            cause => Some(cause),
+        }
+    }
+
+    /// A smaller explanation of the same failure, for when the full set of
+    /// partially matching rules is too much. Rules that failed for the same
+    /// cause (after minimizing nested failures) are collapsed into the one that
+    /// got furthest, as the others are subsumed by it. `self` is left as is.
+    pub fn minimize(&self) -> FailedJudgment {
+        let step_index = |rule: &FailedRule| rule.rule_name_index.as_ref().map(|(_, i)| *i);
+        let mut kept: Vec<FailedRule> = vec![];
+        for rule in &self.failed_rules {
+            let rule = FailedRule {
+                cause: match &rule.cause {
+                    RuleFailureCause::FailedJudgment(judgment) => {
+                        RuleFailureCause::FailedJudgment(Box::new(judgment.minimize()))
+                    }
+                    cause => cause.clone(),
+                },
+                ..rule.clone()
+            };
+            match kept.iter_mut().find(|k| k.cause == rule.cause) {
+                Some(k) if step_index(&rule) > step_index(k) => *k = rule,
+                Some(_) => {}
+                None => kept.push(rule),
+            }
+        }
+        FailedJudgment {
+            judgment: self.judgment.clone(),
+            failed_rules: kept.into_iter().collect(),
+        }
+    }
+
+    /// The failed rules of this judgment, grouped by the category they were
+    /// tagged with. Untagged rules are grouped under `None`.
+    pub fn failed_rules_by_category(&self) -> Map<Option<&str>, Vec<&FailedRule>> {
+        let mut groups: Map<Option<&str>, Vec<&FailedRule>> = Map::new();
+        for rule in &self.failed_rules {
+            groups
+                .entry(rule.category.as_deref())
+                .or_default()
+                .push(rule);
+        }
+        groups
+    }
+
+    /// The cycles that this failure (or a judgment nested within it) ran into,
+    /// each given as the `stack` of its [`RuleFailureCause::Cycle`]. A failure
+    /// with cycles may be due only to cutting them off, rather than a rule that
+    /// genuinely does not apply.
+    pub fn cycles(&self) -> Vec<&[String]> {
+        let mut cycles = vec![];
+        for rule in &self.failed_rules {
+            match &rule.cause {
+                RuleFailureCause::Cycle { stack, .. } => cycles.push(&stack[..]),
+                RuleFailureCause::FailedJudgment(judgment) => cycles.extend(judgment.cycles()),
+                _ => {}
+            }
+        }
+        cycles
+    }
+
+    /// True if this failure may be due only to a limit (recursion, fuel or
+    /// iterations) or to cutting off a cycle somewhere within it, rather than to
+    /// no rule applying, so that it does not show the judgment cannot hold.
+    pub fn is_inconclusive(&self) -> bool {
+        self.failed_rules.iter().any(|rule| match &rule.cause {
+            RuleFailureCause::Cycle { .. }
+            | RuleFailureCause::Overflow { .. }
+            | RuleFailureCause::OutOfFuel { .. }
+            | RuleFailureCause::IterationLimit { .. } => true,
+            RuleFailureCause::FailedJudgment(judgment) => judgment.is_inconclusive(),
+            _ => false,
+        })
+    }
+
     /// Simplifies a set of failed rules to exclude cycles, but only if there are non-cyclic results to show instead.
     /// Recursively Given a set of failed rules, along with the `stack` of judgments that was being solved at the time these failures occurred,
     /// returns `(set, has_non_cycle)`. The `set` represents a new, simplified set of failued
     #[tracing::instrument(level = "Debug", skip(stack), ret)]
     fn strip_cycles(
-        stack: Set<&String>,
+        stack: Vec<&String>,
         failed_rules: Set<FailedRule>,
     ) -> (Set<FailedRule>, HasNonCycle) {
         // Collect all the failures that were due to cycles
@@ -304,11 +528,17 @@ impl FailedJudgment {
 
             if let RuleFailureCause::FailedJudgment(mut judgment) = failed_rule.cause {
                 // Recursive case: we failed because a judgment failed...
-                if stack.contains(&judgment.judgment) && judgment.failed_rules.is_empty() {
+                let on_stack = stack.iter().position(|j| **j == judgment.judgment);
+                if let (Some(start), true) = (on_stack, judgment.failed_rules.is_empty()) {
                     // ...if that judgment was already on the stack, and we didn't have a more interesting reason,
                     // then this is a failure.
                     failed_rule.cause = RuleFailureCause::Cycle {
                         judgment: judgment.judgment.clone(),
+                        stack: stack[start..]
+                            .iter()
+                            .map(|j| j.to_string())
+                            .chain(Some(judgment.judgment.clone()))
+                            .collect(),
                     };
                     cycles.insert(failed_rule);
                 } else {
@@ -317,7 +547,7 @@ impl FailedJudgment {
                     // ultimately failed because of a cycle.
 
                     let mut stack1 = stack.clone();
-                    stack1.insert(&judgment.judgment);
+                    stack1.push(&judgment.judgment);
 
                     let judgment_has_non_cycle;
                     (judgment.failed_rules, judgment_has_non_cycle) =
@@ -355,6 +585,9 @@ pub struct FailedRule {
     /// (if None, then there is only a single rule and this is not relevant)...
     pub rule_name_index: Option<(String, usize)>,
 
+    /// ...which was tagged with this category (if any)...
+    pub category: Option<String>,
+
     /// ...and is located in this file...
     pub file: String,
 
@@ -368,12 +601,35 @@ pub struct FailedRule {
     pub cause: RuleFailureCause,
 }
 
+/// Serialized as `{"rule", "step", "category", "file", "line", "column", "cause"}`,
+/// where `rule` and `step` are `null` for a judgment with a single rule.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FailedRule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("FailedRule", 7)?;
+        s.serialize_field("rule", &self.rule_name_index.as_ref().map(|(name, _)| name))?;
+        s.serialize_field(
+            "step",
+            &self.rule_name_index.as_ref().map(|(_, index)| index),
+        )?;
+        s.serialize_field("category", &self.category)?;
+        s.serialize_field("file", &self.file)?;
+        s.serialize_field("line", &self.line)?;
+        s.serialize_field("column", &self.column)?;
+        s.serialize_field("cause", &self.cause)?;
+        s.end()
+    }
+}
+
 impl FailedRule {
     #[track_caller]
     pub fn new(cause: RuleFailureCause) -> Self {
         let location = std::panic::Location::caller();
         FailedRule {
             rule_name_index: None,
+            category: None,
             file: location.file().to_string(),
             line: location.line(),
             column: location.column(),
@@ -382,7 +638,15 @@ impl FailedRule {
     }
 }
 
+/// Serialized with the kind of failure in a `kind` field, e.g.
+/// `{"kind": "empty_collection", "expr": "..."}`; a failed judgment's own fields
+/// appear alongside the kind.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
 pub enum RuleFailureCause {
     /// The rule did not succeed because an `(if X)` condition evaluated to false.
     IfFalse {
@@ -397,6 +661,14 @@ pub enum RuleFailureCause {
     /// The rule did not succeed because an `(if let)` pattern failed to match.
     IfLetDidNotMatch { pattern: String, value: String },
 
+    /// The rule did not apply because an input could not be downcast to the type
+    /// `expected` that its conclusion pattern `pattern` requires.
+    ConclusionPatternMismatch {
+        pattern: String,
+        expected: String,
+        value: String,
+    },
+
     /// The rule did not succeed because the `x` in a `(x => y)` rule was an empty collection.
     EmptyCollection { expr: String },
 
@@ -404,11 +676,51 @@ pub enum RuleFailureCause {
     /// (for the given reason).
     FailedJudgment(Box<FailedJudgment>),
 
+    /// The rule did not succeed because the judgment in a `(not x)` condition was proven.
+    NegativeConditionHeld { expr: String },
+
+    /// The rule did not succeed because the judgment in a `(not x)` condition
+    /// failed inconclusively (see [`FailedJudgment::is_inconclusive`]), so it
+    /// may yet hold.
+    NegationInconclusive { expr: String },
+
+    /// The rule did not apply because the precondition of a `(require x)` step was false.
+    RequirementFailed { expr: String },
+
     /// The rule did not succeed for some custom reason; this is not generated by the macro.
     Inapplicable { reason: String },
 
-    /// The rule attempted to prove something that was already in the process of being proven
-    Cycle { judgment: String },
+    /// The rule attempted to prove something that was already in the process of being proven.
+    /// The `stack` is the chain of judgments that led from `judgment` back to itself,
+    /// starting and ending with it.
+    Cycle {
+        judgment: String,
+        stack: Vec<String>,
+    },
+
+    /// The judgment was not attempted because `limit` invocations of it were already in progress.
+    Overflow { limit: usize },
+
+    /// The rule was not attempted because the budget of `budget` rule attempts
+    /// installed with [`with_fuel`](super::with_fuel) was used up.
+    OutOfFuel { budget: u64 },
+
+    /// The judgment `judgment` was still changing its output for `input` after
+    /// `limit` iterations, the limit installed with
+    /// [`with_iteration_limit`](crate::fixed_point::with_iteration_limit).
+    IterationLimit {
+        judgment: String,
+        input: String,
+        limit: usize,
+    },
+
+    /// The judgment refers to something (e.g., a trait) that was never declared,
+    /// as opposed to something that is declared but for which no rule applies.
+    UnknownPredicate { name: String },
+
+    /// The goal can never hold because a negative impl (identified by `impl_origin`)
+    /// applies to it, e.g., `impl !Send for T` blocks `T: Send`.
+    DisprovenByNegativeImpl { impl_origin: String },
 }
 
 impl std::error::Error for FailedJudgment {
@@ -427,73 +739,62 @@ impl std::error::Error for FailedJudgment {
 
 impl std::fmt::Display for FailedJudgment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let FailedJudgment {
-            judgment,
-            failed_rules,
-        } = self;
-        if failed_rules.is_empty() {
-            write!(f, "judgment had no applicable rules: `{judgment}` ",)
+        let header = self.header();
+        if self.failed_rules.is_empty() {
+            write!(f, "{header}")
         } else {
-            let rules: Vec<String> = failed_rules.iter().map(|r| r.to_string()).collect();
+            let rules: Vec<String> = self.failed_rules.iter().map(|r| r.to_string()).collect();
             let rules = indent(rules.join("\n"));
-            write!(
-                f,
-                "judgment `{judgment}` failed at the following rule(s):\n{rules}"
-            )
+            write!(f, "{header}\n{rules}")
+        }
+    }
+}
+
+impl FailedJudgment {
+    /// The first line of this judgment's report; its failed rules follow, indented.
+    pub(super) fn header(&self) -> String {
+        let judgment = &self.judgment;
+        if self.failed_rules.is_empty() {
+            format!("judgment had no applicable rules: `{judgment}` ")
+        } else {
+            format!("judgment `{judgment}` failed at the following rule(s):")
         }
     }
 }
 
 impl std::fmt::Display for FailedRule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n{}", self.header(), indent(&self.cause))
+    }
+}
+
+impl FailedRule {
+    /// The first line of this rule's report; its cause follows, indented.
+    pub(super) fn header(&self) -> String {
         let FailedRule {
             rule_name_index,
+            category,
             file,
             line,
             column,
-            cause,
+            cause: _,
         } = self;
 
         if let Some((rule_name, step_index)) = rule_name_index {
-            write!(
-                f,
-                "the rule {rule_name:?} failed at step #{step_index} ({file}:{line}:{column}) because\n{cause}",
-                cause = indent(cause),
-            )
+            let category = match category {
+                Some(category) => format!(" [category: {category:?}]"),
+                None => String::new(),
+            };
+            format!("the rule {rule_name:?}{category} failed at step #{step_index} ({file}:{line}:{column}) because")
         } else {
-            write!(
-                f,
-                "failed at ({file}:{line}:{column}) because\n{cause}",
-                cause = indent(cause),
-            )
+            format!("failed at ({file}:{line}:{column}) because")
         }
     }
 }
 
 impl std::fmt::Display for RuleFailureCause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            RuleFailureCause::IfFalse { expr, args } => {
-                write!(f, "condition evaluted to false: `{expr}`")?;
-                for (arg_expr, arg_value) in args {
-                    write!(f, "\n  {arg_expr} = {arg_value}")?;
-                }
-                Ok(())
-            }
-            RuleFailureCause::IfLetDidNotMatch { pattern, value } => {
-                write!(f, "pattern `{pattern}` did not match value `{value}`")
-            }
-            RuleFailureCause::EmptyCollection { expr } => {
-                write!(f, "expression evaluated to an empty collection: `{expr}`")
-            }
-            RuleFailureCause::FailedJudgment(judgment) => std::fmt::Display::fmt(judgment, f),
-            RuleFailureCause::Inapplicable { reason } => {
-                write!(f, "{reason}")
-            }
-            RuleFailureCause::Cycle { judgment } => {
-                write!(f, "cyclic proof attempt: `{judgment}`")
-            }
-        }
+        super::render::render_cause(self, f)
     }
 }
 
@@ -0,0 +1,191 @@
+//! Rendering of failure causes.
+//!
+//! The `Display` impls for [`FailedJudgment`] and friends route each
+//! [`RuleFailureCause`] through a [`FailureRenderer`]. By default that is
+//! [`DefaultFailureRenderer`], but tools that want their own phrasing (IDE
+//! diagnostics, rustc-style messages, etc) can install a different one for
+//! the duration of a closure with [`with_failure_renderer`].
+
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use super::{FailedJudgment, RuleFailureCause};
+use crate::scoped::with_scoped;
+
+/// Renders the cause of a failed rule. Each method corresponds to a variant of
+/// [`RuleFailureCause`] and defaults to the standard output, so implementors
+/// only override the causes they care about.
+pub trait FailureRenderer {
+    fn render_cause(&self, cause: &RuleFailureCause, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match cause {
+            RuleFailureCause::IfFalse { expr, args } => self.if_false(expr, args, f),
+            RuleFailureCause::IfLetDidNotMatch { pattern, value } => {
+                self.if_let_did_not_match(pattern, value, f)
+            }
+            RuleFailureCause::ConclusionPatternMismatch {
+                pattern,
+                expected,
+                value,
+            } => self.conclusion_pattern_mismatch(pattern, expected, value, f),
+            RuleFailureCause::EmptyCollection { expr } => self.empty_collection(expr, f),
+            RuleFailureCause::NegativeConditionHeld { expr } => {
+                self.negative_condition_held(expr, f)
+            }
+            RuleFailureCause::NegationInconclusive { expr } => self.negation_inconclusive(expr, f),
+            RuleFailureCause::RequirementFailed { expr } => self.requirement_failed(expr, f),
+            RuleFailureCause::FailedJudgment(judgment) => self.failed_judgment(judgment, f),
+            RuleFailureCause::Inapplicable { reason } => self.inapplicable(reason, f),
+            RuleFailureCause::Cycle { judgment, stack } => self.cycle(judgment, stack, f),
+            RuleFailureCause::Overflow { limit } => self.overflow(*limit, f),
+            RuleFailureCause::OutOfFuel { budget } => self.out_of_fuel(*budget, f),
+            RuleFailureCause::IterationLimit {
+                judgment,
+                input,
+                limit,
+            } => self.iteration_limit(judgment, input, *limit, f),
+            RuleFailureCause::UnknownPredicate { name } => self.unknown_predicate(name, f),
+            RuleFailureCause::DisprovenByNegativeImpl { impl_origin } => {
+                self.disproven_by_negative_impl(impl_origin, f)
+            }
+        }
+    }
+
+    fn if_false(
+        &self,
+        expr: &str,
+        args: &[(String, String)],
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "condition evaluted to false: `{expr}`")?;
+        for (arg_expr, arg_value) in args {
+            write!(f, "\n  {arg_expr} = {arg_value}")?;
+        }
+        Ok(())
+    }
+
+    fn if_let_did_not_match(
+        &self,
+        pattern: &str,
+        value: &str,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "pattern `{pattern}` did not match value `{value}`")
+    }
+
+    fn conclusion_pattern_mismatch(
+        &self,
+        pattern: &str,
+        expected: &str,
+        value: &str,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "conclusion pattern `{pattern}` expects a `{expected}`, not value `{value}`"
+        )
+    }
+
+    fn empty_collection(&self, expr: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expression evaluated to an empty collection: `{expr}`")
+    }
+
+    fn negative_condition_held(&self, expr: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negated judgment was proven: `{expr}`")
+    }
+
+    fn negation_inconclusive(&self, expr: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negated judgment was inconclusive: `{expr}`")
+    }
+
+    fn requirement_failed(&self, expr: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "requirement was not met: `{expr}`")
+    }
+
+    /// A nested judgment failed. The default renders it with its `Display` impl,
+    /// which in turn routes its own causes back through the installed renderer.
+    fn failed_judgment(
+        &self,
+        judgment: &FailedJudgment,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        fmt::Display::fmt(judgment, f)
+    }
+
+    fn inapplicable(&self, reason: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{reason}")
+    }
+
+    /// A cycle back to `judgment`. The default lists the judgments in `stack`
+    /// between the two occurrences of `judgment`, if there are any.
+    fn cycle(&self, judgment: &str, stack: &[String], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cyclic proof attempt: `{judgment}`")?;
+        let through = stack.get(1..stack.len().saturating_sub(1)).unwrap_or(&[]);
+        if !through.is_empty() {
+            write!(f, ", reached through:")?;
+            for j in through {
+                write!(f, "\n  `{j}`")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn overflow(&self, limit: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "recursion limit of {limit} reached")
+    }
+
+    fn out_of_fuel(&self, budget: u64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fuel budget of {budget} rule attempts used up")
+    }
+
+    fn iteration_limit(
+        &self,
+        judgment: &str,
+        input: &str,
+        limit: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "`{judgment}` did not reach a fixed point within {limit} iterations for `{input}`"
+        )
+    }
+
+    fn unknown_predicate(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{name}` is not declared")
+    }
+
+    fn disproven_by_negative_impl(
+        &self,
+        impl_origin: &str,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "disproven by `{impl_origin}`")
+    }
+}
+
+/// The renderer used when no other has been installed.
+pub struct DefaultFailureRenderer;
+
+impl FailureRenderer for DefaultFailureRenderer {}
+
+thread_local! {
+    static RENDERER: RefCell<Option<Rc<dyn FailureRenderer>>> = const { RefCell::new(None) };
+}
+
+/// Runs `op` with `renderer` installed as the renderer for failure causes on
+/// this thread, restoring the previous renderer afterwards.
+pub fn with_failure_renderer<R>(
+    renderer: impl FailureRenderer + 'static,
+    op: impl FnOnce() -> R,
+) -> R {
+    with_scoped(&RENDERER, Some(Rc::new(renderer)), op)
+}
+
+/// Renders `cause` with the installed renderer, or the default one if none is installed.
+pub(super) fn render_cause(cause: &RuleFailureCause, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // Clone the `Rc` out so that no borrow is held while rendering, since
+    // rendering nested judgments comes back here.
+    match RENDERER.with(|r| r.borrow().clone()) {
+        Some(renderer) => renderer.render_cause(cause, f),
+        None => DefaultFailureRenderer.render_cause(cause, f),
+    }
+}
@@ -0,0 +1,169 @@
+//! Seeded shuffling of the order in which judgments explore alternatives.
+//!
+//! Judgment results are sets and every collection in the solver is ordered,
+//! so in principle the order in which the alternatives of a `(expr => pat)`
+//! step are tried has no effect on the outcome. In practice that is worth
+//! checking (e.g. cycles are resolved by fixed-point iteration, which could
+//! hide an order dependence). Installing a seed with [`with_shuffle_seed`]
+//! permutes those alternatives pseudo-randomly, but reproducibly: the same
+//! seed yields the same permutations on every run.
+//!
+//! To reproduce one particular order regardless of how it came about, record
+//! it with [`record_selection`] and replay it with [`force_selection`].
+
+use std::cell::{Cell, RefCell};
+
+use super::hooks::{hooks_installed, with_hook};
+
+thread_local! {
+    static SHUFFLE: Cell<Option<ShuffleState>> = const { Cell::new(None) };
+}
+
+#[derive(Copy, Clone)]
+struct ShuffleState {
+    seed: u64,
+    rng: u64,
+}
+
+/// Runs `op` with alternatives shuffled according to `seed` on this thread,
+/// restoring the previous setting afterwards.
+pub fn with_shuffle_seed<R>(seed: u64, op: impl FnOnce() -> R) -> R {
+    with_hook(&SHUFFLE, Some(ShuffleState { seed, rng: seed }), op)
+}
+
+/// The seed installed by the innermost [`with_shuffle_seed`], if any.
+/// Include it when reporting a failure so that the run can be reproduced.
+pub fn shuffle_seed() -> Option<u64> {
+    SHUFFLE.with(|s| s.get().map(|state| state.seed))
+}
+
+/// The order in which each call to [`shuffled`] yielded its items during a run,
+/// as captured by [`record_selection`]. Since judgments pick among their
+/// alternatives (e.g. the impls that might prove a goal) through [`shuffled`],
+/// this determines the order in which solutions are found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectionTrace {
+    /// For each call, in the order they were made, the indices of the items
+    /// in the order they were yielded.
+    pub orders: Vec<Vec<usize>>,
+}
+
+enum Selection {
+    Record(SelectionTrace),
+    Replay(std::vec::IntoIter<Vec<usize>>),
+}
+
+thread_local! {
+    static SELECTION: RefCell<Option<Selection>> = const { RefCell::new(None) };
+}
+
+/// Runs `op` with `selection` installed on this thread, returning what is left
+/// of it afterwards and restoring the previous setting.
+fn with_selection<R>(selection: Selection, op: impl FnOnce() -> R) -> (R, Option<Selection>) {
+    with_hook(&SELECTION, Some(selection), || {
+        let result = op();
+        (result, SELECTION.with(|s| s.borrow_mut().take()))
+    })
+}
+
+/// Runs `op`, recording the order in which alternatives were tried on this
+/// thread (whether shuffled or not) so they can be replayed with [`force_selection`].
+pub fn record_selection<R>(op: impl FnOnce() -> R) -> (R, SelectionTrace) {
+    match with_selection(Selection::Record(SelectionTrace::default()), op) {
+        (result, Some(Selection::Record(trace))) => (result, trace),
+        _ => unreachable!(),
+    }
+}
+
+/// Runs `op` such that alternatives are tried in the order recorded in `trace`,
+/// taking precedence over any shuffle seed. The run must make the same calls
+/// to [`shuffled`] as the recorded one; this panics if it diverges.
+pub fn force_selection<R>(trace: &SelectionTrace, op: impl FnOnce() -> R) -> R {
+    let replay = Selection::Replay(trace.orders.clone().into_iter());
+    with_selection(replay, op).0
+}
+
+/// Yields `items` in order, in a seeded permutation if a shuffle seed is
+/// installed, or in the order forced by [`force_selection`].
+pub fn shuffled<I: IntoIterator>(items: I) -> Shuffled<I::IntoIter> {
+    if !hooks_installed() {
+        return Shuffled::InOrder(items.into_iter());
+    }
+    let shuffle = SHUFFLE.with(|s| s.get());
+    if shuffle.is_none() && SELECTION.with(|s| s.borrow().is_none()) {
+        return Shuffled::InOrder(items.into_iter());
+    }
+
+    let mut items: Vec<Option<I::Item>> = items.into_iter().map(Some).collect();
+    let order = match forced_order() {
+        Some(order) => {
+            assert_eq!(
+                order.len(),
+                items.len(),
+                "selection trace does not match this run"
+            );
+            order
+        }
+        None => {
+            let mut order: Vec<usize> = (0..items.len()).collect();
+            if let Some(mut state) = shuffle {
+                // Fisher-Yates, driven by splitmix64.
+                for i in (1..order.len()).rev() {
+                    let j = (next_random(&mut state.rng) % (i as u64 + 1)) as usize;
+                    order.swap(i, j);
+                }
+                SHUFFLE.with(|s| s.set(Some(state)));
+            }
+            order
+        }
+    };
+
+    SELECTION.with(|s| {
+        if let Some(Selection::Record(trace)) = &mut *s.borrow_mut() {
+            trace.orders.push(order.clone());
+        }
+    });
+
+    let items: Vec<I::Item> = order
+        .into_iter()
+        .map(|i| items[i].take().unwrap())
+        .collect();
+    Shuffled::Permuted(items.into_iter())
+}
+
+/// The next order to use if one is being replayed by [`force_selection`].
+fn forced_order() -> Option<Vec<usize>> {
+    SELECTION.with(|s| match &mut *s.borrow_mut() {
+        Some(Selection::Replay(orders)) => Some(
+            orders
+                .next()
+                .expect("selection trace ended before this run did"),
+        ),
+        _ => None,
+    })
+}
+
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Iterator returned by [`shuffled`].
+pub enum Shuffled<I: Iterator> {
+    InOrder(I),
+    Permuted(std::vec::IntoIter<I::Item>),
+}
+
+impl<I: Iterator> Iterator for Shuffled<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self {
+            Shuffled::InOrder(i) => i.next(),
+            Shuffled::Permuted(i) => i.next(),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+use crate::{judgment_fn, set, ProvenSet};
+use formality_macros::test;
+
+judgment_fn! {
+    fn divisors(n: u32) => u32 {
+        debug(n)
+
+        (
+            (for d in 1..=n)
+            (if n % d == 0)
+            --------------------------------------- ("divides")
+            (divisors(n) => d)
+        )
+    }
+}
+
+judgment_fn! {
+    fn is_even(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n % 2 == 0)
+            --------------------------------------- ("even")
+            (is_even(n) => n)
+        )
+    }
+}
+
+#[test]
+fn results_are_combined() {
+    divisors(6)
+        .and_then(is_even)
+        .assert_ok(expect_test::expect![[r#"
+            {
+              2,
+              6,
+            }
+        "#]]);
+}
+
+#[test]
+fn failures_are_kept() {
+    divisors(3)
+        .and_then(is_even)
+        .assert_err(expect_test::expect![[r#"
+            judgment `"and_then"` failed at the following rule(s):
+              failed at (src/file.rs:LL:CC) because
+                judgment `is_even { n: 1 }` failed at the following rule(s):
+                  the rule "even" failed at step #0 (src/file.rs:LL:CC) because
+                    condition evaluted to false: `n % 2 == 0`
+              failed at (src/file.rs:LL:CC) because
+                judgment `is_even { n: 3 }` failed at the following rule(s):
+                  the rule "even" failed at step #0 (src/file.rs:LL:CC) because
+                    condition evaluted to false: `n % 2 == 0`"#]]);
+}
+
+#[test]
+fn provenance_of_each_result_is_kept() {
+    let result = ProvenSet::proven(set![1, 2])
+        .and_then(|n: u32| ProvenSet::singleton(n * 10).with_provenance(format!("from {n}")));
+    expect_test::expect![[r#"
+        {
+          10,
+          20,
+        }
+        because:
+          from 1
+          from 2
+    "#]]
+    .assert_eq(&result.explain());
+}
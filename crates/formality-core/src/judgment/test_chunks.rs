@@ -0,0 +1,59 @@
+#![cfg(test)]
+
+use crate::judgment::ChunkBy;
+use crate::judgment_fn;
+use crate::test_util::normalize_paths;
+
+judgment_fn!(
+    fn descend(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n > 0)
+            (descend(n - 1) => m)
+            --------------------------------------- ("step")
+            (descend(n) => m)
+        )
+
+        (
+            (if n == 100)
+            --------------------------------------- ("hundred")
+            (descend(n) => n)
+        )
+    }
+);
+
+#[test]
+fn chunks_reconstruct_the_report() {
+    let result = descend(3);
+    let failure = result.failure().unwrap();
+    let report = failure.to_string();
+
+    for by in [
+        ChunkBy::Rule { max_depth: 0 },
+        ChunkBy::Rule { max_depth: 10 },
+        ChunkBy::Lines(1),
+        ChunkBy::Lines(4),
+    ] {
+        let chunks: Vec<String> = failure.render_chunks(by).collect();
+        assert!(chunks.len() > 1, "{by:?} yielded a single chunk");
+        assert_eq!(chunks.join("\n"), report, "{by:?}");
+    }
+}
+
+#[test]
+fn chunks_by_top_level_rule() {
+    let result = descend(1);
+    let failure = result.failure().unwrap();
+    let chunks: Vec<String> = failure
+        .render_chunks(ChunkBy::Rule { max_depth: 0 })
+        .map(normalize_paths)
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "judgment `descend { n: 1 }` failed at the following rule(s):",
+            "  the rule \"hundred\" failed at step #0 (src/file.rs:LL:CC) because\n    condition evaluted to false: `n == 100`",
+            "  the rule \"step\" failed at step #1 (src/file.rs:LL:CC) because\n    judgment `descend { n: 0 }` failed at the following rule(s):\n      the rule \"hundred\" failed at step #0 (src/file.rs:LL:CC) because\n        condition evaluted to false: `n == 100`\n      the rule \"step\" failed at step #0 (src/file.rs:LL:CC) because\n        condition evaluted to false: `n > 0`",
+        ]
+    "#]].assert_debug_eq(&chunks);
+}
@@ -0,0 +1,80 @@
+#![cfg(test)]
+
+use std::sync::Arc;
+
+use crate::{cast_impl, judgment_fn};
+
+/// A graph, standing in for a database. It deliberately implements neither
+/// `Eq` nor `Hash`, which a `#[context]` input does not need.
+#[derive(Clone, Debug)]
+struct Graph {
+    edges: Arc<Vec<(u32, u32)>>,
+}
+
+cast_impl!(Graph);
+
+impl Graph {
+    fn new(edges: &[(u32, u32)]) -> Self {
+        Graph {
+            edges: Arc::new(edges.to_vec()),
+        }
+    }
+
+    fn successors(&self, node: u32) -> Vec<u32> {
+        self.edges
+            .iter()
+            .filter(|(from, _)| *from == node)
+            .map(|(_, to)| *to)
+            .collect()
+    }
+}
+
+judgment_fn!(
+    fn reachable(#[context] graph: Graph, from: u32) => u32 {
+        debug(from, graph)
+
+        (
+            (graph.successors(from) => to)
+            --------------------------------------- ("edge")
+            (reachable(graph, from) => to)
+        )
+
+        (
+            (graph.successors(from) => mid)
+            (reachable(&graph, mid) => to)
+            --------------------------------------- ("transitive")
+            (reachable(graph, from) => to)
+        )
+    }
+);
+
+#[test]
+fn equal_goals_in_different_contexts() {
+    let line = Graph::new(&[(0, 1), (1, 2)]);
+    let star = Graph::new(&[(0, 3), (0, 4)]);
+
+    reachable(&line, 0u32).assert_ok(expect_test::expect![[r#"
+        {
+          1,
+          2,
+        }
+    "#]]);
+    reachable(&star, 0u32).assert_ok(expect_test::expect![[r#"
+        {
+          3,
+          4,
+        }
+    "#]]);
+}
+
+#[test]
+fn cycles_detected_on_the_goal_alone() {
+    let cycle = Graph::new(&[(0, 1), (1, 2), (2, 0)]);
+    reachable(&cycle, 0u32).assert_ok(expect_test::expect![[r#"
+        {
+          0,
+          1,
+          2,
+        }
+    "#]]);
+}
@@ -0,0 +1,64 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// The numbers a word may stand for: the number it names if it is one we
+    /// know, and otherwise its length or (for "unknown") zero.
+    fn number(word: String) => u32 {
+        debug(word)
+
+        (
+            (if word == "one")
+            --------------------------------------- ("one") [category: "names"] [cut]
+            (number(word) => 1)
+        )
+
+        (
+            (if word == "two")
+            --------------------------------------- [cut]
+            (number(word) => 2)
+        )
+
+        (
+            --------------------------------------- ("length")
+            (number(word) => word.len() as u32)
+        )
+
+        (
+            (if word == "unknown")
+            --------------------------------------- ("unknown")
+            (number(word) => 0)
+        )
+    }
+}
+
+#[test]
+fn cut_skips_remaining_rules() {
+    number("one").assert_ok(expect_test::expect![[r#"
+        {
+          1,
+        }
+    "#]]);
+}
+
+#[test]
+fn unlabeled_cut_skips_remaining_rules() {
+    number("two").assert_ok(expect_test::expect![[r#"
+        {
+          2,
+        }
+    "#]]);
+}
+
+/// The cut rules do not apply, so every other rule is tried.
+#[test]
+fn failed_cut_rule_does_not_cut() {
+    number("unknown").assert_ok(expect_test::expect![[r#"
+        {
+          0,
+          7,
+        }
+    "#]]);
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Holds only if `odd(n)` does.
+    fn even(n: u32) => () {
+        debug(n)
+
+        (
+            (odd(n) => ())
+            --------------------------------------- ("via odd")
+            (even(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    /// Holds only if `even(n)` does.
+    fn odd(n: u32) => () {
+        debug(n)
+
+        (
+            (even(n) => ())
+            --------------------------------------- ("via even")
+            (odd(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    /// Holds for zero, or if `even(n)` does (which it never does).
+    fn zero_or_even(n: u32) => () {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (zero_or_even(n) => ())
+        )
+
+        (
+            (even(n) => ())
+            --------------------------------------- ("even")
+            (zero_or_even(n) => ())
+        )
+    }
+}
+
+/// `even` and `odd` only depend on each other, so the failure is due to the
+/// cycle alone, and reports the chain of judgments that formed it.
+#[test]
+fn cycle_stack_is_reported() {
+    let result = even(0);
+    let failure = result.failure().unwrap();
+    expect_test::expect![[r#"
+        [
+            [
+                "even { n: 0 }",
+                "odd { n: 0 }",
+                "even { n: 0 }",
+            ],
+        ]
+    "#]]
+    .assert_debug_eq(&failure.cycles());
+}
+
+/// When some rule fails for another reason, only that failure is kept, so no
+/// cycles are reported.
+#[test]
+fn cycles_hidden_by_other_failures() {
+    let result = zero_or_even(1);
+    let failure = result.failure().unwrap();
+    assert!(failure.cycles().is_empty());
+}
+
+/// The rendered failure names the judgments the cycle went through.
+#[test]
+fn cycle_stack_is_rendered() {
+    even(0).assert_err(expect_test::expect![[r#"
+        judgment `even { n: 0 }` failed at the following rule(s):
+          the rule "via odd" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `odd { n: 0 }` failed at the following rule(s):
+              the rule "via even" failed at step #0 (src/file.rs:LL:CC) because
+                cyclic proof attempt: `even { n: 0 }`, reached through:
+                  `odd { n: 0 }`"#]]);
+}
@@ -0,0 +1,41 @@
+#![cfg(test)]
+
+use crate::judgment::current_judgment_depth;
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Counts down from `n` to zero, yielding the judgment depth reached at zero.
+    fn depth_at_zero(n: u32) => usize {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (depth_at_zero(n) => current_judgment_depth())
+        )
+
+        (
+            (if n > 0)
+            (depth_at_zero(n - 1) => depth)
+            --------------------------------------- ("succ")
+            (depth_at_zero(n) => depth)
+        )
+    }
+}
+
+#[test]
+fn depth_matches_nesting() {
+    assert_eq!(current_judgment_depth(), 0);
+    depth_at_zero(0).assert_ok(expect_test::expect![[r#"
+        {
+          1,
+        }
+    "#]]);
+    depth_at_zero(3).assert_ok(expect_test::expect![[r#"
+        {
+          4,
+        }
+    "#]]);
+    assert_eq!(current_judgment_depth(), 0);
+}
@@ -0,0 +1,55 @@
+#![cfg(test)]
+
+use crate::judgment::{recorded_derivations, to_dot, with_derivation_recording};
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Halves an even number, counting down by two.
+    fn half(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (half(n) => 0)
+        )
+
+        (
+            (if n >= 2)
+            (half(n - 2) => h)
+            --------------------------------------- ("plus two")
+            (half(n) => h + 1)
+        )
+    }
+}
+
+#[test]
+fn derivation_graph() {
+    let result = with_derivation_recording(|| half(4));
+    expect_test::expect![[r#"
+        digraph derivation {
+          node [shape=box];
+          n0 [label="half { n: 0 }\n(\"zero\")\n=> 0"];
+          n1 [label="half { n: 2 }\n(\"plus two\")\n=> 1"];
+          n2 [label="half { n: 4 }\n(\"plus two\")\n=> 2"];
+          n1 -> n0;
+          n2 -> n1;
+        }"#]]
+    .assert_eq(&to_dot(&result));
+    assert_eq!(recorded_derivations().len(), 3);
+}
+
+#[test]
+fn nothing_recorded_for_failure() {
+    let result = with_derivation_recording(|| half(3));
+    assert!(!result.is_proven());
+    assert_eq!(recorded_derivations(), vec![]);
+}
+
+#[test]
+fn nothing_recorded_outside_recording() {
+    let _ = with_derivation_recording(|| half(2));
+    let _ = half(4);
+    assert_eq!(recorded_derivations().len(), 2);
+}
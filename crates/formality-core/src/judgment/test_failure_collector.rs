@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use crate::judgment::{with_failure_collector, FailureCollector};
+use crate::judgment_fn;
+
+judgment_fn!(
+    fn small(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small")
+            (small(n) => n)
+        )
+    }
+);
+
+judgment_fn!(
+    /// Holds if `n` is even and `n / 2` is small.
+    fn small_half(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n % 2 == 0)
+            (small(n / 2) => m)
+            --------------------------------------- ("half")
+            (small_half(n) => m)
+        )
+    }
+);
+
+#[test]
+fn collects_top_level_failures() {
+    let collector = FailureCollector::new();
+    with_failure_collector(&collector, || {
+        assert!(small_half(4).is_proven());
+        assert!(!small_half(3).is_proven());
+        assert!(!small_half(40).is_proven());
+        assert!(!small(12).is_proven());
+    });
+
+    // Successes are not recorded, and neither is the nested failure of
+    // `small(20)`: only the `small_half(40)` that it caused.
+    let entries: Vec<String> = collector
+        .entries()
+        .iter()
+        .map(|e| format!("{} | {} | {}", e.judgment_name, e.input, e.cause))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "small_half | small_half { n: 3 } | condition evaluted to false: `n % 2 == 0`",
+            "small_half | small_half { n: 40 } | condition evaluted to false: `n < 10`",
+            "small | small { n: 12 } | condition evaluted to false: `n < 10`",
+        ]
+    "#]]
+    .assert_debug_eq(&entries);
+}
+
+#[test]
+fn not_collected_once_uninstalled() {
+    let collector = FailureCollector::new();
+    with_failure_collector(&collector, || assert!(!small(12).is_proven()));
+    assert!(!small(13).is_proven());
+    assert_eq!(collector.len(), 1);
+}
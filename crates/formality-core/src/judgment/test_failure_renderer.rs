@@ -0,0 +1,53 @@
+#![cfg(test)]
+
+use std::fmt;
+
+use crate::judgment::{with_failure_renderer, FailureRenderer};
+use crate::judgment_fn;
+
+judgment_fn!(
+    fn even(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n % 2 == 0)
+            --------------------------------------- ("even")
+            (even(n) => n)
+        )
+    }
+);
+
+/// Rephrases `(if ...)` failures and leaves every other cause alone.
+struct Rephrase;
+
+impl FailureRenderer for Rephrase {
+    fn if_false(
+        &self,
+        expr: &str,
+        _args: &[(String, String)],
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "expected `{expr}` to hold")
+    }
+}
+
+#[test]
+fn default_renderer() {
+    even(3).assert_err(expect_test::expect![[r#"
+        judgment `even { n: 3 }` failed at the following rule(s):
+          the rule "even" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n % 2 == 0`"#]]);
+}
+
+#[test]
+fn custom_renderer() {
+    with_failure_renderer(Rephrase, || {
+        even(3).assert_err(expect_test::expect![[r#"
+            judgment `even { n: 3 }` failed at the following rule(s):
+              the rule "even" failed at step #0 (src/file.rs:LL:CC) because
+                expected `n % 2 == 0` to hold"#]]);
+    });
+
+    // The default renderer is restored afterwards.
+    assert!(even(3).to_string().contains("condition evaluted to false"));
+}
@@ -0,0 +1,44 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Holds for the even numbers among `ns`, which are not judgments themselves.
+    fn even_member(ns: Vec<u32>) => u32 {
+        debug(ns)
+
+        (
+            (for n in &ns)
+            (if n % 2 == 0)
+            --------------------------------------- ("even member")
+            (even_member(ns) => *n)
+        )
+    }
+}
+
+#[test]
+fn each_element_is_tried() {
+    even_member(vec![1, 2, 3, 4]).assert_ok(expect_test::expect![[r#"
+        {
+          2,
+          4,
+        }
+    "#]]);
+}
+
+#[test]
+fn no_element_satisfies_the_rule() {
+    even_member(vec![1, 3]).assert_err(expect_test::expect![[r#"
+        judgment `even_member { ns: [1, 3] }` failed at the following rule(s):
+          the rule "even member" failed at step #1 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n % 2 == 0`"#]]);
+}
+
+#[test]
+fn empty_collection_fails_the_rule() {
+    even_member(Vec::<u32>::new()).assert_err(expect_test::expect![[r#"
+        judgment `even_member { ns: [] }` failed at the following rule(s):
+          the rule "even member" failed at step #0 (src/file.rs:LL:CC) because
+            expression evaluated to an empty collection: `&ns`"#]]);
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use crate::judgment::with_fuel;
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Counts down from `n` to zero; each step tries both rules.
+    fn count_down(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (count_down(n) => 0)
+        )
+
+        (
+            (if n > 0)
+            (count_down(n - 1) => m)
+            --------------------------------------- ("succ")
+            (count_down(n) => m + 1)
+        )
+    }
+}
+
+#[test]
+fn completes_with_fuel_to_spare() {
+    let (result, remaining) = with_fuel(100, || count_down(3));
+    result.assert_ok(expect_test::expect![[r#"
+        {
+          3,
+        }
+    "#]]);
+    // Two rules are attempted at each of the four levels.
+    assert_eq!(remaining, 92);
+}
+
+#[test]
+fn exhausting_fuel_overflows() {
+    let (result, remaining) = with_fuel(5, || count_down(10));
+    assert_eq!(remaining, 0);
+    result.assert_err(expect_test::expect![[r#"
+        judgment `count_down { n: 10 }` failed at the following rule(s):
+          the rule "succ" failed at step #1 (src/file.rs:LL:CC) because
+            judgment `count_down { n: 9 }` failed at the following rule(s):
+              the rule "succ" failed at step #1 (src/file.rs:LL:CC) because
+                judgment `count_down { n: 8 }` failed at the following rule(s):
+                  the rule "succ" failed at step #0 (src/file.rs:LL:CC) because
+                    fuel budget of 5 rule attempts used up
+                  the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+                    condition evaluted to false: `n == 0`
+              the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `n == 0`
+          the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 0`"#]]);
+}
+
+#[test]
+fn unlimited_without_fuel() {
+    count_down(10).assert_ok(expect_test::expect![[r#"
+        {
+          10,
+        }
+    "#]]);
+}
@@ -0,0 +1,44 @@
+#![cfg(test)]
+
+use crate::{judgment::FailedJudgment, judgment_fn, set, Set};
+use formality_macros::test;
+
+judgment_fn! {
+    fn is_even(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n % 2 == 0)
+            --------------------------------------- ("even")
+            (is_even(n) => n)
+        )
+    }
+}
+
+fn evens(ns: &[u32]) -> Result<Set<u32>, FailedJudgment> {
+    let mut evens = set![];
+    for &n in ns {
+        evens.extend(is_even(n).into_result()?);
+    }
+    Ok(evens)
+}
+
+#[test]
+fn proven_results_are_returned() {
+    assert_eq!(evens(&[2, 4]).unwrap(), set![2, 4]);
+}
+
+#[test]
+fn failure_is_propagated() {
+    let error = evens(&[2, 3, 4]).unwrap_err();
+    expect_test::expect![[r#"
+        "is_even { n: 3 }"
+    "#]]
+    .assert_debug_eq(&error.judgment);
+}
+
+#[test]
+fn failure_converts_to_anyhow() {
+    let result: anyhow::Result<Set<u32>> = (|| Ok(is_even(5).into_result()?))();
+    assert!(result.unwrap_err().is::<FailedJudgment>());
+}
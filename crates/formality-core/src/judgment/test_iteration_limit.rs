@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use std::cell::RefCell;
+
+use crate::{
+    fixed_point::{fixed_point, with_iteration_limit, FixedPointStack},
+    judgment_fn, set, Set,
+};
+use formality_macros::test;
+
+judgment_fn! {
+    /// The numbers from 0 up to `max`, found by repeatedly adding one to the
+    /// numbers found so far; each new number takes another fixed point iteration.
+    fn count_up(max: u32) => u32 {
+        debug(max)
+
+        (
+            --------------------------------------- ("zero")
+            (count_up(_max) => 0)
+        )
+
+        (
+            (count_up(max) => n)
+            (if n < max)
+            --------------------------------------- ("succ")
+            (count_up(max) => n + 1)
+        )
+    }
+}
+
+#[test]
+fn converges_within_limit() {
+    let result = with_iteration_limit(10, || count_up(3));
+    result.assert_ok(expect_test::expect![[r#"
+        {
+          0,
+          1,
+          2,
+          3,
+        }
+    "#]]);
+}
+
+#[test]
+fn exceeds_limit() {
+    let result = with_iteration_limit(10, || count_up(u32::MAX));
+    result.assert_err(expect_test::expect![[r#"
+        judgment `count_up { max: 4294967295 }` failed at the following rule(s):
+          failed at (src/file.rs:LL:CC) because
+            `count_up` did not reach a fixed point within 10 iterations for `count_up { max: 4294967295 }`"#]]);
+}
+
+#[test]
+fn limit_is_restored() {
+    with_iteration_limit(10, || ());
+    assert!(count_up(20).is_proven());
+}
+
+thread_local! {
+    static COUNT_UP_STACK: RefCell<FixedPointStack<u32, Set<u32>>> = Default::default();
+}
+
+/// Like `count_up`, but computed with a plain [`fixed_point`].
+fn count_up_without_judgment(max: u32) -> Set<u32> {
+    fixed_point(
+        |max| tracing::debug_span!("count_up_without_judgment", ?max),
+        &COUNT_UP_STACK,
+        max,
+        |_| Set::new(),
+        |max| {
+            let found = count_up_without_judgment(max);
+            let next = found.iter().filter(|&&n| n < max).map(|n| n + 1);
+            next.chain(Some(0)).collect()
+        },
+    )
+}
+
+/// [`fixed_point`] cannot report giving up, so it ignores the limit.
+#[test]
+fn plain_fixed_point_ignores_limit() {
+    let result = with_iteration_limit(1, || count_up_without_judgment(3));
+    assert_eq!(result, set![0, 1, 2, 3]);
+}
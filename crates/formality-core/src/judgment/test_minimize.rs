@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use crate::test_util::normalize_paths;
+use formality_macros::test;
+
+judgment_fn! {
+    fn is_small(n: u32) => () {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small")
+            (is_small(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    fn acceptable(n: u32) => () {
+        debug(n)
+
+        (
+            (is_small(n) => ())
+            --------------------------------------- ("small")
+            (acceptable(n) => ())
+        )
+
+        (
+            (let m = n)
+            (is_small(m) => ())
+            --------------------------------------- ("small after all")
+            (acceptable(n) => ())
+        )
+
+        (
+            (if n == 42)
+            --------------------------------------- ("answer")
+            (acceptable(n) => ())
+        )
+    }
+}
+
+#[test]
+fn rules_with_the_same_cause_are_collapsed() {
+    let failure = acceptable(20).failure().unwrap().clone();
+    expect_test::expect![[r#"
+        judgment `acceptable { n: 20 }` failed at the following rule(s):
+          the rule "answer" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 42`
+          the rule "small" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `is_small { n: 20 }` failed at the following rule(s):
+              the rule "small" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `n < 10`
+          the rule "small after all" failed at step #1 (src/file.rs:LL:CC) because
+            judgment `is_small { n: 20 }` failed at the following rule(s):
+              the rule "small" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `n < 10`"#]]
+    .assert_eq(&normalize_paths(&failure));
+    expect_test::expect![[r#"
+        judgment `acceptable { n: 20 }` failed at the following rule(s):
+          the rule "answer" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 42`
+          the rule "small after all" failed at step #1 (src/file.rs:LL:CC) because
+            judgment `is_small { n: 20 }` failed at the following rule(s):
+              the rule "small" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `n < 10`"#]]
+    .assert_eq(&normalize_paths(failure.minimize()));
+}
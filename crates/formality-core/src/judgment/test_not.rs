@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Holds for one and two.
+    fn listed(n: u32) => () {
+        debug(n)
+
+        (
+            (if n == 1 || n == 2)
+            --------------------------------------- ("listed")
+            (listed(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    /// Holds for the numbers that are not `listed`.
+    fn unlisted(n: u32) => () {
+        debug(n)
+
+        (
+            (not listed(n))
+            --------------------------------------- ("not listed")
+            (unlisted(n) => ())
+        )
+    }
+}
+
+#[test]
+fn negated_judgment_fails() {
+    unlisted(3).assert_ok(expect_test::expect![[r#"
+        {
+          (),
+        }
+    "#]]);
+}
+
+#[test]
+fn negated_judgment_holds() {
+    unlisted(2).assert_err(expect_test::expect![[r#"
+        judgment `unlisted { n: 2 }` failed at the following rule(s):
+          the rule "not listed" failed at step #0 (src/file.rs:LL:CC) because
+            negated judgment was proven: `listed(n)`"#]]);
+}
+
+judgment_fn! {
+    /// Never holds: its only rule needs itself, so proving it runs into a cycle.
+    fn looping(n: u32) => () {
+        debug(n)
+
+        (
+            (looping(n) => ())
+            --------------------------------------- ("loop")
+            (looping(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    /// Negates `looping`, whose failure is only due to cutting off a cycle.
+    fn not_looping(n: u32) => () {
+        debug(n)
+
+        (
+            (not looping(n))
+            --------------------------------------- ("not looping")
+            (not_looping(n) => ())
+        )
+    }
+}
+
+#[test]
+fn inconclusive_negated_judgment_fails() {
+    not_looping(0).assert_err(expect_test::expect![[r#"
+        judgment `not_looping { n: 0 }` failed at the following rule(s):
+          the rule "not looping" failed at step #0 (src/file.rs:LL:CC) because
+            negated judgment was inconclusive: `looping(n)`"#]]);
+}
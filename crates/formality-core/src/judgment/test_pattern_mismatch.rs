@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use crate::{Downcast, Upcast};
+use formality_macros::test;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Upcast, Downcast)]
+enum Expr {
+    #[cast]
+    Number(u32),
+    Name(String),
+}
+
+judgment_fn! {
+    /// Doubles an expression that is a number.
+    fn double(e: Expr) => u32 {
+        debug(e)
+
+        (
+            --------------------------------------- ("number")
+            (double(n: u32) => n * 2)
+        )
+    }
+}
+
+judgment_fn! {
+    /// The length of an expression that is a name of at most three letters,
+    /// or of a number.
+    fn short_len(e: Expr) => usize {
+        debug(e)
+
+        (
+            (if name.len() <= 3)
+            --------------------------------------- ("name")
+            (short_len(Expr::Name(name)) => name.len())
+        )
+
+        (
+            --------------------------------------- ("number")
+            (short_len(n: u32) => n.to_string().len())
+        )
+    }
+}
+
+#[test]
+fn pattern_matches() {
+    double(Expr::Number(2)).assert_ok(expect_test::expect![[r#"
+        {
+          4,
+        }
+    "#]]);
+}
+
+#[test]
+fn pattern_mismatch_is_reported() {
+    double(Expr::Name("x".to_string())).assert_err(expect_test::expect![[r#"
+        judgment `double { e: Name("x") }` failed at the following rule(s):
+          the rule "number" failed at step #0 (src/file.rs:LL:CC) because
+            conclusion pattern `n : u32` expects a `u32`, not value `Name("x")`"#]]);
+}
+
+/// The "name" rule matched and failed, so that is the failure shown; the
+/// mismatch of the "number" rule's pattern would only add noise.
+#[test]
+fn pattern_mismatch_hidden_when_another_rule_matched() {
+    short_len(Expr::Name("abcd".to_string())).assert_err(expect_test::expect![[r#"
+        judgment `short_len { e: Name("abcd") }` failed at the following rule(s):
+          the rule "name" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `name.len() <= 3`"#]]);
+}
@@ -57,3 +57,22 @@ fn judgment() {
         }
     "#]]);
 }
+
+#[test]
+fn metadata() {
+    expect_test::expect![[r#"
+        JudgmentMetadata {
+            name: "transitive_reachable",
+            input_names: [
+                "graph",
+                "from",
+            ],
+            debug_input_names: [
+                "from",
+                "graph",
+            ],
+            rule_count: 2,
+        }
+    "#]]
+    .assert_debug_eq(&transitive_reachable_metadata());
+}
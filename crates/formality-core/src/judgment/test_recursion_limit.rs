@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use crate::{fixed_point::limits_reached, judgment_fn};
+use formality_macros::test;
+
+judgment_fn! {
+    /// Counts down from `n` to zero, one recursive call per step.
+    fn count_down(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (count_down(n) => 0)
+        )
+
+        (
+            (if n > 0)
+            (count_down(n - 1) => m)
+            --------------------------------------- ("succ")
+            (count_down(n) => m + 1)
+        )
+    }
+}
+
+judgment_fn! {
+    /// Like `count_down`, but may only be nested three deep.
+    fn count_down_limited(n: u32) => u32 {
+        debug(n)
+        recursion_limit(3)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (count_down_limited(n) => 0)
+        )
+
+        (
+            (if n > 0)
+            (count_down_limited(n - 1) => m)
+            --------------------------------------- ("succ")
+            (count_down_limited(n) => m + 1)
+        )
+    }
+}
+
+#[test]
+fn unlimited_judgment_recurses() {
+    count_down(5).assert_ok(expect_test::expect![[r#"
+        {
+          5,
+        }
+    "#]]);
+}
+
+#[test]
+fn limited_judgment_within_limit() {
+    count_down_limited(2).assert_ok(expect_test::expect![[r#"
+        {
+          2,
+        }
+    "#]]);
+}
+
+#[test]
+fn limited_judgment_overflows() {
+    count_down_limited(5).assert_err(expect_test::expect![[r#"
+        judgment `count_down_limited { n: 5 }` failed at the following rule(s):
+          the rule "succ" failed at step #1 (src/file.rs:LL:CC) because
+            judgment `count_down_limited { n: 4 }` failed at the following rule(s):
+              the rule "succ" failed at step #1 (src/file.rs:LL:CC) because
+                judgment `count_down_limited { n: 3 }` failed at the following rule(s):
+                  the rule "succ" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `count_down_limited { n: 2 }` failed at the following rule(s):
+                      failed at (src/file.rs:LL:CC) because
+                        recursion limit of 3 reached
+                  the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+                    condition evaluted to false: `n == 0`
+              the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `n == 0`
+          the rule "zero" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 0`"#]]);
+}
+
+judgment_fn! {
+    /// Holds for every `n`, either by counting down (with a limited recursion)
+    /// or directly.
+    fn any(n: u32) => u32 {
+        debug(n)
+
+        (
+            (count_down_limited(n) => m)
+            --------------------------------------- ("count")
+            (any(n) => m)
+        )
+
+        (
+            --------------------------------------- ("direct")
+            (any(n) => n)
+        )
+    }
+}
+
+#[test]
+fn limit_reached_within_a_success_is_recorded() {
+    let before = limits_reached();
+    any(5).assert_ok(expect_test::expect![[r#"
+        {
+          5,
+        }
+    "#]]);
+    assert_eq!(limits_reached() - before, 1);
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// The digits of a number, least significant first; a number of two
+    /// digits or more also counts as a whole.
+    fn parts(digits: Vec<u32>) => u32 {
+        debug(digits)
+
+        (
+            (require digits.iter().all(|d| *d < 10))
+            (for d in digits.iter().copied())
+            --------------------------------------- ("digit")
+            (parts(digits) => d)
+        )
+
+        (
+            (if digits.len() >= 2)
+            --------------------------------------- ("whole")
+            (parts(digits) => digits.iter().rev().fold(0, |n, d| n * 10 + d))
+        )
+    }
+}
+
+#[test]
+fn requirement_met() {
+    parts(vec![1, 2]).assert_ok(expect_test::expect![[r#"
+        {
+          1,
+          2,
+          21,
+        }
+    "#]]);
+}
+
+/// A violated requirement skips its rule, rather than panicking, and the other
+/// rules still apply.
+#[test]
+fn requirement_failed_skips_rule() {
+    parts(vec![1, 12]).assert_ok(expect_test::expect![[r#"
+        {
+          121,
+        }
+    "#]]);
+}
+
+#[test]
+fn requirement_failed_is_reported() {
+    parts(vec![12]).assert_err(expect_test::expect![[r#"
+        judgment `parts { digits: [12] }` failed at the following rule(s):
+          the rule "digit" failed at step #0 (src/file.rs:LL:CC) because
+            requirement was not met: `digits.iter().all(|d| *d < 10)`
+          the rule "whole" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `digits.len() >= 2`"#]]);
+}
@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+use crate::judgment::{with_rule_filter, RuleFilter};
+use crate::judgment_fn;
+
+judgment_fn!(
+    fn classify(n: u32) => String {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small") [category: "builtin"]
+            (classify(n) => "small".to_string())
+        )
+
+        (
+            (if n % 2 == 0)
+            --------------------------------------- ("even") [category: "user"]
+            (classify(n) => "even".to_string())
+        )
+
+        (
+            (if n % 3 == 0)
+            --------------------------------------- ("triple") [category: "user"]
+            (classify(n) => "triple".to_string())
+        )
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (classify(n) => "zero".to_string())
+        )
+    }
+);
+
+#[test]
+fn unfiltered_tries_every_rule() {
+    classify(0).assert_ok(expect_test::expect![[r#"
+        {
+          "even",
+          "small",
+          "triple",
+          "zero",
+        }
+    "#]]);
+}
+
+#[test]
+fn filter_to_one_category() {
+    let builtin = with_rule_filter(RuleFilter::only_categories(["builtin"]), || classify(0));
+    builtin.assert_ok(expect_test::expect![[r#"
+        {
+          "small",
+        }
+    "#]]);
+
+    let user = with_rule_filter(RuleFilter::only_categories(["user"]), || classify(6));
+    user.assert_ok(expect_test::expect![[r#"
+        {
+          "even",
+          "triple",
+        }
+    "#]]);
+
+    // Only the allowed rules are attempted, so only they are reported.
+    let failed = with_rule_filter(RuleFilter::only_categories(["builtin"]), || classify(13));
+    failed.assert_err(expect_test::expect![[r#"
+        judgment `classify { n: 13 }` failed at the following rule(s):
+          the rule "small" [category: "builtin"] failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n < 10`"#]]);
+}
+
+#[test]
+fn failures_grouped_by_category() {
+    let result = classify(13);
+    let failure = result.failure().unwrap();
+    let groups: Vec<(Option<&str>, Vec<String>)> = failure
+        .failed_rules_by_category()
+        .into_iter()
+        .map(|(category, rules)| {
+            let names = rules
+                .iter()
+                .map(|rule| rule.rule_name_index.as_ref().unwrap().0.clone())
+                .collect();
+            (category, names)
+        })
+        .collect();
+    expect_test::expect![[r#"
+        [
+            (
+                None,
+                [
+                    "zero",
+                ],
+            ),
+            (
+                Some(
+                    "builtin",
+                ),
+                [
+                    "small",
+                ],
+            ),
+            (
+                Some(
+                    "user",
+                ),
+                [
+                    "even",
+                    "triple",
+                ],
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&groups);
+}
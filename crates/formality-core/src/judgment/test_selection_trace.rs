@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use std::cell::RefCell;
+
+use crate::judgment::{force_selection, record_selection, with_shuffle_seed, SelectionTrace};
+use crate::judgment_fn;
+use formality_macros::test;
+
+thread_local! {
+    /// The pairs that `pick_pair` found, in order.
+    static FOUND: RefCell<Vec<(u32, u32)>> = const { RefCell::new(vec![]) };
+}
+
+fn record(pair: (u32, u32)) -> (u32, u32) {
+    FOUND.with(|f| f.borrow_mut().push(pair));
+    pair
+}
+
+judgment_fn! {
+    fn pick_pair(n: u32) => (u32, u32) {
+        debug(n)
+
+        (
+            (0..n => i)
+            (0..i => j)
+            (let pair = record((i, j)))
+            --------------------------------------- ("pick")
+            (pick_pair(n) => pair)
+        )
+    }
+}
+
+/// Runs `pick_pair(5)` and returns the pairs in the order it found them.
+fn run_pick() -> Vec<(u32, u32)> {
+    FOUND.with(|f| f.borrow_mut().clear());
+    pick_pair(5).assert_ok(expect_test::expect![[r#"
+        {
+          (1, 0),
+          (2, 0),
+          (2, 1),
+          (3, 0),
+          (3, 1),
+          (3, 2),
+          (4, 0),
+          (4, 1),
+          (4, 2),
+          (4, 3),
+        }
+    "#]]);
+    FOUND.with(|f| f.borrow().clone())
+}
+
+#[test]
+fn replay_reproduces_order() {
+    let (recorded, trace) = record_selection(|| with_shuffle_seed(22, run_pick));
+    assert_ne!(recorded, run_pick());
+
+    let replayed = force_selection(&trace, run_pick);
+    assert_eq!(replayed[0], recorded[0]);
+    assert_eq!(replayed, recorded);
+}
+
+#[test]
+fn unshuffled_trace_is_in_order() {
+    let (_, trace) = record_selection(run_pick);
+    expect_test::expect![[r#"
+        SelectionTrace {
+            orders: [
+                [
+                    0,
+                    1,
+                    2,
+                    3,
+                    4,
+                ],
+                [
+                    0,
+                ],
+                [
+                    0,
+                    1,
+                ],
+                [
+                    0,
+                    1,
+                    2,
+                ],
+                [
+                    0,
+                    1,
+                    2,
+                    3,
+                ],
+            ],
+        }
+    "#]]
+    .assert_debug_eq(&trace);
+}
+
+#[test]
+#[should_panic(expected = "selection trace does not match this run")]
+fn diverging_replay_panics() {
+    let trace = SelectionTrace {
+        orders: vec![vec![1, 0]],
+    };
+    force_selection(&trace, run_pick);
+}
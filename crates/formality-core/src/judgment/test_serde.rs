@@ -0,0 +1,81 @@
+#![cfg(all(test, feature = "serde"))]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    fn is_small(n: u32) => () {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small")
+            (is_small(n) => ())
+        )
+    }
+}
+
+judgment_fn! {
+    fn both_small(a: u32, b: u32) => () {
+        debug(a, b)
+
+        (
+            (is_small(a) => ())
+            (is_small(b) => ())
+            --------------------------------------- ("both")
+            (both_small(a, b) => ())
+        )
+    }
+}
+
+#[test]
+fn failure_serializes_to_json() {
+    let failure = both_small(1, 20).failure().unwrap().clone();
+    let mut json = serde_json::to_value(&failure).unwrap();
+
+    // The location depends on where the macro expanded; blank it out.
+    let rule = &mut json["failed_rules"][0];
+    assert!(rule["file"].as_str().unwrap().ends_with(".rs"));
+    rule["file"] = "<file>".into();
+    rule["line"] = 0.into();
+    rule["column"] = 0.into();
+    let inner = &mut rule["cause"]["failed_rules"][0];
+    inner["file"] = "<file>".into();
+    inner["line"] = 0.into();
+    inner["column"] = 0.into();
+
+    expect_test::expect![[r#"
+        {
+          "failed_rules": [
+            {
+              "category": null,
+              "cause": {
+                "failed_rules": [
+                  {
+                    "category": null,
+                    "cause": {
+                      "args": [],
+                      "expr": "n < 10",
+                      "kind": "if_false"
+                    },
+                    "column": 0,
+                    "file": "<file>",
+                    "line": 0,
+                    "rule": "small",
+                    "step": 0
+                  }
+                ],
+                "judgment": "is_small { n: 20 }",
+                "kind": "failed_judgment"
+              },
+              "column": 0,
+              "file": "<file>",
+              "line": 0,
+              "rule": "both",
+              "step": 1
+            }
+          ],
+          "judgment": "both_small { a: 1, b: 20 }"
+        }"#]]
+    .assert_eq(&serde_json::to_string_pretty(&json).unwrap());
+}
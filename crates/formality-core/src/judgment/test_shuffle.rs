@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+use std::cell::RefCell;
+
+use crate::judgment::{shuffle_seed, shuffled, with_shuffle_seed};
+use crate::judgment_fn;
+use formality_macros::test;
+
+thread_local! {
+    /// The order in which `pick` tried its alternatives.
+    static TRIED: RefCell<Vec<u32>> = const { RefCell::new(vec![]) };
+}
+
+fn record(n: u32) -> u32 {
+    TRIED.with(|t| t.borrow_mut().push(n));
+    n
+}
+
+judgment_fn! {
+    fn pick(n: u32) => u32 {
+        debug(n)
+
+        (
+            (0..n => i)
+            (let i = record(i))
+            --------------------------------------- ("pick")
+            (pick(n) => i)
+        )
+    }
+}
+
+/// Runs `pick(8)` and returns its results along with the order it tried them in.
+fn run_pick() -> (String, Vec<u32>) {
+    TRIED.with(|t| t.borrow_mut().clear());
+    let result = format!("{:?}", pick(8));
+    (result, TRIED.with(|t| t.borrow().clone()))
+}
+
+#[test]
+fn same_seed_same_order() {
+    let first = with_shuffle_seed(22, run_pick);
+    let second = with_shuffle_seed(22, run_pick);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn order_does_not_change_results() {
+    let (unshuffled, order) = run_pick();
+    assert_eq!(order, (0..8).collect::<Vec<_>>());
+
+    for seed in 0..16 {
+        let (shuffled, _) = with_shuffle_seed(seed, run_pick);
+        assert_eq!(shuffled, unshuffled);
+    }
+}
+
+#[test]
+fn seeds_permute() {
+    let identity: Vec<u32> = (0..8).collect();
+    let orders: Vec<Vec<u32>> = (0..16)
+        .map(|seed| with_shuffle_seed(seed, || shuffled(0..8).collect()))
+        .collect();
+
+    assert!(orders.iter().any(|order| *order != identity));
+    for order in &orders {
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, identity);
+    }
+}
+
+#[test]
+fn seed_is_exposed_and_restored() {
+    assert_eq!(shuffle_seed(), None);
+    with_shuffle_seed(1, || {
+        assert_eq!(shuffle_seed(), Some(1));
+        with_shuffle_seed(2, || assert_eq!(shuffle_seed(), Some(2)));
+        assert_eq!(shuffle_seed(), Some(1));
+    });
+    assert_eq!(shuffle_seed(), None);
+}
@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use crate::snapshot_judgment;
+use crate::test_util::JudgmentSnapshot;
+
+judgment_fn!(
+    fn classify(n: u32) => String {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small")
+            (classify(n) => "small".to_string())
+        )
+
+        (
+            (if n % 2 == 0)
+            --------------------------------------- ("even")
+            (classify(n) => "even".to_string())
+        )
+    }
+);
+
+// Like `classify`, but the "even" rule has been changed to only accept multiples of 4.
+judgment_fn!(
+    fn classify_changed(n: u32) => String {
+        debug(n)
+
+        (
+            (if n < 10)
+            --------------------------------------- ("small")
+            (classify_changed(n) => "small".to_string())
+        )
+
+        (
+            (if n % 4 == 0)
+            --------------------------------------- ("even")
+            (classify_changed(n) => "even".to_string())
+        )
+    }
+);
+
+snapshot_judgment!(classify, [(3), (6), (12), (13)]);
+
+fn snapshot(judgment: fn(u32) -> crate::ProvenSet<String>, inputs: &[u32]) -> JudgmentSnapshot {
+    let mut snapshot = JudgmentSnapshot::new();
+    for &n in inputs {
+        snapshot.record(format!("classify({n})"), &judgment(n));
+    }
+    snapshot
+}
+
+#[test]
+fn rule_change_shows_up_in_affected_entries_only() {
+    let inputs = [3, 6, 8, 12];
+    let before = snapshot(classify, &inputs);
+    let after = snapshot(classify_changed, &inputs);
+    let changed: Vec<String> = before
+        .entries()
+        .iter()
+        .zip(after.entries())
+        .filter(|(b, a)| b != a)
+        .map(|(b, a)| format!("{}\n- {}\n+ {}", b.0, b.1, a.1))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "classify(6)\n- {\n  \"even\",\n  \"small\",\n}\n+ {\n  \"small\",\n}",
+        ]
+    "#]]
+    .assert_debug_eq(&changed);
+}
@@ -0,0 +1,87 @@
+#![cfg(test)]
+
+use crate::{fixed_point::with_stats, judgment_fn};
+use formality_macros::test;
+
+judgment_fn! {
+    /// Counts down from `n` to zero, one recursive call per step.
+    fn count_down(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n == 0)
+            --------------------------------------- ("zero")
+            (count_down(n) => 0)
+        )
+
+        (
+            (if n > 0)
+            (count_down(n - 1) => m)
+            --------------------------------------- ("succ")
+            (count_down(n) => m + 1)
+        )
+    }
+}
+
+judgment_fn! {
+    /// The numbers from 0 up to `max`; each one found takes another iteration,
+    /// in which the recursive call yields the numbers found so far.
+    fn count_up(max: u32) => u32 {
+        debug(max)
+
+        (
+            --------------------------------------- ("zero")
+            (count_up(_max) => 0)
+        )
+
+        (
+            (count_up(max) => n)
+            (if n < max)
+            --------------------------------------- ("succ")
+            (count_up(max) => n + 1)
+        )
+    }
+}
+
+#[test]
+fn stats_per_judgment() {
+    let ((), stats) = with_stats(|| {
+        assert!(count_down(3).is_proven());
+        assert!(count_up(2).is_proven());
+    });
+    expect_test::expect![[r#"
+        {
+            "count_down": Stats {
+                iterations: 4,
+                cycle_hits: 0,
+                cache_misses: 4,
+                max_depth: 4,
+                limits_reached: 0,
+            },
+            "count_up": Stats {
+                iterations: 4,
+                cycle_hits: 4,
+                cache_misses: 1,
+                max_depth: 1,
+                limits_reached: 0,
+            },
+        }
+    "#]]
+    .assert_debug_eq(&stats);
+}
+
+#[test]
+fn stats_only_recorded_within_scope() {
+    assert!(count_down(1).is_proven());
+    let ((), stats) = with_stats(|| ());
+    assert!(stats.is_empty());
+}
+
+#[test]
+fn inner_scope_not_counted_by_outer() {
+    let ((), outer) = with_stats(|| {
+        let ((), inner) = with_stats(|| assert!(count_down(1).is_proven()));
+        assert_eq!(inner["count_down"].cache_misses, 2);
+    });
+    assert!(outer.is_empty());
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+
+judgment_fn!(
+    /// The numbers made by appending a digit to `n`.
+    fn next_digit(n: u32) => u32 {
+        debug(n)
+
+        trivial(n == 0 => each 1..=9)
+        trivial(n == 5 => each Vec::<u32>::new())
+        trivial(n == 9 => 90)
+
+        (
+            --------------------------------------- ("append")
+            (next_digit(n) => n * 10)
+        )
+    }
+);
+
+#[test]
+fn trivial_case_yields_each_result() {
+    expect_test::expect![[r#"
+        {
+          1,
+          2,
+          3,
+          4,
+          5,
+          6,
+          7,
+          8,
+          9,
+        }
+    "#]]
+    .assert_eq(&next_digit(0u32).to_string());
+}
+
+#[test]
+fn trivial_case_with_single_result() {
+    expect_test::expect![[r#"
+        {
+          90,
+        }
+    "#]]
+    .assert_eq(&next_digit(9u32).to_string());
+}
+
+#[test]
+fn trivial_case_without_results_falls_through() {
+    expect_test::expect![[r#"
+        {
+          50,
+        }
+    "#]]
+    .assert_eq(&next_digit(5u32).to_string());
+}
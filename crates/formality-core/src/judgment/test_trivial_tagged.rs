@@ -0,0 +1,54 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+
+judgment_fn!(
+    fn is_sized(ty: String) => () {
+        debug(ty)
+
+        trivial_tagged(ty == "u32" => (), tag: "trivially Sized because primitive")
+
+        (
+            (if ty.starts_with('&'))
+            --------------------------------------- ("ref")
+            (is_sized(ty) => ())
+        )
+    }
+);
+
+#[test]
+fn tagged_trivial_case_explains_itself() {
+    expect_test::expect![[r#"
+        {
+          (),
+        }
+        because:
+          trivially Sized because primitive
+    "#]]
+    .assert_eq(&is_sized("u32").explain());
+}
+
+#[test]
+fn rules_have_no_provenance() {
+    let result = is_sized("&u32");
+    assert!(result.provenance().is_empty());
+    expect_test::expect![[r#"
+        {
+          (),
+        }
+    "#]]
+    .assert_eq(&result.explain());
+}
+
+#[test]
+fn provenance_survives_map() {
+    let result = is_sized("u32").map(|()| 22);
+    expect_test::expect![[r#"
+        {
+          22,
+        }
+        because:
+          trivially Sized because primitive
+    "#]]
+    .assert_eq(&result.explain());
+}
@@ -0,0 +1,51 @@
+#![cfg(test)]
+
+use crate::judgment_fn;
+use formality_macros::test;
+
+judgment_fn! {
+    /// Holds for numbers below three; only the last rule has a label.
+    fn small(n: u32) => u32 {
+        debug(n)
+
+        (
+            (if n == 0)
+            ---------------------------------------
+            (small(n) => n)
+        )
+
+        (
+            (if n == 1)
+            --------------------------------------- [category: "one"]
+            (small(n) => n)
+        )
+
+        (
+            (if n == 2)
+            --------------------------------------- ("two")
+            (small(n) => n)
+        )
+    }
+}
+
+#[test]
+fn unlabeled_rules_apply() {
+    small(1).assert_ok(expect_test::expect![[r#"
+        {
+          1,
+        }
+    "#]]);
+}
+
+/// Rules without a label are named by their index among the judgment's rules.
+#[test]
+fn unlabeled_rules_named_by_index() {
+    small(3).assert_err(expect_test::expect![[r#"
+        judgment `small { n: 3 }` failed at the following rule(s):
+          the rule "0" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 0`
+          the rule "1" [category: "one"] failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 1`
+          the rule "two" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `n == 2`"#]]);
+}
@@ -17,7 +17,9 @@ pub use tracing::instrument;
 pub use tracing::trace;
 
 // Re-export things from formality-macros.
-pub use formality_macros::{fixed_point, respan, term, test, Visit};
+pub use formality_macros::{
+    concat_ident, fixed_point, respan, term, test, Downcast, Upcast, Visit,
+};
 
 pub type Fallible<T> = anyhow::Result<T>;
 
@@ -31,7 +33,9 @@ pub mod fixed_point;
 pub mod fold;
 pub mod judgment;
 pub mod language;
+mod lru_cache;
 pub mod parse;
+pub mod scoped;
 pub mod substitution;
 pub mod term;
 pub mod test_util;
@@ -39,13 +43,16 @@ pub mod util;
 pub mod variable;
 pub mod visit;
 
-pub use cast::{Downcast, DowncastFrom, DowncastTo, Downcasted, To, Upcast, UpcastFrom, Upcasted};
+pub use cast::{
+    Downcast, DowncastError, DowncastFrom, DowncastTo, Downcasted, To, Upcast, UpcastFrom, Upcasted,
+};
 pub use collections::Cons;
 pub use collections::Deduplicate;
 pub use collections::Map;
 pub use collections::Set;
 pub use collections::SetExt;
 pub use judgment::ProvenSet;
+pub use lru_cache::{CacheStats, LruCache};
 
 /// Run an action with a tracing log subscriber. The logging level is loaded
 /// from `RUST_LOG`. The `formality_macro::test` expansion uses this to enable logs.
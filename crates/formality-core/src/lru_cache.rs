@@ -0,0 +1,108 @@
+//! A bounded cache that evicts the least recently used entry.
+
+use crate::Map;
+
+/// Counters describing how an [`LruCache`] has been used.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+/// A map holding at most `capacity` entries. Inserting into a full cache
+/// evicts the entry that was least recently inserted or looked up.
+#[derive(Clone, Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+
+    /// Each entry along with the tick at which it was last used.
+    entries: Map<K, (V, u64)>,
+
+    /// The key last used at each tick; the first entry is the next to be evicted.
+    by_last_use: Map<u64, K>,
+
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl<K: Ord + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "an LRU cache needs room for at least one entry"
+        );
+        Self {
+            capacity,
+            entries: Map::new(),
+            by_last_use: Map::new(),
+            tick: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Returns the value cached for `key`, if any, marking it as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let tick = self.next_tick();
+        match self.entries.get_mut(key) {
+            Some((value, last_use)) => {
+                self.by_last_use.remove(last_use);
+                self.by_last_use.insert(tick, key.clone());
+                *last_use = tick;
+                self.stats.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches `value` for `key`, evicting the least recently used entry if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        let tick = self.next_tick();
+        if let Some((_, last_use)) = self.entries.remove(&key) {
+            self.by_last_use.remove(&last_use);
+        } else if self.entries.len() == self.capacity {
+            let (_, oldest) = self.by_last_use.pop_first().unwrap();
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+        self.by_last_use.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+    }
+
+    /// Removes the entry for `key`, returning its value if it was cached.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, last_use) = self.entries.remove(key)?;
+        self.by_last_use.remove(&last_use);
+        Some(value)
+    }
+
+    /// True if `key` is cached. Unlike [`LruCache::get`], this does not count as a use.
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
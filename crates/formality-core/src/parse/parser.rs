@@ -506,10 +506,15 @@ where
         let text0 = self.current_text;
         match self.identifier_like_string() {
             Ok(ident) if &*ident == expected => Ok(()),
-            _ => Err(ParseError::at(
-                skip_whitespace(text0),
-                format!("expected `{}`", expected),
-            )),
+            _ => {
+                // Leave the text as it was, so that an optional keyword (as in
+                // `$:guard $x`) can be followed by something else.
+                self.current_text = text0;
+                Err(ParseError::at(
+                    skip_whitespace(text0),
+                    format!("expected `{}`", expected),
+                ))
+            }
         }
     }
 
@@ -0,0 +1,55 @@
+//! Thread-local settings that hold for the extent of a closure.
+
+use std::cell::{Cell, RefCell};
+use std::thread::LocalKey;
+
+/// A cell whose value can be swapped out, i.e., a [`Cell`] or a [`RefCell`].
+pub trait ScopedCell: 'static {
+    type Value;
+
+    /// Stores `value`, returning the value stored before.
+    fn swap_value(&self, value: Self::Value) -> Self::Value;
+}
+
+impl<T: 'static> ScopedCell for Cell<T> {
+    type Value = T;
+
+    fn swap_value(&self, value: T) -> T {
+        self.replace(value)
+    }
+}
+
+impl<T: 'static> ScopedCell for RefCell<T> {
+    type Value = T;
+
+    fn swap_value(&self, value: T) -> T {
+        self.replace(value)
+    }
+}
+
+/// Runs `op` with `value` stored in the thread-local `key`, restoring the
+/// previous value afterwards, even if `op` panics.
+pub fn with_scoped<C: ScopedCell, R>(
+    key: &'static LocalKey<C>,
+    value: C::Value,
+    op: impl FnOnce() -> R,
+) -> R {
+    struct Restore<C: ScopedCell> {
+        key: &'static LocalKey<C>,
+        previous: Option<C::Value>,
+    }
+
+    impl<C: ScopedCell> Drop for Restore<C> {
+        fn drop(&mut self) {
+            let previous = self.previous.take().unwrap();
+            self.key.with(|c| c.swap_value(previous));
+        }
+    }
+
+    let previous = key.with(|c| c.swap_value(value));
+    let _restore = Restore {
+        key,
+        previous: Some(previous),
+    };
+    op()
+}
@@ -27,6 +27,13 @@
 //! }
 //! ```
 //!
+//! # Snapshotting a judgment
+//!
+//! To pin down how a judgment behaves on a set of inputs, use [`snapshot_judgment!`](crate::snapshot_judgment).
+//! It generates a test that runs the judgment on each input and compares the rendered results against a
+//! golden file checked in next to the test. A change to one of the judgment's rules then shows up as a diff
+//! of that file, which can be reviewed and accepted with `UPDATE_EXPECT=1`.
+//!
 //! # Writing standalone tests with `ui_test`
 //!
 //! TODO: nicely package up `ui_test` to do normalization etc.
@@ -37,6 +44,11 @@
 
 use std::fmt::{Debug, Display};
 
+use crate::ProvenSet;
+
+#[doc(hidden)]
+pub use expect_test::expect_file;
+
 /// Converts `s` to a string and replaces path/line/column patterns like `src/blah/foo.rs:22:33` in the string
 /// with `"src/file.rs:LL:CC"`. This makes error messages resilient against changes to the source code.
 pub fn normalize_paths(s: impl Display) -> String {
@@ -45,6 +57,71 @@ pub fn normalize_paths(s: impl Display) -> String {
     re.replace_all(&s, "(src/file.rs:LL:CC)").to_string()
 }
 
+/// The results of a judgment on a series of inputs, rendered as text to compare
+/// against a golden file. Each entry is the call followed by its (normalized) result,
+/// so that a change in behavior affects only the entries for the inputs concerned.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JudgmentSnapshot {
+    entries: Vec<(String, String)>,
+}
+
+impl JudgmentSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `call` (e.g., `classify(22)`) produced `result`.
+    pub fn record<T: Debug>(&mut self, call: impl Display, result: &ProvenSet<T>) {
+        self.entries.push((
+            call.to_string(),
+            normalize_paths(result).trim_end().to_string(),
+        ));
+    }
+
+    /// The recorded calls and their rendered results, in the order they were recorded.
+    pub fn entries(&self) -> &[(String, String)] {
+        &self.entries
+    }
+
+    /// The text of the golden file.
+    pub fn render(&self) -> String {
+        self.entries
+            .iter()
+            .map(|(call, result)| format!("{call} => {result}\n"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Generates a test `snapshot_<judgment>` that runs `judgment` on each of the given
+/// argument lists and compares the results against the golden file
+/// `snapshots/<judgment>.txt`, found relative to the invoking source file.
+/// Run the test with `UPDATE_EXPECT=1` to create or update the file
+/// (the `snapshots` directory must already exist).
+///
+/// ```ignore
+/// snapshot_judgment!(classify, [(0), (6), (13)]);
+/// ```
+#[macro_export]
+macro_rules! snapshot_judgment {
+    ($judgment:ident, [$(($($arg:expr),* $(,)?)),* $(,)?]) => {
+        $crate::concat_ident!(snapshot_ $judgment (
+            #[test]
+            fn __concat_ident() {
+                let mut snapshot = $crate::test_util::JudgmentSnapshot::new();
+                $(
+                    snapshot.record(
+                        concat!(stringify!($judgment), "(", stringify!($($arg),*), ")"),
+                        &$judgment($($arg),*),
+                    );
+                )*
+                $crate::test_util::expect_file![concat!("snapshots/", stringify!($judgment), ".txt")]
+                    .assert_eq(&snapshot.render());
+            }
+        ));
+    };
+}
+
 /// Extension methods for writing tests on functions that return [`Fallible`](crate::Fallible) values.
 pub trait ResultTestExt<T, E> {
     /// Given a `Fallible<T>` value, assert that its debug representation matches the expected value.
@@ -212,3 +212,4 @@ impl std::ops::Add<usize> for VarIndex {
 
 mod cast_impls;
 mod debug_impls;
+pub use debug_impls::with_variable_names;
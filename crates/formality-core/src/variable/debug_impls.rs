@@ -1,5 +1,47 @@
+use std::cell::RefCell;
+
+use crate::{collections::Map, scoped::with_scoped};
+
 use super::*;
 
+thread_local! {
+    /// The names installed by [`with_variable_names`], keyed by whether the
+    /// variable is existential and by its index.
+    static NAMES: RefCell<Option<Map<(bool, usize), String>>> = const { RefCell::new(None) };
+}
+
+/// Runs `op` such that the `Debug` output of each variable in `names` is its
+/// name rather than its kind and index, e.g. to render a solution with names
+/// that do not depend on how many variables were created while solving.
+/// Bound variables are always printed as usual.
+pub fn with_variable_names<L: Language, R>(
+    names: impl IntoIterator<Item = (CoreVariable<L>, String)>,
+    op: impl FnOnce() -> R,
+) -> R {
+    let names = names
+        .into_iter()
+        .filter_map(|(v, name)| match v {
+            CoreVariable::UniversalVar(v) => Some(((false, v.var_index.index), name)),
+            CoreVariable::ExistentialVar(v) => Some(((true, v.var_index.index), name)),
+            CoreVariable::BoundVar(_) => None,
+        })
+        .collect();
+    with_scoped(&NAMES, Some(names), op)
+}
+
+/// Writes the name installed for the variable, returning `None` if there is none.
+fn write_name(
+    existential: bool,
+    var_index: VarIndex,
+    f: &mut std::fmt::Formatter<'_>,
+) -> Option<std::fmt::Result> {
+    NAMES.with(|n| {
+        let names = n.borrow();
+        let name = names.as_ref()?.get(&(existential, var_index.index))?;
+        Some(write!(f, "{name}"))
+    })
+}
+
 impl<L: Language> std::fmt::Debug for CoreVariable<L> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -13,6 +55,9 @@ impl<L: Language> std::fmt::Debug for CoreVariable<L> {
 impl<L: Language> std::fmt::Debug for CoreUniversalVar<L> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let CoreUniversalVar { var_index, kind } = self;
+        if let Some(result) = write_name(false, *var_index, f) {
+            return result;
+        }
         write!(f, "!{:?}_{:?}", kind, var_index)
     }
 }
@@ -20,6 +65,9 @@ impl<L: Language> std::fmt::Debug for CoreUniversalVar<L> {
 impl<L: Language> std::fmt::Debug for CoreExistentialVar<L> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let CoreExistentialVar { var_index, kind } = self;
+        if let Some(result) = write_name(true, *var_index, f) {
+            return result;
+        }
         write!(f, "?{:?}_{:?}", kind, var_index)
     }
 }
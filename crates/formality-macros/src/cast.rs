@@ -6,6 +6,21 @@ use synstructure::VariantInfo;
 
 use crate::attrs::has_isa_attr;
 
+/// `#[derive(Upcast)]`: the upcasts that `#[term]` generates, for types that
+/// are not declared with `#[term]`. See [`upcast_impls`].
+pub(crate) fn derive_upcast(s: synstructure::Structure) -> TokenStream {
+    upcast_impls(s).into_iter().collect()
+}
+
+/// `#[derive(Downcast)]`: the downcasts that `#[term]` generates, for types
+/// that are not declared with `#[term]`. See [`downcast_impls`].
+pub(crate) fn derive_downcast(s: synstructure::Structure) -> TokenStream {
+    downcast_impls(s).into_iter().collect()
+}
+
+/// Upcasts from each `#[cast]` variant's fields (or, for a struct or an enum
+/// with one variant, the fields of that variant) to the type, along with the
+/// identity upcast and the upcast to `Arc<Self>`.
 pub(crate) fn upcast_impls(mut s: synstructure::Structure) -> Vec<TokenStream> {
     s.underscore_const(true);
 
@@ -76,6 +91,8 @@ fn upcast_to_variant(s: &synstructure::Structure, v: &VariantInfo) -> TokenStrea
     })
 }
 
+/// The downcasts matching [`upcast_impls`], plus an `as_variant` method for
+/// each `#[cast]` variant of an enum that has a single field.
 pub(crate) fn downcast_impls(mut s: synstructure::Structure) -> Vec<TokenStream> {
     s.underscore_const(true);
 
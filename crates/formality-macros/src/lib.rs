@@ -40,6 +40,8 @@ pub fn term(args: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 synstructure::decl_derive!([Visit] => visit::derive_visit);
+synstructure::decl_derive!([Upcast, attributes(cast)] => cast::derive_upcast);
+synstructure::decl_derive!([Downcast, attributes(cast)] => cast::derive_downcast);
 
 #[proc_macro_attribute]
 pub fn fixed_point(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -60,6 +62,41 @@ pub fn test(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// Invoked like `concat_ident!(X Y (Z...))` -- produces `Z...` with each occurrence
+/// of the identifier `__concat_ident` replaced by `XY` (with span from `X`).
+#[proc_macro]
+pub fn concat_ident(t: TokenStream) -> TokenStream {
+    let mut t = t.into_iter();
+    let (Some(proc_macro::TokenTree::Ident(prefix)), Some(suffix), Some(stream)) =
+        (t.next(), t.next(), t.next())
+    else {
+        panic!("expected `concat_ident!(X Y (Z...))`");
+    };
+    let ident = proc_macro::Ident::new(&format!("{prefix}{suffix}"), prefix.span());
+    return match stream {
+        proc_macro::TokenTree::Group(g) => replace(&ident, g.stream()),
+        _ => unreachable!(),
+    };
+
+    fn replace(ident: &proc_macro::Ident, tokens: TokenStream) -> TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                proc_macro::TokenTree::Group(group) => {
+                    let mut fixed =
+                        proc_macro::Group::new(group.delimiter(), replace(ident, group.stream()));
+                    fixed.set_span(group.span());
+                    proc_macro::TokenTree::Group(fixed)
+                }
+                proc_macro::TokenTree::Ident(i) if i.to_string() == "__concat_ident" => {
+                    proc_macro::TokenTree::Ident(ident.clone())
+                }
+                token => token,
+            })
+            .collect()
+    }
+}
+
 /// Invoked like `respan!(X (Y...))` -- produces `Y...` with span from `X`.
 #[proc_macro]
 pub fn respan(t: TokenStream) -> TokenStream {
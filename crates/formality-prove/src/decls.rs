@@ -47,6 +47,10 @@ impl Decls {
             .filter(move |i| i.binder.peek().trait_ref.trait_id == *trait_id)
     }
 
+    pub fn has_trait_decl(&self, trait_id: &TraitId) -> bool {
+        self.trait_decls.iter().any(|t| t.id == *trait_id)
+    }
+
     pub fn trait_decl(&self, trait_id: &TraitId) -> &TraitDecl {
         let mut v: Vec<_> = self
             .trait_decls
@@ -78,8 +82,18 @@ impl Decls {
         v.pop().unwrap()
     }
 
+    /// True if cycles through goals of `trait_id` hold; see [`Coinduction`].
+    pub fn is_coinductive(&self, trait_id: &TraitId) -> bool {
+        self.trait_decl(trait_id).coinduction == Coinduction::Coinductive
+    }
+
     /// Return the set of "trait invariants" for all traits.
     /// See [`TraitDecl::trait_invariants`].
+    // FIXME: this (like `impl_decls`) is recomputed on every lookup. Memoizing
+    // the clauses per trait, invalidated when the program changes, was
+    // requested but is not done: `Decls` is passed by value into every
+    // judgment and is part of their (hashed and compared) inputs, so a memo
+    // cannot live in it, and there is no database object to hang one on.
     pub fn trait_invariants(&self) -> Set<TraitInvariant> {
         self.trait_decls
             .iter()
@@ -87,6 +101,62 @@ impl Decls {
             .collect()
     }
 
+    /// Every clause the solver may draw on, whatever the goal: the impls,
+    /// negative impls, alias rules, trait invariants, and the traits (whose
+    /// where-clauses a trait ref must satisfy to be well-formed), in that order.
+    /// Useful for dumping what a program lowers to.
+    pub fn program_clauses(&self) -> Vec<ProgramClause> {
+        let impls = self.impl_decls.iter().map(|i| i.upcast());
+        let neg_impls = self.neg_impl_decls.iter().map(|i| i.upcast());
+        let alias_eqs = self.alias_eq_decls.iter().map(|a| a.upcast());
+        let alias_bounds = self.alias_bound_decls.iter().map(|a| a.upcast());
+        let invariants = self.trait_invariants().into_iter().map(|ti| ti.upcast());
+        let traits = self.trait_decls.iter().map(|t| t.upcast());
+        impls
+            .chain(neg_impls)
+            .chain(alias_eqs)
+            .chain(alias_bounds)
+            .chain(invariants)
+            .chain(traits)
+            .collect()
+    }
+
+    /// Combines the declarations of several members (e.g., crates modeled
+    /// separately) into a single set of declarations, without merging the
+    /// programs they came from. A declaration that appears in more than one
+    /// member, such as a trait from a crate they all depend on, is kept once.
+    /// The items local to any member are local to the result.
+    ///
+    /// The orphan rules are not taken into account: since locality is merged,
+    /// the result answers `IsLocal` goals as though all members were one crate.
+    /// An impl in one member of a trait from a second member for a type from a
+    /// third passes the orphan check against the result, although it is an
+    /// orphan in the member that contains it. The orphan check of a member's
+    /// impls should therefore be run against that member's own declarations.
+    pub fn federated(members: impl IntoIterator<Item = Decls>) -> Self {
+        fn extend_dedup<T: PartialEq>(into: &mut Vec<T>, from: Vec<T>) {
+            for item in from {
+                if !into.contains(&item) {
+                    into.push(item);
+                }
+            }
+        }
+
+        let mut result = Decls::empty();
+        for member in members {
+            result.max_size = result.max_size.max(member.max_size);
+            extend_dedup(&mut result.trait_decls, member.trait_decls);
+            extend_dedup(&mut result.impl_decls, member.impl_decls);
+            extend_dedup(&mut result.neg_impl_decls, member.neg_impl_decls);
+            extend_dedup(&mut result.alias_eq_decls, member.alias_eq_decls);
+            extend_dedup(&mut result.alias_bound_decls, member.alias_bound_decls);
+            extend_dedup(&mut result.adt_decls, member.adt_decls);
+            result.local_trait_ids.extend(member.local_trait_ids);
+            result.local_adt_ids.extend(member.local_adt_ids);
+        }
+        result
+    }
+
     pub fn empty() -> Self {
         Self {
             max_size: Decls::DEFAULT_MAX_SIZE,
@@ -102,10 +172,107 @@ impl Decls {
     }
 }
 
+/// One of the clauses listed by [`Decls::program_clauses`].
+#[term]
+pub enum ProgramClause {
+    #[cast]
+    ImplDecl(ImplDecl),
+    #[cast]
+    NegImplDecl(NegImplDecl),
+    #[cast]
+    AliasEqDecl(AliasEqDecl),
+    #[cast]
+    AliasBoundDecl(AliasBoundDecl),
+    #[cast]
+    TraitInvariant(TraitInvariant),
+    #[cast]
+    TraitDecl(TraitDecl),
+}
+
+/// How a layer pushed onto a [`LayeredDb`] combines with the layers below it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OverrideMode {
+    /// The layer's clauses are added to those below, as with [`Decls::federated`].
+    Extend,
+
+    /// For each trait the layer has impls (or negative impls) of, and each alias
+    /// it has rules for, the clauses from the layers below are dropped; the
+    /// layer's declarations of traits and ADTs replace those with the same name.
+    /// Everything else is kept.
+    Override,
+}
+
+/// Declarations built up in layers, where a later layer may shadow the clauses
+/// of earlier ones (e.g., to simulate patching a crate in a test). Unlike
+/// [`Decls::federated`], in which every member contributes, later layers win.
+#[derive(Clone, Debug)]
+pub struct LayeredDb {
+    decls: Decls,
+}
+
+impl LayeredDb {
+    pub fn new(base: Decls) -> Self {
+        Self { decls: base }
+    }
+
+    pub fn push_layer(&mut self, layer: Decls, mode: OverrideMode) -> &mut Self {
+        let base = std::mem::replace(&mut self.decls, Decls::empty());
+        let base = match mode {
+            OverrideMode::Extend => base,
+            OverrideMode::Override => base.without_clauses_overridden_by(&layer),
+        };
+        self.decls = Decls::federated([base, layer]);
+        self
+    }
+
+    /// The declarations of all layers, with the overridden clauses removed.
+    pub fn decls(&self) -> &Decls {
+        &self.decls
+    }
+}
+
+impl Decls {
+    /// Drops the clauses that `layer` overrides; see [`OverrideMode::Override`].
+    fn without_clauses_overridden_by(mut self, layer: &Decls) -> Self {
+        let traits: Set<TraitId> = layer
+            .impl_decls
+            .iter()
+            .map(|i| i.binder.peek().trait_ref.trait_id.clone())
+            .chain(
+                layer
+                    .neg_impl_decls
+                    .iter()
+                    .map(|i| i.binder.peek().trait_ref.trait_id.clone()),
+            )
+            .collect();
+        let aliases: Set<AliasName> = layer
+            .alias_eq_decls
+            .iter()
+            .map(|a| a.alias_name())
+            .chain(layer.alias_bound_decls.iter().map(|a| a.alias_name()))
+            .collect();
+
+        self.impl_decls
+            .retain(|i| !traits.contains(&i.binder.peek().trait_ref.trait_id));
+        self.neg_impl_decls
+            .retain(|i| !traits.contains(&i.binder.peek().trait_ref.trait_id));
+        self.alias_eq_decls
+            .retain(|a| !aliases.contains(&a.alias_name()));
+        self.alias_bound_decls
+            .retain(|a| !aliases.contains(&a.alias_name()));
+        self.trait_decls.retain(|t| !layer.has_trait_decl(&t.id));
+        self.adt_decls
+            .retain(|a| !layer.adt_decls.iter().any(|b| b.id == a.id));
+        self
+    }
+}
+
 /// An "impl decl" indicates that a trait is implemented for a given set of types.
 /// One "impl decl" is created for each impl in the Rust source.
-#[term($?safety impl $binder)]
+#[term($?defaultness $?safety impl $binder)]
 pub struct ImplDecl {
+    /// Whether this is a `default impl`, which more specific impls may specialize.
+    pub defaultness: Defaultness,
     /// The safety this impl declares, which needs to match the implemented trait's safety.
     pub safety: Safety,
     /// The binder covers the generic variables from the impl
@@ -149,12 +316,48 @@ pub enum Safety {
     Unsafe,
 }
 
+/// Mark a trait impl as `default`, meaning that a more specific impl may
+/// specialize (override) it.
+#[term]
+#[derive(Default)]
+pub enum Defaultness {
+    #[default]
+    Final,
+    Default,
+}
+
+/// Mark an ADT as fundamental, like `Box`: a local type wrapped in it still
+/// counts as local for the orphan rules.
+#[term]
+#[derive(Default)]
+pub enum Fundamentalness {
+    #[default]
+    NotFundamental,
+    Fundamental,
+}
+
+/// Whether a proof of a trait goal may rely on the goal itself.
+///
+/// A cycle of goals holds if every goal in it is coinductive (as for auto
+/// traits). If the cycle passes through an inductive goal, it does not.
+/// A trait is inductive unless declared `coinductive`.
+#[term]
+#[derive(Default)]
+pub enum Coinduction {
+    Coinductive,
+    #[default]
+    Inductive,
+}
+
 /// A "trait declaration" declares a trait that exists, its generics, and its where-clauses.
 /// It doesn't capture the trait items, which will be transformed into other sorts of rules.
 ///
 /// In Rust syntax, it covers the `trait Foo: Bar` part of the declaration, but not what appears in the `{...}`.
-#[term($?safety trait $id $binder)]
+#[term($?coinduction $?safety trait $id $binder)]
 pub struct TraitDecl {
+    /// Whether cycles through the trait's goals hold
+    pub coinduction: Coinduction,
+
     /// The name of the trait
     pub id: TraitId,
 
@@ -289,8 +492,11 @@ pub struct AliasBoundDeclBoundData {
 /// It doesn't capture the ADT fields, yet.
 ///
 /// In Rust syntax, it covers the `struct Foo<X> where X: Bar` part of the declaration, but not what appears in the `{...}`.
-#[term(adt $id $binder)]
+#[term($?fundamentalness adt $id $binder)]
 pub struct AdtDecl {
+    /// Whether the ADT is fundamental, see [`Fundamentalness`].
+    pub fundamentalness: Fundamentalness,
+
     /// The name of the ADT.
     pub id: AdtId,
 
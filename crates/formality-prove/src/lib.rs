@@ -6,10 +6,30 @@ mod decls;
 mod prove;
 
 pub use decls::*;
+pub use prove::can_upcast_dyn;
+pub use prove::filter_implementors;
+pub use prove::impls_may_overlap;
 pub use prove::prove;
+pub use prove::prove_array_repeat;
+pub use prove::prove_normalized_eq;
+pub use prove::trait_satisfiable;
 pub use prove::Constraints;
+pub use prove::Sizedness;
+pub use prove::{candidate_clauses, Candidate};
+pub use prove::{
+    discharge, prove_or_defer, DeferredObligation, DeferredObligations, ObligationKind,
+};
 pub use prove::{is_definitely_not_proveable, may_not_be_provable, negation_via_failure};
-pub use prove::{Bias, Env};
+pub use prove::{propagate, prove_with_propagation};
+pub use prove::{prove_hidden_types, DefiningScope, OpaqueTyDecl, OpaqueTyId};
+pub use prove::{solver_configuration, with_solver_configuration, SolverConfiguration};
+pub use prove::{verify_proof, ProofError, ProofStep, ProofTree};
+pub use prove::{AmbiguityPolicy, ProveCache};
+pub use prove::{Bias, Env, Inconsistency};
+pub use prove::{DifferentialSolver, Divergence, Solver};
+pub use prove::{EquivalenceClass, InferenceGraph, InferenceGraphDiff};
+pub use prove::{ObligationForest, ObligationId, ObligationState};
+pub use prove::{SolveResult, Trivalent};
 
 #[cfg(test)]
 mod test;
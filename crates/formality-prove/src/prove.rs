@@ -1,27 +1,72 @@
+mod can_upcast_dyn;
+mod candidate_clauses;
 mod combinators;
 mod constraints;
+mod deferred;
+mod differential;
 mod env;
+mod filter_implementors;
+mod impls_may_overlap;
+mod inference_graph;
 mod is_local;
 mod minimize;
 mod negation;
+mod obligation_forest;
+mod opaque;
+mod propagate;
 mod prove_after;
+mod prove_cache;
 mod prove_eq;
 mod prove_normalize;
+mod prove_normalized_eq;
 mod prove_via;
 mod prove_wc;
 mod prove_wc_list;
 mod prove_wf;
+mod sizedness;
+mod solve_result;
+mod solver_configuration;
+mod trait_satisfiable;
+mod verify_proof;
 
+pub use can_upcast_dyn::can_upcast_dyn;
+pub use candidate_clauses::{candidate_clauses, Candidate};
 pub use constraints::Constraints;
+pub use deferred::{
+    discharge, prove_or_defer, DeferredObligation, DeferredObligations, ObligationKind,
+};
+use std::cell::RefCell;
+
+use formality_core::judgment::{
+    current_judgment_depth, failure_collector_installed, hooks_installed, ran_out_of_fuel,
+};
+use formality_core::scoped::with_scoped;
 use formality_core::visit::CoreVisit;
-use formality_core::{ProvenSet, Upcast};
-use formality_types::grammar::Wcs;
+use formality_core::{fixed_point, Map, ProvenSet, Upcast};
+use formality_types::grammar::{Predicate, TraitId, Wc, WcData, Wcs};
 use tracing::Level;
 
-use crate::decls::Decls;
+use crate::decls::{Decls, NegImplDecl};
 
-pub use self::env::{Bias, Env};
+pub use self::differential::{DifferentialSolver, Divergence, Solver};
+pub use self::env::{Bias, Env, Inconsistency};
+pub use self::filter_implementors::filter_implementors;
+pub use self::impls_may_overlap::impls_may_overlap;
+pub use self::inference_graph::{EquivalenceClass, InferenceGraph, InferenceGraphDiff};
+pub use self::obligation_forest::{ObligationForest, ObligationId, ObligationState};
+pub use self::opaque::{prove_hidden_types, DefiningScope, OpaqueTyDecl, OpaqueTyId};
+pub use self::propagate::{propagate, prove_with_propagation};
+pub use self::prove_cache::{AmbiguityPolicy, ProveCache};
+pub use self::prove_normalized_eq::prove_normalized_eq;
 use self::prove_wc_list::prove_wc_list;
+pub use self::prove_wf::prove_array_repeat;
+pub use self::sizedness::Sizedness;
+pub use self::solve_result::{SolveResult, Trivalent};
+pub use self::solver_configuration::{
+    solver_configuration, with_solver_configuration, SolverConfiguration,
+};
+pub use self::trait_satisfiable::trait_satisfiable;
+pub use self::verify_proof::{verify_proof, ProofError, ProofStep, ProofTree};
 pub use negation::{is_definitely_not_proveable, may_not_be_provable, negation_via_failure};
 
 /// Top-level entry point for proving things; other rules recurse to this one.
@@ -48,17 +93,192 @@ pub fn prove(
             term_in.size(),
             decls.max_size
         );
-        return ProvenSet::singleton(Constraints::none(env).ambiguous());
+        return ProvenSet::singleton(Constraints::none(env).overflow());
     }
 
     assert!(env.encloses(term_in));
 
-    let result_set = prove_wc_list(decls, &env, assumptions, goal);
+    // The checks for undeclared traits and for blocking negative impls only
+    // serve to explain a failure, so they are done for the goal a caller posed
+    // and not again for each subgoal of its proof.
+    let top_level = current_judgment_depth() == 0;
+
+    if top_level {
+        if let Some(trait_id) = (&goal)
+            .into_iter()
+            .find_map(|wc| undeclared_trait(&decls, &wc))
+        {
+            return ProvenSet::unknown_predicate(
+                format!("prove {{ goal: {goal:?} }}"),
+                format!("trait {trait_id:?}"),
+            );
+        }
+    }
+
+    // Under a soundness bias, failing is always a safe answer, so there is no
+    // need to search when the assumptions contradict each other.
+    if env.bias() == Bias::Soundness {
+        if let Some(Inconsistency { a, b }) = env.is_inconsistent(&assumptions) {
+            return ProvenSet::failed(
+                format!("prove {{ goal: {goal:?}, assumptions: {assumptions:?} }}"),
+                format!("inconsistent assumptions: `{a:?}` and `{b:?}` cannot both hold"),
+            );
+        }
+    }
+
+    // FIXME: the search cannot be checkpointed (to step backward and forward
+    // in a debugger), as it recurses on the Rust stack from here rather than
+    // running off an explicit work stack, and each subgoal gets its own clone
+    // of `env` rather than sharing structure through a trail of snapshots. The
+    // closest there is today is replaying a search with
+    // `formality_core::judgment::{record_selection, force_selection}`.
+    let result_set = with_subgoal_memo((&env, &assumptions, &goal), || {
+        let limits_reached = fixed_point::limits_reached();
+        let mut result_set = prove_wc_list(&decls, &env, &assumptions, &goal);
+
+        // Rules cut off by running out of fuel or by a recursion or iteration
+        // limit may have had other solutions. (If there are none, the failure
+        // already says why, see `SolveResult::of`.)
+        if ran_out_of_fuel() || fixed_point::limits_reached() != limits_reached {
+            result_set = result_set.map(Constraints::overflow);
+        }
+
+        result_set
+    });
+
+    if top_level && !result_set.is_proven() {
+        if let Some(neg_impl) = (&goal)
+            .into_iter()
+            .find_map(|wc| blocking_neg_impl(&decls, &env, &assumptions, &wc))
+        {
+            return ProvenSet::disproven_by_negative_impl(
+                format!("prove {{ goal: {goal:?} }}"),
+                format!("{neg_impl:?}"),
+            );
+        }
+    }
 
     tracing::debug!(?result_set);
 
-    result_set.map(|r| {
+    Constraints::merge_duplicates(result_set.map(|r| {
         assert!(r.is_valid_extension_of(&env));
         min.reconstitute(r)
-    })
+    }))
+}
+
+/// The subgoals solved so far by the outermost [`prove`] call on this thread.
+#[derive(Default)]
+struct SubgoalMemo {
+    results: Map<(Env, Wcs, Wcs), ProvenSet<Constraints>>,
+}
+
+thread_local! {
+    /// The memo of the outermost [`prove`] call on this thread, or `None` if
+    /// there is none in progress.
+    static SUBGOALS: RefCell<Option<SubgoalMemo>> = const { RefCell::new(None) };
+}
+
+/// Solves the (minimized) subgoal `key` with `op`, unless the same subgoal was
+/// already solved during the current top-level call to [`prove`], so that
+/// independent branches of a proof do not solve a shared subgoal again.
+/// This is the memo of [`SolverConfiguration::Memoized`].
+///
+/// The memo belongs to the outermost call and is dropped when it returns, so
+/// it is not keyed on the declarations: the rules pass their `decls` along
+/// unchanged, and comparing them on every subgoal would cost about as much as
+/// solving it.
+///
+/// A result is only remembered if it is final: computing it read no
+/// provisional result of a goal still being solved (see
+/// [`cycle_hits`](formality_core::fixed_point::cycle_hits)), and it did not
+/// overflow, since overflow depends on how deep in the proof it happened.
+///
+/// A reused result skips the rules that derived it, so the memo is bypassed
+/// while anything observes those rules: a judgment hook (see
+/// [`hooks_installed`](formality_core::judgment::hooks_installed)) or a
+/// failure collector.
+fn with_subgoal_memo(
+    key: (&Env, &Wcs, &Wcs),
+    op: impl FnOnce() -> ProvenSet<Constraints>,
+) -> ProvenSet<Constraints> {
+    if solver_configuration() == SolverConfiguration::Cosld
+        || hooks_installed()
+        || failure_collector_installed()
+    {
+        return op();
+    }
+
+    if SUBGOALS.with(|s| s.borrow().is_none()) {
+        return with_scoped(&SUBGOALS, Some(SubgoalMemo::default()), || {
+            with_subgoal_memo(key, op)
+        });
+    }
+
+    let (env, assumptions, goal) = key;
+    let key = (env.clone(), assumptions.clone(), goal.clone());
+    let memoized = SUBGOALS.with(|s| s.borrow().as_ref().unwrap().results.get(&key).cloned());
+    if let Some(result) = memoized {
+        tracing::debug!("reusing result of subgoal solved earlier in this query");
+        return result;
+    }
+
+    let cycle_hits = fixed_point::cycle_hits();
+    let result = op();
+    if fixed_point::cycle_hits() == cycle_hits && SolveResult::of(&result) != SolveResult::Overflow
+    {
+        SUBGOALS.with(|s| {
+            s.borrow_mut()
+                .as_mut()
+                .unwrap()
+                .results
+                .insert(key, result.clone())
+        });
+    }
+    result
+}
+
+/// Finds a trait referenced by a predicate in `wc` that `decls` does not declare.
+/// Such a top-level goal fails with a distinct [`RuleFailureCause::UnknownPredicate`][]
+/// rather than the usual failure to find an applicable rule, since no rule could
+/// ever apply.
+///
+/// [`RuleFailureCause::UnknownPredicate`]: formality_core::judgment::RuleFailureCause::UnknownPredicate
+fn undeclared_trait(decls: &Decls, wc: &Wc) -> Option<TraitId> {
+    match wc.data() {
+        WcData::Predicate(
+            Predicate::IsImplemented(trait_ref)
+            | Predicate::NotImplemented(trait_ref)
+            | Predicate::WellFormedTraitRef(trait_ref)
+            | Predicate::IsLocal(trait_ref),
+        ) if !decls.has_trait_decl(&trait_ref.trait_id) => Some(trait_ref.trait_id.clone()),
+        WcData::Predicate(_) | WcData::Relation(_) => None,
+        WcData::ForAll(binder) => undeclared_trait(decls, binder.peek()),
+        WcData::Implies(_, wc) => undeclared_trait(decls, wc),
+    }
+}
+
+/// Finds a negative impl showing that `wc` can never hold: `wc` is a trait goal
+/// and the negative impl applies to it without constraining any inference
+/// variables. Such a top-level goal fails with a
+/// [`RuleFailureCause::DisprovenByNegativeImpl`][] naming that impl.
+///
+/// [`RuleFailureCause::DisprovenByNegativeImpl`]: formality_core::judgment::RuleFailureCause::DisprovenByNegativeImpl
+fn blocking_neg_impl(decls: &Decls, env: &Env, assumptions: &Wcs, wc: &Wc) -> Option<NegImplDecl> {
+    let WcData::Predicate(Predicate::IsImplemented(trait_ref)) = wc.data() else {
+        return None;
+    };
+    decls
+        .neg_impl_decls(&trait_ref.trait_id)
+        .find(|neg_impl| {
+            let (env, subst) = env.existential_substitution(&neg_impl.binder);
+            let i = neg_impl.binder.instantiate_with(&subst).unwrap();
+            let goal = (
+                Wcs::all_eq(&trait_ref.parameters, &i.trait_ref.parameters),
+                &i.where_clause,
+            );
+            prove(decls, env, assumptions, goal)
+                .iter()
+                .any(|c| c.clone().pop_subst(&subst).unconditionally_true())
+        })
+        .cloned()
 }
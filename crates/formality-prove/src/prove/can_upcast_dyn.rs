@@ -0,0 +1,42 @@
+use formality_core::{judgment_fn, Upcast};
+use formality_types::grammar::{BoundVar, DynTy, ParameterKind, Wc, WcData, Wcs};
+
+use crate::{decls::Decls, prove::prove};
+
+use super::{constraints::Constraints, env::Env};
+
+judgment_fn! {
+    /// Proves that the trait object `from` can be upcast to the trait object `to`,
+    /// as in the coercion `dyn Sub + Send` to `dyn Super`. This holds when the
+    /// principal of `to` is a supertrait of (or the same as) the principal of
+    /// `from`, and `from` carries every auto trait that `to` requires.
+    pub fn can_upcast_dyn(
+        decls: Decls,
+        env: Env,
+        assumptions: Wcs,
+        from: DynTy,
+        to: DynTy,
+    ) => Constraints {
+        debug(from, to, assumptions, env, decls)
+
+        (
+            (if to.auto_traits.is_subset(&from.auto_traits))
+            (prove(&decls, &env, &assumptions, principal_implies(&from, &to)) => c)
+            --- ("upcast")
+            (can_upcast_dyn(decls, env, assumptions, from, to) => c)
+        )
+    }
+}
+
+/// `for<ty Self> if (Self: From) (Self: To)`, where `From` and `To` are the
+/// principal traits. Proving it relies on the supertrait invariants of `From`.
+fn principal_implies(from: &DynTy, to: &DynTy) -> Wc {
+    let self_var = BoundVar::fresh(ParameterKind::Ty);
+    Wc::for_all(
+        &[self_var],
+        WcData::Implies(
+            from.principal_with(self_var).upcast(),
+            to.principal_with(self_var).upcast(),
+        ),
+    )
+}
@@ -0,0 +1,54 @@
+use formality_core::Upcast;
+use formality_types::grammar::{TraitRef, Wcs};
+
+use crate::decls::{Decls, ImplDecl};
+
+use super::{prove, Constraints, Env};
+
+/// An impl that may apply to a goal, as found by [`candidate_clauses`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Candidate {
+    /// The impl itself, as declared.
+    pub impl_decl: ImplDecl,
+
+    /// The result of unifying the goal with the impl header. The environment
+    /// includes the existential variables that replaced the impl's parameters.
+    pub unifier: Constraints,
+
+    /// The impl's where-clauses under `unifier`, which remain to be proven
+    /// for the impl to apply.
+    pub residual: Wcs,
+}
+
+/// Lists the impls whose header unifies with `goal`, without trying to prove
+/// their where-clauses. This exposes the candidate selection step of the
+/// "positive impl" rule for tooling; an impl whose header unifies in more than
+/// one way yields one candidate per unifier.
+pub fn candidate_clauses(
+    decls: impl Upcast<Decls>,
+    env: impl Upcast<Env>,
+    goal: &TraitRef,
+) -> Vec<Candidate> {
+    let decls: Decls = decls.upcast();
+    let env: Env = env.upcast();
+
+    let mut candidates = vec![];
+    for impl_decl in decls.impl_decls(&goal.trait_id) {
+        let (env, subst) = env.existential_substitution(&impl_decl.binder);
+        let i = impl_decl.binder.instantiate_with(&subst).unwrap();
+        let unifiers = prove(
+            &decls,
+            env,
+            (),
+            Wcs::all_eq(&goal.parameters, &i.trait_ref.parameters),
+        );
+        for unifier in unifiers.iter() {
+            candidates.push(Candidate {
+                impl_decl: impl_decl.clone(),
+                unifier: unifier.clone(),
+                residual: unifier.substitution().apply(&i.where_clause),
+            });
+        }
+    }
+    candidates
+}
@@ -1,15 +1,40 @@
 use super::env::Env;
-use formality_core::{cast_impl, visit::CoreVisit, Downcast, Upcast, UpcastFrom};
+use formality_core::{
+    cast_impl, variable::with_variable_names, visit::CoreVisit, Downcast, Map, ProvenSet, Set,
+    Upcast, UpcastFrom,
+};
 use formality_types::{
-    grammar::{ExistentialVar, Parameter, Substitution, Variable},
+    grammar::{
+        ExistentialVar, Parameter, Predicate, Substitution, TraitRef, Variable, Wc, WcData, Wcs,
+    },
     rust::Visit,
 };
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Constraints {
     pub env: Env,
     pub known_true: bool,
+
+    /// True if a limit was reached while solving, so that these constraints
+    /// are not `known_true` because of overflow and not (or not only) because
+    /// the goal is genuinely ambiguous. Raising the limit may resolve them.
+    pub overflow: bool,
+
     pub substitution: Substitution,
+
+    /// Trait goals that the proof assumed to hold because proving them led
+    /// back to themselves (coinduction), e.g. `Send(List<T>)` when `List<T>`
+    /// contains a `Box<List<T>>`. Solutions that differ only in bookkeeping
+    /// like this are merged by [`prove`](crate::prove).
+    pub coinductive_assumptions: Set<Wc>,
+
+    /// Trait assumptions that the proof relied on, kept so that the rule that
+    /// introduced a coinductive assumption can tell whether it was used.
+    pub(crate) assumptions_used: Set<Wc>,
+
+    /// Like `assumptions_used`, but used beneath the impl of an inductive
+    /// trait. A cycle that closes through such a goal is not coinductive.
+    pub(crate) assumptions_used_inductively: Set<Wc>,
 }
 
 cast_impl!(Constraints);
@@ -46,6 +71,10 @@ impl Constraints {
             env,
             substitution,
             known_true: true,
+            overflow: false,
+            coinductive_assumptions: Set::new(),
+            assumptions_used: Set::new(),
+            assumptions_used_inductively: Set::new(),
         };
         c2.assert_valid();
         c2
@@ -66,6 +95,54 @@ impl Constraints {
         }
     }
 
+    /// Like [`Self::ambiguous`], but because the solver gave up at a limit.
+    pub fn overflow(self) -> Constraints {
+        Self {
+            known_true: false,
+            overflow: true,
+            ..self
+        }
+    }
+
+    /// Records that the proof used the assumption `a`. Only trait assumptions
+    /// are recorded, as only they can be coinductive.
+    pub(crate) fn with_assumption_used(mut self, a: &Wc) -> Self {
+        if let WcData::Predicate(Predicate::IsImplemented(_)) = a.data() {
+            self.assumptions_used.insert(self.substitution.apply(a));
+        }
+        self
+    }
+
+    /// Called once `trait_ref` has been proven with itself added to
+    /// `assumptions`: if the proof used that extra assumption, it is recorded
+    /// as a coinductive assumption.
+    pub(crate) fn with_coinduction(mut self, assumptions: &Wcs, trait_ref: &TraitRef) -> Self {
+        let goal: Wc = self.substitution.apply(trait_ref).is_implemented().upcast();
+        let assumptions = self.substitution.apply(assumptions);
+        if !(&assumptions).into_iter().any(|a| a == goal) && self.assumptions_used.remove(&goal) {
+            self.coinductive_assumptions.insert(goal);
+        }
+        self
+    }
+
+    /// Called once the goal of an inductive trait has been proven: the
+    /// assumptions used so far were used beneath it.
+    pub(crate) fn with_induction(mut self) -> Self {
+        let used = std::mem::take(&mut self.assumptions_used);
+        self.assumptions_used_inductively.extend(used);
+        self
+    }
+
+    /// True if `trait_ref`, proven with itself added to `assumptions`, relied
+    /// on that extra assumption beneath an inductive goal; the cycle then
+    /// passes through that goal and does not hold.
+    pub(crate) fn closes_inductive_cycle(&self, assumptions: &Wcs, trait_ref: &TraitRef) -> bool {
+        let goal: Wc = self.substitution.apply(trait_ref).is_implemented().upcast();
+        let assumptions = self.substitution.apply(assumptions);
+        !(&assumptions).into_iter().any(|a| a == goal)
+            && self.assumptions_used_inductively.contains(&goal)
+    }
+
     /// Given constraints from solving the subparts of `(A /\ B)`, yield combined constraints.
     ///
     /// # Parameters
@@ -97,10 +174,29 @@ impl Constraints {
         // Apply c2's substitution to our substitution (since it may have bound
         // existential variables that we reference)
         let c1_substitution = c2.substitution.apply(&self.substitution);
+        let merge = |c1: &Set<Wc>, c2: Set<Wc>, subst: &Substitution| -> Set<Wc> {
+            c1.iter().map(|wc| subst.apply(wc)).chain(c2).collect()
+        };
 
         Constraints {
             env: c2.env,
             known_true: self.known_true && c2.known_true,
+            overflow: self.overflow || c2.overflow,
+            coinductive_assumptions: merge(
+                &self.coinductive_assumptions,
+                c2.coinductive_assumptions,
+                &c2.substitution,
+            ),
+            assumptions_used: merge(
+                &self.assumptions_used,
+                c2.assumptions_used,
+                &c2.substitution,
+            ),
+            assumptions_used_inductively: merge(
+                &self.assumptions_used_inductively,
+                c2.assumptions_used_inductively,
+                &c2.substitution,
+            ),
             substitution: c1_substitution.into_iter().chain(c2.substitution).collect(),
         }
     }
@@ -114,14 +210,97 @@ impl Constraints {
         }
 
         let vars = self.env.pop_vars(v);
+
+        // Assumptions about the popped variables cannot be stated outside
+        // of their scope.
+        let mentions_none = |wc: &Wc| vars.iter().all(|&v| !occurs_in(v, wc));
+        self.coinductive_assumptions.retain(mentions_none);
+        self.assumptions_used.retain(mentions_none);
+        self.assumptions_used_inductively.retain(mentions_none);
+
         self.substitution -= vars;
 
         self
     }
 
+    /// Merges solutions that differ only in `overflow` and the assumption
+    /// sets, which are bookkeeping about how a solution was found rather than
+    /// part of the solution: the merged solution overflowed if any of them did,
+    /// and records every assumption any of them recorded, which is conservative
+    /// for the inductive-cycle check.
+    pub(crate) fn merge_duplicates(set: ProvenSet<Constraints>) -> ProvenSet<Constraints> {
+        let mut merged: Map<_, Constraints> = Map::new();
+        for c in set.iter() {
+            match merged.get_mut(&c.identity()) {
+                Some(m) => {
+                    m.overflow |= c.overflow;
+                    m.coinductive_assumptions
+                        .extend(c.coinductive_assumptions.iter().cloned());
+                    m.assumptions_used
+                        .extend(c.assumptions_used.iter().cloned());
+                    m.assumptions_used_inductively
+                        .extend(c.assumptions_used_inductively.iter().cloned());
+                }
+                None => {
+                    merged.insert(c.identity(), c.clone());
+                }
+            }
+        }
+
+        // Each merged solution replaces the first of the solutions it merges.
+        set.flat_map(|c| merged.remove(&c.identity()))
+    }
+
+    /// The fields that [`Self::merge_duplicates`] does not merge.
+    fn identity(&self) -> (Env, bool, Substitution) {
+        (self.env.clone(), self.known_true, self.substitution.clone())
+    }
+
     pub fn is_valid_extension_of(&self, env0: &Env) -> bool {
         self.env.is_valid_extension_of(env0)
     }
+
+    /// Renders these constraints like `Debug`, but with each variable of the
+    /// environment relabeled `A`, `B`, ... (in the order the environment lists
+    /// them) while keeping its `?`/`!` sigil. The names depend only on the shape
+    /// of the solution, not on how many variables were created while solving.
+    pub fn to_readable_string(&self) -> String {
+        let names = self.env.variables().iter().enumerate().map(|(index, &v)| {
+            let sigil = match v {
+                Variable::ExistentialVar(_) => "?",
+                Variable::UniversalVar(_) | Variable::BoundVar(_) => "!",
+            };
+            (v, format!("{sigil}{}", readable_name(index)))
+        });
+        with_variable_names(names, || format!("{self:?}"))
+    }
+}
+
+/// `A`, `B`, ..., `Z`, `A1`, `B1`, ...
+fn readable_name(index: usize) -> String {
+    let letter = (b'A' + (index % 26) as u8) as char;
+    match index / 26 {
+        0 => letter.to_string(),
+        n => format!("{letter}{n}"),
+    }
+}
+
+impl std::fmt::Debug for Constraints {
+    /// `overflow` and the coinductive assumptions are only shown when set, and
+    /// the assumptions used are never shown, as they are bookkeeping.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Constraints");
+        s.field("env", &self.env)
+            .field("known_true", &self.known_true);
+        if self.overflow {
+            s.field("overflow", &self.overflow);
+        }
+        s.field("substitution", &self.substitution);
+        if !self.coinductive_assumptions.is_empty() {
+            s.field("coinductive_assumptions", &self.coinductive_assumptions);
+        }
+        s.finish()
+    }
 }
 
 impl CoreVisit<crate::FormalityLang> for Constraints {
@@ -129,7 +308,11 @@ impl CoreVisit<crate::FormalityLang> for Constraints {
         let Constraints {
             env,
             known_true: _,
+            overflow: _,
             substitution,
+            coinductive_assumptions,
+            assumptions_used,
+            assumptions_used_inductively,
         } = self;
 
         // Debatable if `env.free_variables()` should be considered
@@ -138,6 +321,9 @@ impl CoreVisit<crate::FormalityLang> for Constraints {
         env.free_variables()
             .into_iter()
             .chain(substitution.free_variables())
+            .chain(coinductive_assumptions.free_variables())
+            .chain(assumptions_used.free_variables())
+            .chain(assumptions_used_inductively.free_variables())
             .collect()
     }
 
@@ -145,7 +331,11 @@ impl CoreVisit<crate::FormalityLang> for Constraints {
         let Constraints {
             env,
             known_true: _,
+            overflow: _,
             substitution,
+            coinductive_assumptions: _,
+            assumptions_used: _,
+            assumptions_used_inductively: _,
         } = self;
         env.size() + substitution.size()
     }
@@ -154,9 +344,17 @@ impl CoreVisit<crate::FormalityLang> for Constraints {
         let Constraints {
             env,
             known_true: _,
+            overflow: _,
             substitution,
+            coinductive_assumptions,
+            assumptions_used,
+            assumptions_used_inductively,
         } = self;
 
+        assert!(env.encloses(coinductive_assumptions));
+        assert!(env.encloses(assumptions_used));
+        assert!(env.encloses(assumptions_used_inductively));
+
         let domain = substitution.domain();
         let range = substitution.range();
 
@@ -0,0 +1,153 @@
+use formality_core::{judgment::FailedJudgment, Downcast, Upcast};
+use formality_types::grammar::{AliasTy, Predicate, Relation, Ty, Wc, WcData, Wcs};
+
+use crate::decls::Decls;
+
+use super::{prove, Constraints, Env};
+
+/// The broad category of a [`DeferredObligation`], so that a client can decide
+/// which ones are worth retrying.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ObligationKind {
+    /// An equality involving an alias type (e.g., `<?X as Iterator>::Item = u32`).
+    Projection,
+
+    /// An outlives relation (e.g., `?X : 'a`).
+    Outlives,
+
+    /// A trait goal (e.g., `Debug(?X)`).
+    PendingTrait,
+
+    /// Anything else.
+    Other,
+}
+
+impl ObligationKind {
+    pub fn of(goal: &Wc) -> Self {
+        match goal.data() {
+            WcData::Predicate(Predicate::AliasEq(..)) => ObligationKind::Projection,
+            WcData::Relation(Relation::Equals(a, b))
+                if is_alias(a.downcast()) || is_alias(b.downcast()) =>
+            {
+                ObligationKind::Projection
+            }
+            WcData::Relation(Relation::Outlives(..)) => ObligationKind::Outlives,
+            WcData::Predicate(Predicate::IsImplemented(_)) => ObligationKind::PendingTrait,
+            WcData::Predicate(_) | WcData::Relation(_) => ObligationKind::Other,
+            WcData::ForAll(binder) => ObligationKind::of(binder.peek()),
+            WcData::Implies(_, goal) => ObligationKind::of(goal),
+        }
+    }
+}
+
+fn is_alias(ty: Option<Ty>) -> bool {
+    ty.is_some_and(|ty| ty.downcast::<AliasTy>().is_some())
+}
+
+/// A goal that could not yet be proven unconditionally, but may be once more
+/// is known about the inference variables it mentions.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeferredObligation {
+    pub kind: ObligationKind,
+    pub goal: Wc,
+}
+
+/// The obligations deferred while solving, to be re-posed later with
+/// [`discharge`]. This lets a type checker run trait solving in phases.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeferredObligations {
+    obligations: Vec<DeferredObligation>,
+}
+
+impl DeferredObligations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn defer(&mut self, goal: impl Upcast<Wc>) {
+        let goal: Wc = goal.upcast();
+        self.obligations.push(DeferredObligation {
+            kind: ObligationKind::of(&goal),
+            goal,
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DeferredObligation> {
+        self.obligations.iter()
+    }
+
+    /// The deferred obligations of the given kind.
+    pub fn of_kind(&self, kind: ObligationKind) -> impl Iterator<Item = &DeferredObligation> {
+        self.iter().filter(move |o| o.kind == kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.obligations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.obligations.is_empty()
+    }
+}
+
+/// Proves each goal in `goal` separately. Goals that hold unconditionally are
+/// done; goals that hold only ambiguously or under some choice of inference
+/// variables are added to `deferred`. Returns an error if a goal cannot be
+/// proven at all.
+pub fn prove_or_defer(
+    decls: impl Upcast<Decls>,
+    env: impl Upcast<Env>,
+    assumptions: impl Upcast<Wcs>,
+    goal: impl Upcast<Wcs>,
+    deferred: &mut DeferredObligations,
+) -> Result<(), Box<FailedJudgment>> {
+    let decls: Decls = decls.upcast();
+    let env: Env = env.upcast();
+    let assumptions: Wcs = assumptions.upcast();
+    let goal: Wcs = goal.upcast();
+    for wc in &goal {
+        if !holds_unconditionally(&decls, &env, &assumptions, &wc)? {
+            deferred.defer(wc);
+        }
+    }
+    Ok(())
+}
+
+/// Re-poses the obligations in `deferred` under `constraints`, the current
+/// state of inference. Those that now hold unconditionally are removed; the
+/// ones that remain in `deferred` are still undischarged. Returns an error if
+/// one of them can no longer be proven at all; that one and those not yet
+/// re-posed then remain in `deferred` as well.
+pub fn discharge(
+    decls: impl Upcast<Decls>,
+    constraints: &Constraints,
+    assumptions: impl Upcast<Wcs>,
+    deferred: &mut DeferredObligations,
+) -> Result<(), Box<FailedJudgment>> {
+    let decls: Decls = decls.upcast();
+    let assumptions: Wcs = constraints.substitution().apply(&assumptions.upcast());
+    for o in &mut deferred.obligations {
+        o.goal = constraints.substitution().apply(&o.goal);
+        o.kind = ObligationKind::of(&o.goal);
+    }
+    let mut index = 0;
+    while index < deferred.obligations.len() {
+        let goal = &deferred.obligations[index].goal;
+        if holds_unconditionally(&decls, constraints.env(), &assumptions, goal)? {
+            deferred.obligations.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    Ok(())
+}
+
+fn holds_unconditionally(
+    decls: &Decls,
+    env: &Env,
+    assumptions: &Wcs,
+    goal: &Wc,
+) -> Result<bool, Box<FailedJudgment>> {
+    let solutions = prove(decls, env, assumptions, goal).into_set()?;
+    Ok(solutions.iter().any(|c| c.unconditionally_true()))
+}
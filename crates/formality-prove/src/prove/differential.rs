@@ -0,0 +1,90 @@
+use formality_core::{ProvenSet, Upcast};
+use formality_types::grammar::Wcs;
+
+use crate::decls::Decls;
+
+use super::{prove, with_solver_configuration, Constraints, Env, SolveResult, SolverConfiguration};
+
+/// Something that proves a goal, as [`prove`] does.
+pub type Solver = Box<dyn Fn(&Decls, &Env, &Wcs, &Wcs) -> ProvenSet<Constraints>>;
+
+/// Runs two solvers on every query and checks that they agree on whether the
+/// goal holds (see [`SolveResult::truth`]), for validating one solver against
+/// another. Which solutions they find may differ.
+pub struct DifferentialSolver {
+    left: (String, Solver),
+    right: (String, Solver),
+}
+
+/// A query on which the solvers of a [`DifferentialSolver`] disagree.
+#[derive(Clone, Debug)]
+pub struct Divergence {
+    pub assumptions: Wcs,
+    pub goal: Wcs,
+
+    /// The name of the first solver and its result.
+    pub left: (String, ProvenSet<Constraints>),
+
+    /// The name of the second solver and its result.
+    pub right: (String, ProvenSet<Constraints>),
+}
+
+impl DifferentialSolver {
+    pub fn new(
+        left_name: impl ToString,
+        left: impl Fn(&Decls, &Env, &Wcs, &Wcs) -> ProvenSet<Constraints> + 'static,
+        right_name: impl ToString,
+        right: impl Fn(&Decls, &Env, &Wcs, &Wcs) -> ProvenSet<Constraints> + 'static,
+    ) -> Self {
+        Self {
+            left: (left_name.to_string(), Box::new(left)),
+            right: (right_name.to_string(), Box::new(right)),
+        }
+    }
+
+    /// Compares [`prove`] under two [`SolverConfiguration`]s.
+    pub fn configurations(left: SolverConfiguration, right: SolverConfiguration) -> Self {
+        let solver = |config| {
+            move |decls: &Decls, env: &Env, assumptions: &Wcs, goal: &Wcs| {
+                with_solver_configuration(config, || prove(decls, env, assumptions, goal))
+            }
+        };
+        Self::new(
+            format!("{left:?}"),
+            solver(left),
+            format!("{right:?}"),
+            solver(right),
+        )
+    }
+
+    /// Proves `goal` with both solvers, returning the first one's result if
+    /// they agree.
+    pub fn prove(
+        &self,
+        decls: impl Upcast<Decls>,
+        env: impl Upcast<Env>,
+        assumptions: impl Upcast<Wcs>,
+        goal: impl Upcast<Wcs>,
+    ) -> Result<ProvenSet<Constraints>, Divergence> {
+        let decls: Decls = decls.upcast();
+        let env: Env = env.upcast();
+        let assumptions: Wcs = assumptions.upcast();
+        let goal: Wcs = goal.upcast();
+
+        let (left_name, left) = &self.left;
+        let (right_name, right) = &self.right;
+        let left_result = left(&decls, &env, &assumptions, &goal);
+        let right_result = right(&decls, &env, &assumptions, &goal);
+        if SolveResult::of(&left_result).truth() == SolveResult::of(&right_result).truth() {
+            return Ok(left_result);
+        }
+
+        tracing::debug!(?goal, "solvers {left_name} and {right_name} disagree");
+        Err(Divergence {
+            assumptions,
+            goal,
+            left: (left_name.clone(), left_result),
+            right: (right_name.clone(), right_result),
+        })
+    }
+}
@@ -1,8 +1,9 @@
-use formality_core::{cast_impl, visit::CoreVisit, Set, To, Upcast, UpcastFrom};
+use formality_core::{cast_impl, visit::CoreVisit, Map, Set, To, Upcast, UpcastFrom};
 use formality_macros::term;
 use formality_types::{
     grammar::{
-        Binder, ExistentialVar, ParameterKind, UniversalVar, VarIndex, VarSubstitution, Variable,
+        Binder, ExistentialVar, Parameter, ParameterKind, Relation, RigidName, TyData,
+        UniversalVar, VarIndex, VarSubstitution, Variable, Wc, WcData, Wcs,
     },
     rust::{Fold, Visit},
 };
@@ -252,6 +253,59 @@ impl Env {
     pub fn defines(&self, v: Variable) -> bool {
         self.variables.contains(&v)
     }
+
+    /// Looks for two of the `assumptions` that cannot both hold because they
+    /// equate a variable (or a rigid type) with rigid types of different names,
+    /// e.g., `T = u32` and `T = i32`. Nothing follows usefully from such
+    /// assumptions, so [`prove`](super::prove) fails right away rather than
+    /// searching. This is a shallow syntactic check, not a full consistency check.
+    pub fn is_inconsistent(&self, assumptions: &Wcs) -> Option<Inconsistency> {
+        let mut bindings: Map<Variable, (RigidName, Wc)> = Map::new();
+        for wc in assumptions {
+            let WcData::Relation(Relation::Equals(Parameter::Ty(a), Parameter::Ty(b))) = wc.data()
+            else {
+                continue;
+            };
+            let (var, name) = match (a.data(), b.data()) {
+                (TyData::RigidTy(a), TyData::RigidTy(b)) => {
+                    if a.name != b.name {
+                        return Some(Inconsistency {
+                            a: wc.clone(),
+                            b: wc.clone(),
+                        });
+                    }
+                    continue;
+                }
+                (TyData::Variable(v), TyData::RigidTy(r))
+                | (TyData::RigidTy(r), TyData::Variable(v)) => (*v, &r.name),
+                _ => continue,
+            };
+            if !self.defines(var) {
+                continue;
+            }
+            match bindings.get(&var) {
+                Some((bound, wc0)) if bound != name => {
+                    return Some(Inconsistency {
+                        a: wc0.clone(),
+                        b: wc.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    bindings.insert(var, (name.clone(), wc.clone()));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Two assumptions that cannot both hold, found by [`Env::is_inconsistent`].
+/// If a single assumption contradicts itself (e.g., `u32 = i32`), `a` and `b` are the same.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inconsistency {
+    pub a: Wc,
+    pub b: Wc,
 }
 
 impl CoreVisit<crate::FormalityLang> for Env {
@@ -0,0 +1,39 @@
+use formality_core::Upcast;
+use formality_types::grammar::{Parameter, TraitRef, Ty};
+
+use crate::decls::Decls;
+
+use super::{prove, Env};
+
+/// Returns those `candidates` that definitely implement `trait_ref_template`.
+///
+/// Each candidate is substituted for the self type (the first parameter) of
+/// `trait_ref_template`; the remaining parameters are kept as given. A candidate
+/// is retained only if the resulting goal is proven unconditionally, so
+/// candidates for which the solver is ambiguous are filtered out.
+pub fn filter_implementors(
+    decls: impl Upcast<Decls>,
+    trait_ref_template: &TraitRef,
+    candidates: &[Ty],
+) -> Vec<Ty> {
+    let decls: Decls = decls.upcast();
+    let other_parameters: Vec<Parameter> = trait_ref_template
+        .parameters
+        .iter()
+        .skip(1)
+        .cloned()
+        .collect();
+
+    candidates
+        .iter()
+        .filter(|candidate| {
+            let trait_ref = trait_ref_template
+                .trait_id
+                .with(*candidate, &other_parameters);
+            prove(&decls, Env::default(), (), trait_ref.is_implemented())
+                .iter()
+                .any(|c| c.unconditionally_true())
+        })
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,26 @@
+use formality_core::Upcast;
+use formality_types::grammar::Wcs;
+
+use crate::decls::{Decls, ImplDecl};
+
+use super::{prove, Env};
+
+/// True if the headers of `impl_a` and `impl_b` are for the same trait and
+/// may unify, i.e., some types might be covered by both. The impls'
+/// where-clauses are not taken into account, so this is a quick, conservative
+/// check; `formality_check::impls_overlap` is the full overlap check.
+pub fn impls_may_overlap(decls: impl Upcast<Decls>, impl_a: &ImplDecl, impl_b: &ImplDecl) -> bool {
+    let decls: Decls = decls.upcast();
+    let (env, vars_a) = Env::default().existential_substitution(&impl_a.binder);
+    let (env, vars_b) = env.existential_substitution(&impl_b.binder);
+    let a = impl_a.binder.instantiate_with(&vars_a).unwrap();
+    let b = impl_b.binder.instantiate_with(&vars_b).unwrap();
+    a.trait_ref.trait_id == b.trait_ref.trait_id
+        && prove(
+            &decls,
+            env,
+            (),
+            Wcs::all_eq(&a.trait_ref.parameters, &b.trait_ref.parameters),
+        )
+        .is_proven()
+}
@@ -0,0 +1,117 @@
+use std::fmt::Write;
+
+use formality_core::{Map, Upcast};
+use formality_types::grammar::{Parameter, Variable};
+
+use super::Constraints;
+
+/// The inference variables of a solution grouped by what they were unified with,
+/// for debugging unification; see [`Constraints::inference_graph`]. It is built
+/// from a solution rather than an [`Env`](crate::Env), as the environment only
+/// lists the variables and the solution holds what they are bound to.
+///
+/// FIXME: the solver does not record outlives constraints between lifetimes,
+/// so the graph only shows equalities; once it does, they belong here as edges
+/// between the classes of the lifetimes involved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InferenceGraph {
+    pub classes: Vec<EquivalenceClass>,
+}
+
+/// Variables that the solution equates with one another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EquivalenceClass {
+    /// The existential variables in the class, in the order of the environment.
+    pub variables: Vec<Variable>,
+
+    /// What the variables resolve to, or `None` if they are still unconstrained.
+    pub value: Option<Parameter>,
+}
+
+/// How the equivalence classes of two [`InferenceGraph`]s differ, found by
+/// [`InferenceGraph::diff`]. A class that gained a variable or a value shows up
+/// as removed in its old form and added in its new one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InferenceGraphDiff {
+    /// The classes of the earlier graph that the later one does not have.
+    pub removed: Vec<EquivalenceClass>,
+
+    /// The classes of the later graph that the earlier one does not have.
+    pub added: Vec<EquivalenceClass>,
+}
+
+impl Constraints {
+    /// Groups the existential variables of the environment by the value the
+    /// substitution gives them. A variable with no binding is unconstrained and
+    /// forms a class with the variables bound to it.
+    pub fn inference_graph(&self) -> InferenceGraph {
+        let mut classes: Map<Parameter, Vec<Variable>> = Map::new();
+        let mut order: Vec<Parameter> = vec![];
+        for &v in self.env().variables() {
+            if !matches!(v, Variable::ExistentialVar(_)) {
+                continue;
+            }
+            let v_param: Parameter = v.upcast();
+            let value = self.substitution().apply(&v_param);
+            let members = classes.entry(value.clone()).or_insert_with(|| {
+                order.push(value.clone());
+                vec![]
+            });
+            members.push(v);
+        }
+
+        InferenceGraph {
+            classes: order
+                .into_iter()
+                .map(|value| {
+                    let variables = classes.remove(&value).unwrap();
+                    let unconstrained =
+                        matches!(value.as_variable(), Some(Variable::ExistentialVar(_)));
+                    EquivalenceClass {
+                        variables,
+                        value: if unconstrained { None } else { Some(value) },
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl InferenceGraph {
+    /// The classes that changed from `self` to `later`, e.g. from the graph of a
+    /// solution to that of the solution after proving a further goal.
+    pub fn diff(&self, later: &InferenceGraph) -> InferenceGraphDiff {
+        let missing_from = |a: &InferenceGraph, b: &InferenceGraph| {
+            a.classes
+                .iter()
+                .filter(|class| !b.classes.contains(class))
+                .cloned()
+                .collect()
+        };
+        InferenceGraphDiff {
+            removed: missing_from(self, later),
+            added: missing_from(later, self),
+        }
+    }
+
+    /// Renders the graph in Graphviz's DOT format: one cluster per class, with
+    /// an edge from each variable to the class's value (if any).
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph inference {\n");
+        for (index, class) in self.classes.iter().enumerate() {
+            writeln!(dot, "  subgraph cluster_{index} {{").unwrap();
+            for v in &class.variables {
+                writeln!(dot, "    \"{v:?}\";").unwrap();
+            }
+            writeln!(dot, "  }}").unwrap();
+            if let Some(value) = &class.value {
+                writeln!(dot, "  \"{value:?}\" [shape=box];").unwrap();
+                for v in &class.variables {
+                    writeln!(dot, "  \"{v:?}\" -> \"{value:?}\";").unwrap();
+                }
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
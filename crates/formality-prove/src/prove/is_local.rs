@@ -4,7 +4,7 @@ use formality_types::grammar::{
 };
 
 use crate::{
-    decls::Decls,
+    decls::{Decls, Fundamentalness},
     prove::{
         combinators::for_all, env::Bias, negation::may_not_be_provable,
         prove_normalize::prove_normalize, Constraints,
@@ -109,6 +109,17 @@ judgment_fn! {
                 => Constraints::none(env))
         )
 
+        // Fundamental types do not cover their parameters: if `T` may be a
+        // downstream type, a downstream crate may implement traits for `Box<T>`
+        // or `&T` just as it may for `T`.
+        (
+            (if is_fundamental(&decls, &name))
+            (parameters.iter() => p)
+            (may_be_downstream_parameter(&decls, &env, &assumptions, p) => c)
+            --- ("fundamental rigid type")
+            (may_be_downstream_parameter(decls, env, assumptions, RigidTy { name, parameters }) => c)
+        )
+
         // If `parameter` is an alias which refers a type which may be
         // from a downstream crate, it may be normalized to that type,
         // so recurse into it.
@@ -316,7 +327,7 @@ judgment_fn! {
     }
 }
 
-fn is_fundamental(_decls: &Decls, name: &RigidName) -> bool {
+fn is_fundamental(decls: &Decls, name: &RigidName) -> bool {
     // From https://rust-lang.github.io/rfcs/2451-re-rebalancing-coherence.html:
     //
     // Fundamental Type: A type for which you cannot add a blanket impl backwards
@@ -326,12 +337,18 @@ fn is_fundamental(_decls: &Decls, name: &RigidName) -> bool {
     // used, `&T`, `&mut T`, and `Box<T>` are not considered covered.
 
     match name {
-        RigidName::AdtId(_) => false, // FIXME
+        RigidName::AdtId(a) => decls
+            .adt_decls
+            .iter()
+            .any(|d| d.id == *a && d.fundamentalness == Fundamentalness::Fundamental),
 
         RigidName::Ref(_) => true,
 
         RigidName::ScalarId(_)
         | RigidName::Tuple(_)
+        | RigidName::Array
+        | RigidName::Slice
+        | RigidName::Never
         | RigidName::FnPtr(_)
         | RigidName::FnDef(_) => false,
     }
@@ -116,7 +116,11 @@ impl Minimization {
         let Constraints {
             env: _,
             known_true,
+            overflow,
             substitution,
+            coinductive_assumptions,
+            assumptions_used,
+            assumptions_used_inductively,
         } = constraints;
         let substitution: Substitution = substitution
             .iter()
@@ -127,7 +131,11 @@ impl Minimization {
         Constraints {
             env: env_out,
             known_true,
+            overflow,
             substitution,
+            coinductive_assumptions: env2out_subst.apply(&coinductive_assumptions),
+            assumptions_used: env2out_subst.apply(&assumptions_used),
+            assumptions_used_inductively: env2out_subst.apply(&assumptions_used_inductively),
         }
     }
 }
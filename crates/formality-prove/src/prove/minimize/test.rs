@@ -1,5 +1,5 @@
 use expect_test::expect;
-use formality_core::To;
+use formality_core::{Set, To};
 use formality_macros::test;
 use formality_types::{
     grammar::{Binder, Parameter, ScalarId, Ty},
@@ -35,9 +35,13 @@ fn minimize_a() {
     let c_min = Constraints {
         env: env_min,
         known_true: true,
+        overflow: false,
         substitution: vec![(ty1, ty2.to::<Ty>()), (ty0, ScalarId::U32.to::<Ty>())]
             .into_iter()
             .collect(),
+        coinductive_assumptions: Set::new(),
+        assumptions_used: Set::new(),
+        assumptions_used_inductively: Set::new(),
     };
     let c = m.reconstitute(c_min);
 
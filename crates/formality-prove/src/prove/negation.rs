@@ -102,7 +102,11 @@ pub fn negation_via_failure<T: CoreFold<FormalityLang>>(
                 )
             } else {
                 tracing::debug!("ambiguous `negation_via_failure`, solutions: {s:?}");
-                ProvenSet::singleton(Constraints::none(env).ambiguous())
+                if s.iter().any(|constraints| constraints.overflow) {
+                    ProvenSet::singleton(Constraints::none(env).overflow())
+                } else {
+                    ProvenSet::singleton(Constraints::none(env).ambiguous())
+                }
             }
         }
 
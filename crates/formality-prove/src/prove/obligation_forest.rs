@@ -0,0 +1,174 @@
+use formality_core::{judgment::FailedJudgment, Upcast};
+use formality_types::grammar::{Wc, Wcs};
+
+use crate::decls::Decls;
+
+use super::{prove, Constraints, Env};
+
+/// Identifies an obligation registered with an [`ObligationForest`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObligationId(usize);
+
+/// Where an obligation of an [`ObligationForest`] stands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObligationState {
+    /// Not processed yet.
+    Pending,
+
+    /// Processed, but it could hold in more than one way (or only ambiguously),
+    /// so it waits for inference to learn more.
+    Stalled,
+
+    /// The obligation and all of its sub-obligations hold.
+    Proven,
+
+    /// The obligation, or one of its sub-obligations, cannot hold.
+    Failed(Box<FailedJudgment>),
+}
+
+struct Obligation {
+    goal: Wc,
+    parent: Option<ObligationId>,
+    state: ObligationState,
+}
+
+/// The obligations a type checker accumulates, solved in passes as inference
+/// progresses (after rustc's structure of the same name).
+///
+/// [`Self::process_obligations`] proves each obligation that is not yet done.
+/// One that holds unconditionally is proven. One that holds in exactly one way
+/// is proven too, and that solution's inference (e.g., `?X = u32`) is adopted
+/// for the remaining obligations. Anything else stalls it until another
+/// obligation adopts a solution. An obligation may have sub-obligations
+/// (registered with [`Self::register_child`]): it is only proven once they
+/// are, and fails if any of them fails.
+pub struct ObligationForest {
+    decls: Decls,
+    assumptions: Wcs,
+    constraints: Constraints,
+    obligations: Vec<Obligation>,
+}
+
+impl ObligationForest {
+    pub fn new(
+        decls: impl Upcast<Decls>,
+        env: impl Upcast<Env>,
+        assumptions: impl Upcast<Wcs>,
+    ) -> Self {
+        Self {
+            decls: decls.upcast(),
+            assumptions: assumptions.upcast(),
+            constraints: Constraints::none(env.upcast()),
+            obligations: vec![],
+        }
+    }
+
+    pub fn register(&mut self, goal: impl Upcast<Wc>) -> ObligationId {
+        self.push(goal.upcast(), None)
+    }
+
+    /// Registers `goal` as a sub-obligation of `parent`.
+    pub fn register_child(&mut self, parent: ObligationId, goal: impl Upcast<Wc>) -> ObligationId {
+        self.push(goal.upcast(), Some(parent))
+    }
+
+    fn push(&mut self, goal: Wc, parent: Option<ObligationId>) -> ObligationId {
+        let id = ObligationId(self.obligations.len());
+        self.obligations.push(Obligation {
+            goal,
+            parent,
+            state: ObligationState::Pending,
+        });
+        id
+    }
+
+    /// The inference adopted so far.
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    /// The goal of `id`, with the inference adopted so far applied.
+    pub fn goal(&self, id: ObligationId) -> Wc {
+        self.constraints
+            .substitution()
+            .apply(&self.obligations[id.0].goal)
+    }
+
+    /// The state of `id`, taking its sub-obligations into account.
+    pub fn state(&self, id: ObligationId) -> ObligationState {
+        let mut state = self.obligations[id.0].state.clone();
+        for child in self.children(id) {
+            state = match (state, self.state(child)) {
+                (failed @ ObligationState::Failed(_), _)
+                | (_, failed @ ObligationState::Failed(_)) => failed,
+                (ObligationState::Proven, ObligationState::Proven) => ObligationState::Proven,
+                (ObligationState::Pending, _) | (_, ObligationState::Pending) => {
+                    ObligationState::Pending
+                }
+                _ => ObligationState::Stalled,
+            };
+        }
+        state
+    }
+
+    fn children(&self, id: ObligationId) -> impl Iterator<Item = ObligationId> + '_ {
+        (0..self.obligations.len())
+            .map(ObligationId)
+            .filter(move |&child| self.obligations[child.0].parent == Some(id))
+    }
+
+    /// The obligations that are not sub-obligations, in the order registered.
+    pub fn roots(&self) -> impl Iterator<Item = ObligationId> + '_ {
+        (0..self.obligations.len())
+            .map(ObligationId)
+            .filter(|&id| self.obligations[id.0].parent.is_none())
+    }
+
+    /// Processes the pending and stalled obligations in passes until a pass
+    /// changes nothing. Returns the number of obligations that were proven or
+    /// failed.
+    pub fn process_obligations(&mut self) -> usize {
+        let mut settled = 0;
+        loop {
+            let mut progress = false;
+            for index in 0..self.obligations.len() {
+                if !matches!(
+                    self.obligations[index].state,
+                    ObligationState::Pending | ObligationState::Stalled
+                ) {
+                    continue;
+                }
+                let state = self.process(ObligationId(index));
+                if !matches!(state, ObligationState::Stalled) {
+                    progress = true;
+                    settled += 1;
+                }
+                self.obligations[index].state = state;
+            }
+            if !progress {
+                return settled;
+            }
+        }
+    }
+
+    fn process(&mut self, id: ObligationId) -> ObligationState {
+        let goal = self.goal(id);
+        let assumptions = self.constraints.substitution().apply(&self.assumptions);
+        let solutions =
+            match prove(&self.decls, self.constraints.env(), assumptions, goal).into_set() {
+                Ok(solutions) => solutions,
+                Err(e) => return ObligationState::Failed(e),
+            };
+        if solutions.iter().any(|c| c.unconditionally_true()) {
+            return ObligationState::Proven;
+        }
+        match solutions.into_iter().collect::<Vec<_>>().as_slice() {
+            [c] if c.known_true => {
+                tracing::debug!(?c, "adopting the only solution of obligation {id:?}");
+                self.constraints = self.constraints.seq(c.clone());
+                ObligationState::Proven
+            }
+            _ => ObligationState::Stalled,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use formality_core::{id, Map, ProvenSet, Upcast};
+use formality_macros::term;
+use formality_types::grammar::{Binder, Ty, Wcs};
+
+use crate::decls::Decls;
+
+use super::{prove, Constraints, Env};
+
+id!(OpaqueTyId);
+
+/// An opaque type, such as the `impl Iterator` in the return type of
+/// `fn f() -> impl Iterator`. The bounds are bound over its hidden type,
+/// e.g., `opaque F <ty X> {Iterator(X)}`.
+#[term(opaque $id $bounds)]
+pub struct OpaqueTyDecl {
+    pub id: OpaqueTyId,
+    pub bounds: Binder<Wcs>,
+}
+
+/// The hidden types inferred for opaque types while checking their defining
+/// scope, one per defining use (e.g., per return path of a function).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DefiningScope {
+    hidden_types: Map<OpaqueTyId, Vec<Ty>>,
+}
+
+impl DefiningScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a defining use of `opaque` has the hidden type `ty`.
+    pub fn register_hidden_type(&mut self, opaque: &OpaqueTyId, ty: impl Upcast<Ty>) {
+        self.hidden_types
+            .entry(opaque.clone())
+            .or_default()
+            .push(ty.upcast());
+    }
+
+    /// The hidden types registered for `opaque`, in the order they were registered.
+    pub fn hidden_types(&self, opaque: &OpaqueTyId) -> &[Ty] {
+        self.hidden_types.get(opaque).map_or(&[], |tys| &tys[..])
+    }
+}
+
+/// Proves that the hidden types registered in `scope` for `opaque` are all
+/// equal, and that the hidden type satisfies the opaque's bounds. The hidden
+/// types may contain inference variables; the solutions say how they must be
+/// bound for the defining uses to agree. Fails if no hidden type was registered.
+pub fn prove_hidden_types(
+    decls: impl Upcast<Decls>,
+    env: impl Upcast<Env>,
+    assumptions: impl Upcast<Wcs>,
+    opaque: &OpaqueTyDecl,
+    scope: &DefiningScope,
+) -> ProvenSet<Constraints> {
+    let Some((hidden_ty, others)) = scope.hidden_types(&opaque.id).split_first() else {
+        return ProvenSet::failed(
+            format!("prove_hidden_types {{ opaque: {opaque:?} }}"),
+            format!("no hidden type registered for `{:?}`", opaque.id),
+        );
+    };
+    let bounds: Wcs = opaque.bounds.instantiate_with(&[hidden_ty]).unwrap();
+    let agree = Wcs::all_eq(others, vec![hidden_ty; others.len()]);
+    prove(decls, env, assumptions, (agree, bounds))
+}
@@ -0,0 +1,119 @@
+use formality_core::{visit::CoreVisit, Downcast, ProvenSet, Upcast};
+use formality_types::grammar::{
+    ExistentialVar, Parameter, Predicate, TraitId, TraitRef, Variable, Wc, WcData, Wcs,
+};
+
+use crate::decls::Decls;
+
+use super::{
+    candidate_clauses::candidate_clauses, env::Bias, prove_after::prove_after,
+    sizedness::Sizedness, Constraints, Env,
+};
+
+/// Like [`prove`](`super::prove`), but first runs [`propagate`] and hands the
+/// simplified goal to the solver. The solutions are the same; only the amount
+/// of search needed to find them differs.
+pub fn prove_with_propagation(
+    decls: impl Upcast<Decls>,
+    env: impl Upcast<Env>,
+    assumptions: impl Upcast<Wcs>,
+    goal: impl Upcast<Wcs>,
+) -> ProvenSet<Constraints> {
+    let decls: Decls = decls.upcast();
+    let assumptions: Wcs = assumptions.upcast();
+    let goal: Wcs = goal.upcast();
+    let c = propagate(&decls, env, &assumptions, &goal);
+    prove_after(decls, c, assumptions, goal)
+}
+
+/// Cheap, deterministic propagation to run before the full solver.
+///
+/// Repeatedly looks for a trait goal that can only be proven by one impl,
+/// and whose header, when unified with the goal, binds one of the goal's
+/// existential variables to something other than a variable. Every solution
+/// must agree with that binding, so it is applied to the environment up front.
+/// The impl's where-clauses are not proven here; that is left to the solver.
+///
+/// This only fires in the simple cases where it is clearly sound: there are no
+/// assumptions, we are not in coherence mode, and the goal's trait has no
+/// built-in rules and is not implied by another trait.
+pub fn propagate(
+    decls: &Decls,
+    env: impl Upcast<Env>,
+    assumptions: &Wcs,
+    goal: &Wcs,
+) -> Constraints {
+    let mut c = Constraints::none(env);
+    if *assumptions != Wcs::t() || c.env().bias() == Bias::Completeness {
+        return c;
+    }
+
+    loop {
+        let goal = c.substitution().apply(goal);
+        if goal.size() > decls.max_size {
+            return c;
+        }
+        let Some(c1) = (&goal)
+            .into_iter()
+            .find_map(|wc| forced_binding(decls, c.env(), &wc))
+        else {
+            return c;
+        };
+        tracing::debug!(?goal, ?c1, "propagated");
+        c = c.seq(c1);
+    }
+}
+
+/// If `wc` is a trait goal with a unique candidate impl whose unifier binds an
+/// existential variable of `wc` to a non-variable, returns that unifier.
+fn forced_binding(decls: &Decls, env: &Env, wc: &Wc) -> Option<Constraints> {
+    let WcData::Predicate(Predicate::IsImplemented(trait_ref)) = wc.data() else {
+        return None;
+    };
+    if !has_only_impl_rules(decls, trait_ref) {
+        return None;
+    }
+
+    let [candidate] = &candidate_clauses(decls, env, trait_ref)[..] else {
+        return None;
+    };
+    let unifier = &candidate.unifier;
+    if !unifier.known_true {
+        return None;
+    }
+
+    let forces = trait_ref.free_variables().into_iter().any(|v| match v {
+        Variable::ExistentialVar(v) => binds_to_non_variable(unifier, v),
+        _ => false,
+    });
+    forces.then(|| unifier.clone())
+}
+
+fn binds_to_non_variable(c: &Constraints, v: ExistentialVar) -> bool {
+    let v: Parameter = v.upcast();
+    !c.substitution().apply(&v).is_a::<Variable>()
+}
+
+/// True if `trait_ref` can only be proven through impls: no built-in rule covers
+/// its trait and no trait invariant implies it.
+fn has_only_impl_rules(decls: &Decls, trait_ref: &TraitRef) -> bool {
+    let never_self = match &trait_ref.parameters[..] {
+        [Parameter::Ty(ty), ..] => ty.is_never(),
+        _ => false,
+    };
+    !never_self
+        && Sizedness::of_trait(&trait_ref.trait_id).is_none()
+        && !decls
+            .trait_invariants()
+            .iter()
+            .any(|ti| implies_trait(&ti.binder.peek().where_clause, &trait_ref.trait_id))
+}
+
+fn implies_trait(wc: &Wc, trait_id: &TraitId) -> bool {
+    match wc.data() {
+        WcData::Predicate(Predicate::IsImplemented(trait_ref)) => trait_ref.trait_id == *trait_id,
+        WcData::Predicate(_) | WcData::Relation(_) => false,
+        WcData::ForAll(binder) => implies_trait(binder.peek(), trait_id),
+        WcData::Implies(_, wc) => implies_trait(wc, trait_id),
+    }
+}
@@ -0,0 +1,142 @@
+use formality_core::{
+    visit::CoreVisit, CacheStats, Downcast, LruCache, Map, ProvenSet, Set, Upcast,
+};
+use formality_types::grammar::{ExistentialVar, Wcs};
+
+use crate::decls::Decls;
+
+use super::{prove, Constraints, Env};
+
+/// Caches the results of top-level calls to [`prove`], holding at most a fixed
+/// number of goals and evicting the least recently used one when full. This
+/// keeps memory bounded for long-running clients that issue many queries.
+///
+/// Only complete, top-level queries are cached: results computed during a
+/// proof may be provisional (e.g., while a cycle is being solved), so the
+/// judgments themselves never consult the cache.
+///
+/// Ambiguous results need more care, since learning more about the
+/// variables involved could resolve them; see [`AmbiguityPolicy`].
+pub struct ProveCache {
+    cache: LruCache<CacheKey, ProvenSet<Constraints>>,
+    ambiguity_policy: AmbiguityPolicy,
+
+    /// The cached ambiguous results, with the existential variables their
+    /// queries mention.
+    ambiguous: Map<CacheKey, Set<ExistentialVar>>,
+}
+
+type CacheKey = (Decls, Env, Wcs, Wcs);
+
+/// Whether [`ProveCache`] caches results that are ambiguous (i.e., that
+/// include constraints that are not known to be true).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AmbiguityPolicy {
+    /// Ambiguous results are never cached.
+    #[default]
+    Never,
+
+    /// Ambiguous results are cached until [`ProveCache::end_query`] is called,
+    /// so they are only reused within the current top-level query.
+    WithinQuery,
+
+    /// Ambiguous results are cached until [`ProveCache::variable_bound`] is
+    /// called for one of the existential variables their query mentions.
+    UntilBound,
+}
+
+impl ProveCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+            ambiguity_policy: AmbiguityPolicy::default(),
+            ambiguous: Map::new(),
+        }
+    }
+
+    pub fn with_ambiguity_policy(self, ambiguity_policy: AmbiguityPolicy) -> Self {
+        Self {
+            ambiguity_policy,
+            ..self
+        }
+    }
+
+    /// Like [`prove`], but reuses the result of an identical earlier query if it is still cached.
+    pub fn prove(
+        &mut self,
+        decls: impl Upcast<Decls>,
+        env: impl Upcast<Env>,
+        assumptions: impl Upcast<Wcs>,
+        goal: impl Upcast<Wcs>,
+    ) -> ProvenSet<Constraints> {
+        let key: CacheKey = (
+            decls.upcast(),
+            env.upcast(),
+            assumptions.upcast(),
+            goal.upcast(),
+        );
+        if let Some(result) = self.cache.get(&key) {
+            return result;
+        }
+
+        let (decls, env, assumptions, goal) = &key;
+        let result = prove(decls, env, assumptions, goal);
+        let ambiguous = result.iter().any(|c| !c.known_true);
+        if ambiguous && self.ambiguity_policy == AmbiguityPolicy::Never {
+            return result;
+        }
+        if ambiguous {
+            let vars = (assumptions, goal)
+                .free_variables()
+                .into_iter()
+                .filter_map(|v| v.downcast())
+                .collect();
+            self.ambiguous.insert(key.clone(), vars);
+        }
+        self.cache.insert(key, result.clone());
+
+        // Forget ambiguous entries that the insertion evicted.
+        let cache = &self.cache;
+        self.ambiguous.retain(|key, _| cache.contains(key));
+
+        result
+    }
+
+    /// Signals the end of the current top-level query. Under
+    /// [`AmbiguityPolicy::WithinQuery`], this drops the cached ambiguous results.
+    pub fn end_query(&mut self) {
+        if self.ambiguity_policy == AmbiguityPolicy::WithinQuery {
+            self.invalidate_ambiguous(|_| true);
+        }
+    }
+
+    /// Signals that `var` has been bound by the client, so ambiguous results
+    /// for queries mentioning it may be stale and are dropped.
+    pub fn variable_bound(&mut self, var: ExistentialVar) {
+        self.invalidate_ambiguous(|vars| vars.contains(&var));
+    }
+
+    fn invalidate_ambiguous(&mut self, stale: impl Fn(&Set<ExistentialVar>) -> bool) {
+        let cache = &mut self.cache;
+        self.ambiguous.retain(|key, vars| {
+            if stale(vars) {
+                cache.remove(key);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+}
@@ -2,8 +2,8 @@ use formality_core::visit::CoreVisit;
 use formality_core::{judgment_fn, Downcast, ProvenSet, Upcast};
 use formality_core::{Deduplicate, Upcasted};
 use formality_types::grammar::{
-    AliasTy, ExistentialVar, Parameter, Relation, RigidTy, Substitution, TyData, UniversalVar,
-    Variable, Wcs,
+    AliasTy, ConstData, ExistentialVar, Parameter, Relation, RigidTy, Substitution, TyData,
+    UniversalVar, Variable, Wcs,
 };
 
 use crate::{
@@ -58,6 +58,28 @@ judgment_fn! {
             (prove_eq(decls, env, assumptions, TyData::AliasTy(a), TyData::AliasTy(b)) => env_c)
         )
 
+        (
+            (if has_unresolved_self_ty(&a))!
+            (if !prove_normalize(&decls, &env, &assumptions, &a).is_proven())
+            ----------------------------- ("alias-unresolved")
+            (prove_eq(decls, env, assumptions, TyData::AliasTy(a), _b) => Constraints::none(env).ambiguous())
+        )
+
+        (
+            (let simplified = a.simplify())
+            (if simplified != a)!
+            (prove_eq(decls, env, assumptions, simplified, b) => c)
+            ----------------------------- ("const-simplify")
+            (prove_eq(decls, env, assumptions, Parameter::Const(a), b) => c)
+        )
+
+        (
+            (if a_op == b_op)!
+            (prove(decls, env, assumptions, Wcs::all_eq(vec![a_l, a_r], vec![b_l, b_r])) => c)
+            ----------------------------- ("const-op")
+            (prove_eq(decls, env, assumptions, ConstData::Op(a_l, a_op, a_r), ConstData::Op(b_l, b_op, b_r)) => c)
+        )
+
         (
             (prove_existential_var_eq(decls, env, assumptions, v, r) => c)
             ----------------------------- ("existential")
@@ -177,3 +199,14 @@ fn equate_variable(
 
     prove_after(decls, constraints, assumptions, goals)
 }
+
+/// True if `alias` is a projection like `<?T as Foo>::Assoc` whose self type
+/// is an inference variable. Which impl would normalize it is not yet known,
+/// so if nothing normalizes it yet, an equality involving it is ambiguous
+/// rather than false.
+pub(crate) fn has_unresolved_self_ty(alias: &AliasTy) -> bool {
+    matches!(
+        alias.parameters.first().and_then(|p| p.as_variable()),
+        Some(Variable::ExistentialVar(_))
+    )
+}
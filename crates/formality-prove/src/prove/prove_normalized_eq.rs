@@ -0,0 +1,130 @@
+use formality_core::{judgment_fn, Downcast, ProvenSet, Upcast};
+use formality_types::grammar::{AliasTy, Parameter, Ty, TyData, Variable, Wcs};
+
+use crate::{
+    decls::Decls,
+    prove::{prove, prove_eq::eq, prove_normalize::prove_normalize},
+};
+
+use super::{constraints::Constraints, env::Env};
+
+judgment_fn! {
+    /// Proves `a = b` by normalizing both types and then comparing them structurally.
+    ///
+    /// [`prove_eq`](`super::prove_eq::prove_eq`) interleaves normalization with
+    /// unification, so when it fails it is hard to tell why. Here the two phases
+    /// are separate: a failure names the point at which the normalized types
+    /// differ. An alias that cannot be normalized is compared as a rigid type,
+    /// equal to the same alias with equal parameters.
+    pub fn prove_normalized_eq(
+        decls: Decls,
+        env: Env,
+        assumptions: Wcs,
+        a: Ty,
+        b: Ty,
+    ) => Constraints {
+        debug(a, b, assumptions, env, decls)
+
+        trivial(a == b => Constraints::none(env))
+
+        (
+            (normalize_ty(&decls, &env, &assumptions, a) => (c_a, a))
+            (let b = c_a.substitution().apply(&b))
+            (normalize_ty(&decls, c_a.env(), &assumptions, b) => (c_b, b))
+            (let c = c_a.seq(c_b))
+            (let a = c.substitution().apply(&a))
+            (prove_structurally_eq(&decls, c.env(), &assumptions, a, b) => c_eq)
+            ----------------------------- ("normalize-then-compare")
+            (prove_normalized_eq(decls, env, assumptions, a, b) => c.seq(c_eq))
+        )
+    }
+}
+
+judgment_fn! {
+    /// Normalizes `ty` until it is no longer an alias, or is one that cannot be normalized.
+    fn normalize_ty(
+        decls: Decls,
+        env: Env,
+        assumptions: Wcs,
+        ty: Ty,
+    ) => (Constraints, Ty) {
+        debug(ty, assumptions, env, decls)
+
+        (
+            (if let None = ty.downcast::<AliasTy>())!
+            ----------------------------- ("not-alias")
+            (normalize_ty(_decls, env, _assumptions, ty) => (Constraints::none(env), ty))
+        )
+
+        (
+            (normalize_alias(&decls, &env, &assumptions, &alias) => (c, p))
+            (if let Some(ty) = p.downcast::<Ty>())
+            (normalize_ty(&decls, c.env(), &assumptions, ty) => (c1, ty))
+            ----------------------------- ("alias")
+            (normalize_ty(decls, env, assumptions, TyData::AliasTy(alias)) => (c.seq(c1), ty))
+        )
+
+        (
+            (if !normalize_alias(&decls, &env, &assumptions, &alias).is_proven())
+            (let ty = Upcast::<Ty>::upcast(alias))
+            ----------------------------- ("rigid-alias")
+            (normalize_ty(decls, env, assumptions, TyData::AliasTy(alias)) => (Constraints::none(env), ty))
+        )
+    }
+}
+
+/// Normalizes `alias` one step. Failing to do so is reported as such, rather
+/// than with the (often cyclic) reasons that each normalization rule failed.
+fn normalize_alias(
+    decls: &Decls,
+    env: &Env,
+    assumptions: &Wcs,
+    alias: &AliasTy,
+) -> ProvenSet<(Constraints, Parameter)> {
+    let result = prove_normalize(decls, env, assumptions, alias);
+    if result.is_proven() {
+        result
+    } else {
+        ProvenSet::failed(
+            format!("normalize_alias {{ alias: {alias:?} }}"),
+            format!("could not normalize `{alias:?}`"),
+        )
+    }
+}
+
+judgment_fn! {
+    /// Compares two normalized types: rigid types (including aliases that could
+    /// not be normalized) must have the same name and equal parameters, and variables are related through the usual `prove`.
+    fn prove_structurally_eq(
+        decls: Decls,
+        env: Env,
+        assumptions: Wcs,
+        a: Ty,
+        b: Ty,
+    ) => Constraints {
+        debug(a, b, assumptions, env, decls)
+
+        trivial(a == b => Constraints::none(env))
+
+        (
+            (if a.name == b.name)
+            (prove(decls, env, assumptions, Wcs::all_eq(&a.parameters, &b.parameters)) => c)
+            ----------------------------- ("rigid")
+            (prove_structurally_eq(decls, env, assumptions, TyData::RigidTy(a), TyData::RigidTy(b)) => c)
+        )
+
+        (
+            (if a.name == b.name)
+            (prove(decls, env, assumptions, Wcs::all_eq(&a.parameters, &b.parameters)) => c)
+            ----------------------------- ("rigid-alias")
+            (prove_structurally_eq(decls, env, assumptions, TyData::AliasTy(a), TyData::AliasTy(b)) => c)
+        )
+
+        (
+            (if a.is_a::<Variable>() || b.is_a::<Variable>())!
+            (prove(decls, env, assumptions, eq(&a, &b)) => c)
+            ----------------------------- ("variable")
+            (prove_structurally_eq(decls, env, assumptions, a, b) => c)
+        )
+    }
+}
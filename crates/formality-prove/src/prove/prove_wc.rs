@@ -1,5 +1,7 @@
-use formality_core::judgment_fn;
-use formality_types::grammar::{Predicate, Relation, Wc, WcData, Wcs};
+use formality_core::{judgment_fn, visit::CoreVisit, Upcast};
+use formality_types::grammar::{
+    Binder, Parameter, Predicate, Relation, TraitRef, Variable, Wc, WcData, Wcs,
+};
 
 use crate::{
     decls::Decls,
@@ -11,6 +13,7 @@ use crate::{
         prove_eq::prove_eq,
         prove_via::prove_via,
         prove_wf::prove_wf,
+        sizedness::builtin_sizedness_goals,
     },
 };
 
@@ -34,16 +37,19 @@ judgment_fn! {
         )
 
         (
+            (let binder = Binder::new(hypothesis_existentials(&p1), (p1.clone(), p2.clone())))
+            (let (env, subst) = env.universal_substitution(&binder))
+            (let (p1, p2) = binder.instantiate_with(&subst).unwrap())
             (prove_wc(decls, env, (assumptions, p1), p2) => c)
             --- ("implies")
-            (prove_wc(decls, env, assumptions, WcData::Implies(p1, p2)) => c)
+            (prove_wc(decls, env, assumptions, WcData::Implies(p1, p2)) => c.pop_subst(&subst))
         )
 
         (
             (&assumptions => a)!
-            (prove_via(&decls, &env, &assumptions, a, &goal) => c)
+            (prove_via(&decls, &env, &assumptions, &a, &goal) => c)
             ----------------------------- ("assumption")
-            (prove_wc(decls, env, assumptions, WcData::Predicate(goal)) => c)
+            (prove_wc(decls, env, assumptions, WcData::Predicate(goal)) => c.with_assumption_used(&a))
         )
         (
             (&assumptions => a)!
@@ -52,17 +58,38 @@ judgment_fn! {
             (prove_wc(decls, env, assumptions, WcData::Relation(goal)) => c)
         )
 
+        // FIXME: caching an impl's elaborated where-clauses across attempts of
+        // this rule was requested but is not done. The rule does not elaborate
+        // them: they are proven as goals, and their implied bounds only come in
+        // through `decls.trait_invariants()` when proving from assumptions, so
+        // there is no per-impl elaboration to reuse.
         (
             (decls.impl_decls(&trait_ref.trait_id) => i)!
             (let (env, subst) = env.existential_substitution(&i.binder))
             (let i = i.binder.instantiate_with(&subst).unwrap())
             (let t = decls.trait_decl(&i.trait_ref.trait_id).binder.instantiate_with(&i.trait_ref.parameters).unwrap())
-            (let co_assumptions = (&assumptions, &trait_ref))
-            (prove(&decls, env, co_assumptions, Wcs::all_eq(&trait_ref.parameters, &i.trait_ref.parameters)) => c)
-            (prove_after(&decls, c, co_assumptions, &i.where_clause) => c)
+            (let co_assumptions = co_assumptions(&decls, &assumptions, &trait_ref))
+            (prove(&decls, env, &co_assumptions, Wcs::all_eq(&trait_ref.parameters, &i.trait_ref.parameters)) => c)
+            (prove_after(&decls, c, &co_assumptions, &i.where_clause) => c)
             (prove_after(&decls, c, &assumptions, &t.where_clause) => c)
+            (if !c.closes_inductive_cycle(&assumptions, &trait_ref))
             ----------------------------- ("positive impl")
-            (prove_wc(decls, env, assumptions, Predicate::IsImplemented(trait_ref)) => c.pop_subst(&subst))
+            (prove_wc(decls, env, assumptions, Predicate::IsImplemented(trait_ref)) => close_cycle(&decls, c, &assumptions, &trait_ref).pop_subst(&subst))
+        )
+
+        (
+            (if is_never(&trait_ref.parameters[0]))!
+            (let t = decls.trait_decl(&trait_ref.trait_id).binder.instantiate_with(&trait_ref.parameters).unwrap())
+            (prove(&decls, env, &assumptions, &t.where_clause) => c)
+            ----------------------------- ("never type")
+            (prove_wc(decls, env, assumptions, Predicate::IsImplemented(trait_ref)) => c)
+        )
+
+        (
+            (if let Some(goals) = builtin_sizedness_goals(&decls, &trait_ref))!
+            (prove(&decls, env, &assumptions, goals) => c)
+            ----------------------------- ("built-in sizedness")
+            (prove_wc(decls, env, assumptions, Predicate::IsImplemented(trait_ref)) => c)
         )
 
         (
@@ -104,6 +131,18 @@ judgment_fn! {
             (prove_wc(decls, env, assumptions, Relation::Equals(a, b)) => c)
         )
 
+        (
+            (prove_eq(decls, env, assumptions, a, b) => c)
+            ----------------------------- ("sub via eq")
+            (prove_wc(decls, env, assumptions, Relation::Sub(a, b)) => c)
+        )
+
+        (
+            (if is_never(&a))!
+            ----------------------------- ("sub never")
+            (prove_wc(_decls, env, _assumptions, Relation::Sub(a, _b)) => Constraints::none(env))
+        )
+
         (
             (let t = decls.trait_decl(&trait_ref.trait_id))
             (let t = t.binder.instantiate_with(&trait_ref.parameters).unwrap())
@@ -133,3 +172,53 @@ judgment_fn! {
         )
     }
 }
+
+/// The inference variables mentioned by the hypotheses of an implication.
+/// We cannot assume something about a variable whose value is not yet known,
+/// so the implication is proven with these replaced by placeholders, i.e.,
+/// for every value they might take.
+fn hypothesis_existentials(hypotheses: &Wcs) -> Vec<Variable> {
+    let mut vars: Vec<Variable> = hypotheses
+        .free_variables()
+        .into_iter()
+        .filter(|v| matches!(v, Variable::ExistentialVar(_)))
+        .collect();
+    vars.sort();
+    vars.dedup();
+    vars
+}
+
+/// True if `p` is the never type `!`, which is a subtype of, and implements,
+/// everything. A variable that might later be inferred to `!` does not count.
+fn is_never(p: &Parameter) -> bool {
+    match p {
+        Parameter::Ty(ty) => ty.is_never(),
+        Parameter::Lt(_) | Parameter::Const(_) => false,
+    }
+}
+
+/// The assumptions under which the impl's where-clauses are proven: for a
+/// coinductive trait these include `trait_ref` itself, so that a cycle back
+/// to it holds.
+fn co_assumptions(decls: &Decls, assumptions: &Wcs, trait_ref: &TraitRef) -> Wcs {
+    if decls.is_coinductive(&trait_ref.trait_id) {
+        (assumptions, trait_ref).upcast()
+    } else {
+        assumptions.clone()
+    }
+}
+
+/// Records how a proof of `trait_ref` relied on cycles; see [`Constraints::with_coinduction`]
+/// and [`Constraints::with_induction`].
+fn close_cycle(
+    decls: &Decls,
+    c: Constraints,
+    assumptions: &Wcs,
+    trait_ref: &TraitRef,
+) -> Constraints {
+    if decls.is_coinductive(&trait_ref.trait_id) {
+        c.with_coinduction(assumptions, trait_ref)
+    } else {
+        c.with_induction()
+    }
+}
@@ -1,6 +1,7 @@
-use formality_core::{judgment_fn, ProvenSet};
+use formality_core::{judgment_fn, ProvenSet, Upcast};
 use formality_types::grammar::{
-    AliasName, AliasTy, ConstData, Parameter, Parameters, RigidName, RigidTy, UniversalVar, Wcs,
+    AliasName, AliasTy, AssociatedTyName, Const, ConstData, Parameter, ParameterKind, Parameters,
+    Predicate, Relation, RigidName, RigidTy, ScalarId, TraitId, Ty, UniversalVar, Wc, Wcs,
 };
 
 use crate::{
@@ -42,6 +43,27 @@ judgment_fn! {
             (prove_wf(decls, env, assumptions, RigidTy { name: RigidName::ScalarId(_), parameters }) => c)
         )
 
+        (
+            --- ("never")
+            (prove_wf(_decls, env, _assumptions, RigidTy { name: RigidName::Never, parameters: _ }) => Constraints::none(env))
+        )
+
+        (
+            (for_all(&decls, &env, &assumptions, &parameters, &prove_wf) => c)
+            (if let [Parameter::Ty(elem), Parameter::Const(len)] = &parameters[..])
+            (prove_after(&decls, c, &assumptions, array_wf_goals(&decls, elem, len)) => c)
+            --- ("arrays")
+            (prove_wf(decls, env, assumptions, RigidTy { name: RigidName::Array, parameters }) => c)
+        )
+
+        (
+            (for_all(&decls, &env, &assumptions, &parameters, &prove_wf) => c)
+            (if let [Parameter::Ty(elem)] = &parameters[..])
+            (prove_after(&decls, c, &assumptions, element_sized_goals(&decls, elem)) => c)
+            --- ("slices")
+            (prove_wf(decls, env, assumptions, RigidTy { name: RigidName::Slice, parameters }) => c)
+        )
+
         (
             (for_all(&decls, &env, &assumptions, &parameters, &prove_wf) => c)
             (let t = decls.adt_decl(&adt_id))
@@ -58,20 +80,89 @@ judgment_fn! {
         )
 
         (
-            (prove_alias_wf(&decls, &env, &assumptions, name, parameters) => c)
+            (for_all(&decls, &env, &assumptions, &vec![l, r], &prove_wf) => c)
+            --- ("constant expressions")
+            (prove_wf(decls, env, assumptions, ConstData::Op(l, _, r)) => c)
+        )
+
+        (
+            (prove_alias_wf(&decls, &env, &assumptions, &name, &parameters) => c)
+            (prove_after(&decls, c, &assumptions, alias_implied_bounds(&name, &parameters)) => c)
             --- ("aliases")
             (prove_wf(decls, env, assumptions, AliasTy { name, parameters }) => c)
         )
     }
 }
 
+judgment_fn! {
+    /// Proves that a repeat expression `[x; N]` may produce a value of the array
+    /// type `array_ty`: the array type must be well-formed and, since `x` is
+    /// copied `N` times, its element type must be `Copy`.
+    ///
+    /// FIXME: rustc also accepts a non-`Copy` element when `x` is a constant,
+    /// but the operand is not visible at this level.
+    pub fn prove_array_repeat(
+        decls: Decls,
+        env: Env,
+        assumptions: Wcs,
+        array_ty: Ty,
+    ) => Constraints {
+        debug(array_ty, assumptions, env, decls)
+
+        (
+            (if let [Parameter::Ty(elem), Parameter::Const(_)] = &parameters[..])
+            (let array_ty = RigidTy { name: RigidName::Array, parameters: parameters.clone() })
+            (prove_wf(&decls, &env, &assumptions, array_ty) => c)
+            (prove_after(&decls, c, &assumptions, TraitId::copy().with(elem, Vec::<Parameter>::new())) => c)
+            --- ("repeat")
+            (prove_array_repeat(decls, env, assumptions, RigidTy { name: RigidName::Array, parameters }) => c)
+        )
+    }
+}
+
+/// The goals that make `[elem; len]` well-formed beyond `elem` and `len` being
+/// well-formed themselves: the length must be a `usize` constant and the element
+/// must be `Sized`.
+fn array_wf_goals(decls: &Decls, elem: &Ty, len: &Const) -> Wcs {
+    let len_goal: Wc = Predicate::ConstHasType(len.clone(), ScalarId::Usize.upcast()).upcast();
+    std::iter::once(len_goal)
+        .chain(element_sized_goals(decls, elem))
+        .collect()
+}
+
+/// The elements of arrays and slices must be `Sized`. As with the implicit
+/// `Sized` bounds on type parameters, this only applies when the program
+/// declares a `Sized` trait.
+fn element_sized_goals(decls: &Decls, elem: &Ty) -> Wcs {
+    if !decls.has_trait_decl(&TraitId::sized()) {
+        return Wcs::t();
+    }
+    let goal: Wc = TraitId::sized()
+        .with(elem, Vec::<Parameter>::new())
+        .upcast();
+    goal.upcast()
+}
+
 pub fn prove_alias_wf(
     decls: &Decls,
     env: &Env,
     assumptions: &Wcs,
-    _name: AliasName,
-    parameters: Parameters,
+    _name: &AliasName,
+    parameters: &Parameters,
 ) -> ProvenSet<Constraints> {
     // FIXME: verify self type implements trait
-    for_all(decls, env, assumptions, &parameters, &prove_wf)
+    for_all(decls, env, assumptions, parameters, &prove_wf)
+}
+
+/// The bounds implied by a lifetime-generic associated type: `<T as Foo>::Ref<'x>`
+/// is only well-formed if `T: 'x`, as though `type Ref<'a>` had been declared
+/// with `where Self: 'a`.
+fn alias_implied_bounds(name: &AliasName, parameters: &Parameters) -> Wcs {
+    let AliasName::AssociatedTyId(AssociatedTyName { item_arity, .. }) = name;
+    let self_ty = &parameters[0];
+    parameters[parameters.len() - item_arity..]
+        .iter()
+        .filter(|p| p.kind() == ParameterKind::Lt)
+        .map(|lt| Relation::outlives(self_ty, lt).upcast())
+        .collect()
 }
@@ -0,0 +1,97 @@
+//! Built-in rules for the sizedness hierarchy `Sized: MetaSized: Pointee`.
+//!
+//! This models the extended sizedness traits as an experiment. None of it
+//! applies unless the program declares `MetaSized`; programs that only declare
+//! `Sized` keep relying on their own impls, as before.
+
+use formality_core::Upcast;
+use formality_types::grammar::{Parameter, RigidName, RigidTy, TraitId, TraitRef, TyData, Wc, Wcs};
+
+use crate::decls::Decls;
+
+/// A level of the sizedness hierarchy. Each level implies the ones below it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sizedness {
+    /// Every type.
+    Pointee,
+
+    /// Types whose size can be computed from their pointer metadata, like `[T]`.
+    MetaSized,
+
+    /// Types whose size is known statically.
+    Sized,
+}
+
+impl Sizedness {
+    pub fn of_trait(trait_id: &TraitId) -> Option<Sizedness> {
+        [Sizedness::Pointee, Sizedness::MetaSized, Sizedness::Sized]
+            .into_iter()
+            .find(|level| level.trait_id() == *trait_id)
+    }
+
+    pub fn trait_id(self) -> TraitId {
+        match self {
+            Sizedness::Pointee => TraitId::pointee(),
+            Sizedness::MetaSized => TraitId::meta_sized(),
+            Sizedness::Sized => TraitId::sized(),
+        }
+    }
+}
+
+/// If `trait_ref` is a sizedness trait and a built-in rule covers its self type,
+/// returns the goals under which it holds. Returns `None` when no built-in rule
+/// applies, in which case the goal may still be proven from impls or assumptions.
+pub fn builtin_sizedness_goals(decls: &Decls, trait_ref: &TraitRef) -> Option<Wcs> {
+    let level = Sizedness::of_trait(&trait_ref.trait_id)?;
+    if !decls.has_trait_decl(&TraitId::meta_sized()) || !decls.has_trait_decl(&trait_ref.trait_id) {
+        return None;
+    }
+    let [Parameter::Ty(self_ty)] = &trait_ref.parameters[..] else {
+        return None;
+    };
+
+    if level == Sizedness::Pointee {
+        return match self_ty.data() {
+            TyData::Variable(v) if !v.is_universal() => None,
+            _ => Some(Wcs::t()),
+        };
+    }
+
+    let TyData::RigidTy(RigidTy { name, parameters }) = self_ty.data() else {
+        return None;
+    };
+    match name {
+        RigidName::ScalarId(_)
+        | RigidName::Ref(_)
+        | RigidName::Never
+        | RigidName::FnPtr(_)
+        | RigidName::FnDef(_) => Some(Wcs::t()),
+
+        // Every element but the last must be sized; the last decides the level.
+        RigidName::Tuple(_) => {
+            let Some((last, init)) = parameters.split_last() else {
+                return Some(Wcs::t());
+            };
+            let goals: Vec<Wc> = init
+                .iter()
+                .map(|p| sizedness_goal(Sizedness::Sized, p))
+                .chain(Some(sizedness_goal(level, last)))
+                .collect();
+            Some(goals.into_iter().collect())
+        }
+
+        RigidName::Array => Some(sizedness_goal(Sizedness::Sized, &parameters[0]).upcast()),
+
+        RigidName::Slice if level <= Sizedness::MetaSized => {
+            Some(sizedness_goal(Sizedness::Sized, &parameters[0]).upcast())
+        }
+        RigidName::Slice => None,
+
+        // FIXME: an ADT is as sized as its last field, which the decls do not record.
+        RigidName::AdtId(_) => None,
+    }
+}
+
+fn sizedness_goal(level: Sizedness, ty: &Parameter) -> Wc {
+    TraitRef::new(level.trait_id(), vec![ty.clone()]).upcast()
+}
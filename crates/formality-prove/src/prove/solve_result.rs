@@ -0,0 +1,74 @@
+use formality_core::{
+    judgment::{FailedJudgment, RuleFailureCause},
+    ProvenSet,
+};
+
+use super::Constraints;
+
+/// What the result of [`prove`](`super::prove`) says about its goal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SolveResult {
+    /// Some solution is known to hold.
+    Proven,
+
+    /// There are solutions, but none is known to hold.
+    Ambiguous,
+
+    /// The solver gave up because a recursion, iteration, or size limit was
+    /// reached or the fuel installed with [`with_fuel`](formality_core::judgment::with_fuel)
+    /// ran out. This includes solutions that are ambiguous only because part
+    /// of the goal overflowed (see [`Constraints::overflow`]).
+    Overflow,
+
+    /// There is no solution.
+    Disproven,
+}
+
+/// A three-valued truth value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Trivalent {
+    True,
+    False,
+    Unknown,
+}
+
+impl SolveResult {
+    pub fn of(result: &ProvenSet<Constraints>) -> Self {
+        match result.failure() {
+            Some(failure) if mentions_overflow(failure) => SolveResult::Overflow,
+            Some(_) => SolveResult::Disproven,
+            None if result.iter().any(|c| c.known_true) => SolveResult::Proven,
+            None if result.iter().any(|c| c.overflow) => SolveResult::Overflow,
+            None => SolveResult::Ambiguous,
+        }
+    }
+
+    /// Whether the goal holds: only a proof or a disproof is conclusive.
+    pub fn truth(&self) -> Trivalent {
+        match self {
+            SolveResult::Proven => Trivalent::True,
+            SolveResult::Disproven => Trivalent::False,
+            SolveResult::Ambiguous | SolveResult::Overflow => Trivalent::Unknown,
+        }
+    }
+
+    pub fn is_definitely_true(&self) -> bool {
+        self.truth() == Trivalent::True
+    }
+
+    pub fn is_definitely_false(&self) -> bool {
+        self.truth() == Trivalent::False
+    }
+}
+
+/// True if some rule of `failure` (or of a judgment nested within it) failed
+/// because of a recursion or iteration limit, in which case the failure is not conclusive.
+fn mentions_overflow(failure: &FailedJudgment) -> bool {
+    failure.failed_rules.iter().any(|rule| match &rule.cause {
+        RuleFailureCause::Overflow { .. }
+        | RuleFailureCause::OutOfFuel { .. }
+        | RuleFailureCause::IterationLimit { .. } => true,
+        RuleFailureCause::FailedJudgment(judgment) => mentions_overflow(judgment),
+        _ => false,
+    })
+}
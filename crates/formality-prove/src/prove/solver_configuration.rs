@@ -0,0 +1,35 @@
+use std::cell::Cell;
+
+use formality_core::scoped::with_scoped;
+
+/// How [`prove`](super::prove) searches for solutions, see [`with_solver_configuration`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SolverConfiguration {
+    /// Plain coinductive SLD resolution: every occurrence of a subgoal is
+    /// solved anew, even if the same subgoal was solved earlier in the query.
+    #[default]
+    Cosld,
+
+    /// Like `Cosld`, but the final results of subgoals are memoized for the
+    /// rest of the top-level query and reused when a subgoal comes up again.
+    /// This is not tabling: results that are not final (they depend on a goal
+    /// still being solved, or they overflowed) are not remembered, so subgoals
+    /// within a cycle are still solved as with `Cosld`.
+    Memoized,
+}
+
+thread_local! {
+    static SOLVER_CONFIGURATION: Cell<SolverConfiguration> = const { Cell::new(SolverConfiguration::Cosld) };
+}
+
+/// Runs `op` with `config` used by every [`prove`](super::prove) on this
+/// thread, restoring the previous configuration afterwards.
+pub fn with_solver_configuration<R>(config: SolverConfiguration, op: impl FnOnce() -> R) -> R {
+    with_scoped(&SOLVER_CONFIGURATION, config, op)
+}
+
+/// The configuration installed by the innermost [`with_solver_configuration`],
+/// or the default if there is none.
+pub fn solver_configuration() -> SolverConfiguration {
+    SOLVER_CONFIGURATION.with(|c| c.get())
+}
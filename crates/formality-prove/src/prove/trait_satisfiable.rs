@@ -0,0 +1,34 @@
+use formality_core::judgment_fn;
+use formality_types::grammar::TraitId;
+
+use crate::{
+    decls::{Decls, TraitDecl, TraitDeclBoundData},
+    prove::prove,
+};
+
+use super::env::Env;
+
+judgment_fn! {
+    /// Holds if some instantiation of the generics of `trait_id` (including
+    /// `Self`) satisfies the trait's where-clauses. A trait for which this fails
+    /// can never be implemented, which is usually a mistake.
+    ///
+    /// This is advisory, like a lint: some traits are uninhabited on purpose,
+    /// so nothing in the checker rejects a trait because of it.
+    pub fn trait_satisfiable(
+        decls: Decls,
+        trait_id: TraitId,
+    ) => () {
+        debug(trait_id, decls)
+
+        (
+            (if decls.has_trait_decl(&trait_id))
+            (let TraitDecl { binder, .. } = decls.trait_decl(&trait_id))
+            (let (env, subst) = Env::default().existential_substitution(binder))
+            (let TraitDeclBoundData { where_clause } = binder.instantiate_with(&subst).unwrap())
+            (prove(&decls, env, (), where_clause) => _c)
+            --- ("satisfiable")
+            (trait_satisfiable(decls, trait_id) => ())
+        )
+    }
+}
@@ -0,0 +1,144 @@
+use formality_core::Upcast;
+use formality_types::grammar::{Parameter, Predicate, Relation, Wc, WcData, Wcs};
+
+use crate::decls::{Decls, ImplDecl};
+
+use super::Env;
+
+/// A certificate that `goal` holds: the rule applied in `step`, together with
+/// proofs of the goals that rule requires. Unlike a [`Constraints`](super::Constraints)
+/// solution, a proof tree can be checked with [`verify_proof`] without any search.
+///
+/// Only the basic rules are covered; goals needing others (normalization,
+/// well-formedness, coinduction, ...) cannot be certified yet.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProofTree {
+    pub goal: Wc,
+    pub step: ProofStep,
+    pub premises: Vec<ProofTree>,
+}
+
+/// The rule used at one node of a [`ProofTree`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProofStep {
+    /// The goal is one of the assumptions. There are no premises.
+    Assumption,
+
+    /// The goal is `a = a`. There are no premises.
+    Reflexive,
+
+    /// The goal is a trait goal implemented by `impl_decl` with its generics
+    /// instantiated as `args`. The premises prove the impl's where-clauses
+    /// and those of the trait.
+    Impl {
+        impl_decl: ImplDecl,
+        args: Vec<Parameter>,
+    },
+}
+
+/// Why [`verify_proof`] rejected a proof tree, naming the goal whose step is wrong.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    /// The goal refers to variables not in the environment.
+    UnboundVariables { goal: Wc },
+
+    /// The step claims an assumption that was not made.
+    NotAnAssumption { goal: Wc },
+
+    /// The step claims reflexivity for a goal that is not `a = a`.
+    NotReflexive { goal: Wc },
+
+    /// The step names an impl that does not exist or does not implement the goal.
+    ImplDoesNotApply { goal: Wc, impl_decl: ImplDecl },
+
+    /// The goal names a trait that is not declared, or gives it parameters
+    /// that do not match its declaration.
+    IllFormedTraitRef { goal: Wc },
+
+    /// The premises do not prove the goals that the step requires.
+    WrongPremises { goal: Wc, expected: Wcs, found: Wcs },
+}
+
+/// Checks that `tree` proves its goal under `assumptions` given `decls`: that
+/// each step is an instance of its rule and the premises are proofs of exactly
+/// the goals that rule requires. The tree may have been produced any way at all.
+pub fn verify_proof(
+    decls: impl Upcast<Decls>,
+    env: impl Upcast<Env>,
+    assumptions: impl Upcast<Wcs>,
+    tree: &ProofTree,
+) -> Result<(), ProofError> {
+    let decls: Decls = decls.upcast();
+    let env: Env = env.upcast();
+    let assumptions: Wcs = assumptions.upcast();
+    verify(&decls, &env, &assumptions, tree)
+}
+
+fn verify(decls: &Decls, env: &Env, assumptions: &Wcs, tree: &ProofTree) -> Result<(), ProofError> {
+    let ProofTree {
+        goal,
+        step,
+        premises,
+    } = tree;
+    if !env.encloses(goal) {
+        return Err(ProofError::UnboundVariables { goal: goal.clone() });
+    }
+
+    let expected: Wcs = match step {
+        ProofStep::Assumption => {
+            if !assumptions.into_iter().any(|a| a == *goal) {
+                return Err(ProofError::NotAnAssumption { goal: goal.clone() });
+            }
+            Wcs::t()
+        }
+
+        ProofStep::Reflexive => match goal.data() {
+            WcData::Relation(Relation::Equals(a, b)) if a == b => Wcs::t(),
+            _ => return Err(ProofError::NotReflexive { goal: goal.clone() }),
+        },
+
+        ProofStep::Impl { impl_decl, args } => {
+            let does_not_apply = || ProofError::ImplDoesNotApply {
+                goal: goal.clone(),
+                impl_decl: impl_decl.clone(),
+            };
+            let WcData::Predicate(Predicate::IsImplemented(trait_ref)) = goal.data() else {
+                return Err(does_not_apply());
+            };
+            if !decls.impl_decls.contains(impl_decl) || !env.encloses(args) {
+                return Err(does_not_apply());
+            }
+            let Ok(i) = impl_decl.binder.instantiate_with(args) else {
+                return Err(does_not_apply());
+            };
+            if i.trait_ref != *trait_ref {
+                return Err(does_not_apply());
+            }
+            let ill_formed = || ProofError::IllFormedTraitRef { goal: goal.clone() };
+            if !decls.has_trait_decl(&trait_ref.trait_id) {
+                return Err(ill_formed());
+            }
+            let Ok(t) = decls
+                .trait_decl(&trait_ref.trait_id)
+                .binder
+                .instantiate_with(&trait_ref.parameters)
+            else {
+                return Err(ill_formed());
+            };
+            (i.where_clause, t.where_clause).upcast()
+        }
+    };
+
+    let found: Wcs = premises.iter().map(|p| p.goal.clone()).collect();
+    if found != expected {
+        return Err(ProofError::WrongPremises {
+            goal: goal.clone(),
+            expected,
+            found,
+        });
+    }
+
+    premises
+        .iter()
+        .try_for_each(|p| verify(decls, env, assumptions, p))
+}
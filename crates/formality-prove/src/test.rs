@@ -1,10 +1,40 @@
 mod adt_wf;
+mod array;
+mod candidate_clauses;
+mod coinduction;
+mod const_simplify;
+mod deferred;
+mod differential;
+mod dyn_upcast;
 mod eq_assumptions;
 mod eq_partial_eq;
 mod exists_constraints;
 mod expanding;
+mod federated;
+mod filter_implementors;
+mod implies;
+mod impls_may_overlap;
+mod inconsistent;
+mod inference_graph;
 mod is_local;
+mod layered;
 mod magic_copy;
+mod multi_param;
+mod negative_impl;
+mod never;
+mod obligation_forest;
 mod occurs_check;
+mod opaque;
+mod projection_ambiguity;
+mod propagate;
+mod prove_cache;
+mod prove_normalized_eq;
 mod simple_impl;
+mod sizedness;
+mod solve_result;
+mod subgoal_memo;
+mod trait_satisfiable;
+mod trait_wf;
 mod universes;
+mod unknown_predicate;
+mod verify_proof;
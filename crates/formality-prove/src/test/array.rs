@@ -0,0 +1,95 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::{
+    grammar::{Parameter, Relation, Ty, Wcs},
+    rust::term,
+};
+
+use crate::{decls::Decls, prove::prove, prove_array_repeat, Env};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Sized<ty Self> where {}"),
+            term("trait Copy<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl Sized(u32) where {}"),
+            term("impl Copy(u32) where {}"),
+            term("impl Sized(String) where {}"),
+        ],
+        adt_decls: vec![term("adt String where {}")],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn well_formed_array() {
+    let goal: Parameter = term("[u32; 4_usize]");
+    prove(decls(), Env::default(), Wcs::t(), Relation::WellFormed(goal)).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn array_length_not_usize() {
+    let goal: Parameter = term("[u32; 4_u32]");
+    prove(decls(), Env::default(), Wcs::t(), Relation::WellFormed(goal)).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {@ wf([u32; const value(4, u32)])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: @ wf([u32; const value(4, u32)]), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+              the rule "parameter well formed" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wf { goal: [u32; const value(4, u32)], assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                  the rule "arrays" failed at step #2 (src/file.rs:LL:CC) because
+                    judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Sized(u32), @ ConstHasType(value(4, u32) , usize)}, assumptions: {}, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                      the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {Sized(u32), @ ConstHasType(value(4, u32) , usize)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                          the rule "some" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {@ ConstHasType(value(4, u32) , usize)}, assumptions: {}, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                              the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_wc_list { goal: {@ ConstHasType(value(4, u32) , usize)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc { goal: @ ConstHasType(value(4, u32) , usize), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                      the rule "const has ty" failed at step #1 (src/file.rs:LL:CC) because
+                                        judgment `prove_wc_list { goal: {u32 = usize}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                            judgment `prove_wc { goal: u32 = usize, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                              the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                                judgment `prove_eq { a: u32, b: usize, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }`
+                                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                                    judgment `prove_eq { a: usize, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                                                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                                        judgment had no applicable rules: `prove_normalize { p: usize, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }`
+                                                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                                        cyclic proof attempt: `prove_eq { a: u32, b: usize, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }`, reached through:
+                                                          `prove_eq { a: usize, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }`"#]]);
+}
+
+#[test]
+fn repeat_copy_element() {
+    let array_ty: Ty = term("[u32; 4_usize]");
+    prove_array_repeat(decls(), Env::default(), Wcs::t(), array_ty).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn repeat_non_copy_element() {
+    let array_ty: Ty = term("[String; 4_usize]");
+    prove_array_repeat(decls(), Env::default(), Wcs::t(), array_ty).assert_err(expect![[r#"
+        judgment `prove_array_repeat { array_ty: [String; const value(4, usize)], assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+          the rule "repeat" failed at step #3 (src/file.rs:LL:CC) because
+            judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Copy(String)}, assumptions: {}, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+              the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                judgment `prove_wc_list { goal: {Copy(String)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_wc { goal: Copy(String), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> , trait Copy <ty> ], [impl Sized(u32), impl Copy(u32), impl Sized(String)], [], [], [], [adt String ], {}, {}) }` failed at the following rule(s):
+                      the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                        expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
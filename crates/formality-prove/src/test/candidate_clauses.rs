@@ -0,0 +1,44 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::{grammar::TraitRef, rust::term};
+
+use crate::{candidate_clauses, decls::Decls, Env};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+            term("trait Baz<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl<ty T> Foo(T) where {Bar(T)}"),
+            term("impl Foo(u32) where {Baz(u32)}"),
+            term("impl Foo(u64) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn blanket_and_specific_impl() {
+    let goal: TraitRef = term("Foo(u32)");
+    let candidates: Vec<String> = candidate_clauses(decls(), Env::default(), &goal)
+        .into_iter()
+        .map(|c| {
+            format!(
+                "{:?} with {:?} leaves {:?}",
+                c.impl_decl,
+                c.unifier.substitution(),
+                c.residual
+            )
+        })
+        .collect();
+    expect![[r#"
+        [
+            "impl <ty> Foo(^ty0_0) where {Bar(^ty0_0)} with {?ty_1 => u32} leaves {Bar(u32)}",
+            "impl Foo(u32) where {Baz(u32)} with {} leaves {Baz(u32)}",
+        ]
+    "#]]
+    .assert_debug_eq(&candidates);
+}
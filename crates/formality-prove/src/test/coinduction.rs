@@ -0,0 +1,101 @@
+use expect_test::expect;
+use formality_macros::test;
+use formality_types::{grammar::Wc, rust::term};
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+/// An auto trait `Send` with the impls that would be generated for a list
+/// whose tail is boxed: proving `Send(List)` needs `Send(List)` again.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("coinductive trait Send<ty Self> where {}")],
+        impl_decls: vec![
+            term("impl Send(u32) where {}"),
+            term("impl<ty T> Send(Box<T>) where {Send(T)}"),
+            term("impl Send(List) where {Send(u32), Send(Box<List>)}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn cyclic_auto_trait_lists_coinductive_assumption() {
+    test_prove(decls(), term("{} => {Send(List)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {}, coinductive_assumptions: {Send(Box<List>), Send(List)} },
+        }
+    "#]]);
+}
+
+#[test]
+fn acyclic_proof_has_no_coinductive_assumptions() {
+    test_prove(decls(), term("{} => {Send(Box<u32>)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// `Send(List)` is among the assumptions, so using it closes no cycle and it
+/// is never a coinductive assumption; only the cycle that unfolds it back to
+/// `Send(Box<List>)` is.
+#[test]
+fn assumed_goal_is_not_coinductive() {
+    let result = test_prove(decls(), term("{Send(List)} => {Send(Box<List>)}"));
+    result.assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {}, coinductive_assumptions: {Send(Box<List>)} },
+        }
+    "#]]);
+    let assumed: Wc = term("Send(List)");
+    assert!(result
+        .iter()
+        .all(|c| !c.coinductive_assumptions.contains(&assumed)));
+}
+
+/// The coinductive `Send` and the inductive `Ord` in cycles of both kinds:
+/// the cycle through `Send(A)` passes through `Ord(A)`, while the cycle
+/// through `Send(B)` stays beneath `Ord(B)` and closes with `Send` alone.
+fn mixed_decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("coinductive trait Send<ty Self> where {}"),
+            term("trait Ord<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl Send(A) where {Ord(A)}"),
+            term("impl Ord(A) where {Send(A)}"),
+            term("impl Ord(B) where {Send(B)}"),
+            term("impl Send(B) where {Send(B)}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn cycle_closing_through_inductive_goal_fails() {
+    let decls = mixed_decls();
+    assert!(!test_prove(decls.clone(), term("{} => {Send(A)}")).is_proven());
+    assert!(!test_prove(decls, term("{} => {Ord(A)}")).is_proven());
+}
+
+#[test]
+fn coinductive_cycle_beneath_inductive_goal_holds() {
+    test_prove(mixed_decls(), term("{} => {Ord(B)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {}, coinductive_assumptions: {Send(B)} },
+        }
+    "#]]);
+}
+
+#[test]
+fn inductive_self_cycle_fails() {
+    let decls = Decls {
+        trait_decls: vec![term("trait Ord<ty Self> where {}")],
+        impl_decls: vec![term("impl Ord(B) where {Ord(B)}")],
+        ..Decls::empty()
+    };
+    assert!(!test_prove(decls, term("{} => {Ord(B)}")).is_proven());
+}
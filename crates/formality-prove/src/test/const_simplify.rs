@@ -0,0 +1,84 @@
+use expect_test::expect;
+use formality_macros::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+#[test]
+fn add_zero() {
+    test_prove(
+        Decls::empty(),
+        term("{} => {for<const N> const {N + 0_usize} = const N}"),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn double_is_sum() {
+    test_prove(
+        Decls::empty(),
+        term("{} => {for<const N> const {2_usize * N} = const {N + N}}"),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn literals_fold() {
+    test_prove(
+        Decls::empty(),
+        term("{} => {for<const N> const {{N + 1_usize} + 2_usize} = const {N + 3_usize}}"),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn simplified_before_inference() {
+    test_prove(
+        Decls::empty(),
+        term("exists<const M> {} => {const {M * 1_usize} = const 3_usize}"),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?const_1], bias: Soundness }, known_true: true, substitution: {?const_1 => const value(3, usize)} },
+        }
+    "#]]);
+}
+
+#[test]
+fn add_one_differs() {
+    test_prove(
+        Decls::empty(),
+        term("{} => {for<const N> const {N + 1_usize} = const N}"),
+    )
+    .assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {for <const> const { ^const0_0 + value(1, usize) } = const ^const0_0}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: for <const> const { ^const0_0 + value(1, usize) } = const ^const0_0, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "forall" failed at step #2 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: const { !const_1 + value(1, usize) } = const !const_1, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_eq { a: const { !const_1 + value(1, usize) }, b: const !const_1, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment had no applicable rules: `prove_normalize { p: const { !const_1 + value(1, usize) }, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }`
+                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_eq { a: const !const_1, b: const { !const_1 + value(1, usize) }, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_normalize { p: const !const_1, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }`
+                          the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                            cyclic proof attempt: `prove_eq { a: const { !const_1 + value(1, usize) }, b: const !const_1, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }`, reached through:
+                              `prove_eq { a: const !const_1, b: const { !const_1 + value(1, usize) }, assumptions: {}, env: Env { variables: [!const_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }`"#]]);
+}
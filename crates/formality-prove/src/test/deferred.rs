@@ -0,0 +1,71 @@
+use expect_test::expect;
+use formality_core::Upcast;
+use formality_types::{
+    grammar::{ParameterKind, Relation, Ty, Wcs},
+    rust::term,
+};
+
+use crate::{
+    decls::Decls, discharge, prove_or_defer, Constraints, DeferredObligations, Env, ObligationKind,
+};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Iterator<ty Self> where {}")],
+        impl_decls: vec![term("impl<ty T> Iterator(Vec<T>) where {}")],
+        alias_eq_decls: vec![term("alias <ty T> <Vec<T> as Iterator>::Item = T where {}")],
+        ..Decls::empty()
+    }
+}
+
+/// `?X = <Vec<u32> as Iterator>::Item` only holds by choosing `?X`, so it is
+/// deferred until inference decides `?X = u32`.
+#[test]
+fn projection_discharged_once_variable_is_constrained() {
+    let mut env = Env::default();
+    let x = env.fresh_existential(ParameterKind::Ty);
+    let x_ty: Ty = x.upcast();
+    let projection = Relation::equals(x_ty, term::<Ty>("<Vec<u32> as Iterator>::Item"));
+    let trait_goal: Wcs = term("{Iterator(Vec<u32>)}");
+
+    let mut deferred = DeferredObligations::new();
+    prove_or_defer(decls(), &env, (), (projection, trait_goal), &mut deferred).unwrap();
+    expect![[r#"
+        [
+            "?ty_1 = <Vec<u32> as Iterator>::Item",
+        ]
+    "#]]
+    .assert_debug_eq(
+        &deferred
+            .of_kind(ObligationKind::Projection)
+            .map(|o| format!("{:?}", o.goal.data()))
+            .collect::<Vec<_>>(),
+    );
+
+    assert_eq!(deferred.len(), 1);
+
+    // Nothing has been learned yet, so it stays deferred.
+    discharge(decls(), &Constraints::none(&env), (), &mut deferred).unwrap();
+    assert_eq!(deferred.len(), 1);
+
+    let u32_ty: Ty = term("u32");
+    let constraints = Constraints::from(&env, vec![(x, u32_ty)]);
+    discharge(decls(), &constraints, (), &mut deferred).unwrap();
+    assert!(deferred.is_empty());
+}
+
+#[test]
+fn obligation_that_no_longer_holds_is_an_error() {
+    let mut env = Env::default();
+    let x = env.fresh_existential(ParameterKind::Ty);
+    let x_ty: Ty = x.upcast();
+    let projection = Relation::equals(x_ty, term::<Ty>("<Vec<u32> as Iterator>::Item"));
+
+    let mut deferred = DeferredObligations::new();
+    prove_or_defer(decls(), &env, (), projection, &mut deferred).unwrap();
+
+    let i32_ty: Ty = term("i32");
+    let constraints = Constraints::from(&env, vec![(x, i32_ty)]);
+    assert!(discharge(decls(), &constraints, (), &mut deferred).is_err());
+    assert_eq!(deferred.len(), 1);
+}
@@ -0,0 +1,63 @@
+use expect_test::expect;
+use formality_core::{test, ProvenSet};
+use formality_types::{grammar::Wcs, rust::term};
+
+use crate::{
+    decls::Decls,
+    prove::{prove, Constraints, DifferentialSolver, SolveResult, SolverConfiguration},
+};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {Foo(Self)}"),
+        ],
+        impl_decls: vec![
+            term("impl Foo(u32) where {}"),
+            term("impl<ty T> Bar(Vec<T>) where {Foo(T)}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn memoized_and_cosld_agree() {
+    let solver = DifferentialSolver::configurations(
+        SolverConfiguration::Memoized,
+        SolverConfiguration::Cosld,
+    );
+    for goal in [
+        "{Foo(u32)}",
+        "{Foo(u64)}",
+        "{Bar(Vec<u32>)}",
+        "{Bar(Vec<u64>)}",
+    ] {
+        let goal: Wcs = term(goal);
+        assert!(solver.prove(decls(), (), (), goal).is_ok());
+    }
+}
+
+/// A solver that claims every goal holds is caught on a goal that does not.
+#[test]
+fn divergence_of_buggy_solver_is_reported() {
+    let solver = DifferentialSolver::new(
+        "prove",
+        |decls, env, assumptions, goal| prove(decls, env, assumptions, goal),
+        "always true",
+        |_decls, env, _assumptions, _goal| ProvenSet::singleton(Constraints::none(env)),
+    );
+    let goal: Wcs = term("{Foo(u32)}");
+    assert!(solver.prove(decls(), (), (), goal).is_ok());
+
+    let goal: Wcs = term("{Foo(u64)}");
+    let divergence = solver.prove(decls(), (), (), goal).unwrap_err();
+    let (left_name, left) = &divergence.left;
+    let (right_name, right) = &divergence.right;
+    expect!["goal: {Foo(u64)}, prove: Disproven, always true: Proven"].assert_eq(&format!(
+        "goal: {:?}, {left_name}: {:?}, {right_name}: {:?}",
+        divergence.goal,
+        SolveResult::of(left),
+        SolveResult::of(right),
+    ));
+}
@@ -0,0 +1,68 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::{grammar::DynTy, rust::term};
+
+use crate::{can_upcast_dyn, decls::Decls, Env};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Super<ty Self> where {}"),
+            term("trait Sub<ty Self> where {Super(Self)}"),
+            term("trait Send<ty Self> where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn upcast_to_supertrait() {
+    let from: DynTy = term("dyn Sub [Send]");
+    let to: DynTy = term("dyn Super");
+    can_upcast_dyn(decls(), Env::default(), (), from, to).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn upcast_to_same_trait() {
+    let from: DynTy = term("dyn Sub [Send]");
+    let to: DynTy = term("dyn Sub [Send]");
+    can_upcast_dyn(decls(), Env::default(), (), from, to).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn upcast_to_subtrait() {
+    let from: DynTy = term("dyn Super");
+    let to: DynTy = term("dyn Sub");
+    can_upcast_dyn(decls(), Env::default(), (), from, to).assert_err(expect![[r#"
+        judgment `can_upcast_dyn { from: dyn Super, to: dyn Sub, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "upcast" failed at step #1 (src/file.rs:LL:CC) because
+            judgment `prove_wc_list { goal: {for <ty> if {Super(^ty0_0)} Sub(^ty0_0)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: for <ty> if {Super(^ty0_0)} Sub(^ty0_0), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "forall" failed at step #2 (src/file.rs:LL:CC) because
+                    judgment `prove_wc { goal: if {Super(!ty_1)} Sub(!ty_1), assumptions: {}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "implies" failed at step #3 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Sub(!ty_1), assumptions: {Super(!ty_1)}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "assumption" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_via { goal: Sub(!ty_1), via: Super(!ty_1), assumptions: {Super(!ty_1)}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_via { goal: Sub(!ty_1), via: Super(?ty_2), assumptions: {Super(!ty_1)}, env: Env { variables: [!ty_1, ?ty_2], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }`"#]]);
+}
+
+#[test]
+fn upcast_adds_auto_trait() {
+    let from: DynTy = term("dyn Sub");
+    let to: DynTy = term("dyn Super [Send]");
+    can_upcast_dyn(decls(), Env::default(), (), from, to).assert_err(expect![[r#"
+        judgment `can_upcast_dyn { from: dyn Sub, to: dyn Super [Send], assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Super <ty> , trait Sub <ty> where {Super(^ty0_0)}, trait Send <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "upcast" failed at step #0 (src/file.rs:LL:CC) because
+            condition evaluted to false: `to.auto_traits.is_subset(&from.auto_traits)`"#]]);
+}
@@ -24,3 +24,17 @@ fn exists_u_for_t() {
         }
     "#]]);
 }
+
+/// Variables are relabeled in environment order when rendered for users.
+#[test]
+fn exists_u_for_t_readable() {
+    let constraints = test_prove(decls(), term("exists<ty U> {} => {Foo(U)}"))
+        .into_set()
+        .unwrap();
+    let rendered: Vec<String> = constraints.iter().map(|c| c.to_readable_string()).collect();
+    expect![[r#"
+        [
+            "Constraints { env: Env { variables: [?A, ?B], bias: Soundness }, known_true: true, substitution: {?B => Vec<?A>} }",
+        ]
+    "#]].assert_debug_eq(&rendered);
+}
@@ -0,0 +1,56 @@
+use expect_test::expect;
+use formality_core::{set, test};
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+/// The declarations shared by every member, like a common dependency.
+fn core() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Foo<ty Self> where {}")],
+        ..Decls::empty()
+    }
+}
+
+fn federated() -> Decls {
+    let second = Decls {
+        impl_decls: vec![term("impl Foo(u32) where {}")],
+        ..core()
+    };
+    Decls::federated([core(), second])
+}
+
+#[test]
+fn clause_from_second_member() {
+    test_prove(federated(), term("{} => {Foo(u32)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn shared_decls_deduplicated() {
+    expect!["decls(222, [trait Foo <ty> ], [impl Foo(u32)], [], [], [], [], {}, {})"]
+        .assert_eq(&format!("{:?}", federated()));
+}
+
+/// Locality is merged, so an impl of `Foo` in a member other than the one that
+/// defines it (an orphan there) is local with respect to the federated
+/// declarations.
+#[test]
+fn locality_is_merged() {
+    let defines_foo = Decls {
+        local_trait_ids: set![term("Foo")],
+        ..core()
+    };
+    let other = Decls {
+        adt_decls: vec![term("adt Bar where {}")],
+        ..core()
+    };
+    let goal = "{} => {@IsLocal(Foo(Bar))}";
+    assert!(!test_prove(other.clone(), term(goal)).is_proven());
+    assert!(test_prove(Decls::federated([defines_foo, other]), term(goal)).is_proven());
+}
@@ -0,0 +1,35 @@
+use expect_test::expect;
+use formality_core::Upcast;
+use formality_macros::test;
+use formality_types::grammar::{RigidName, RigidTy, TraitRef, Ty};
+use formality_types::rust::term;
+
+use crate::{decls::Decls, prove::filter_implementors};
+
+/// `Display` is implemented for `u32` and `String` but not for function pointers.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Display<ty Self> where {}")],
+        impl_decls: vec![
+            term("impl Display(u32) where {}"),
+            term("impl Display(String) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+fn fn_ptr() -> Ty {
+    RigidTy {
+        name: RigidName::FnPtr(0),
+        parameters: vec![term("()")],
+    }
+    .upcast()
+}
+
+#[test]
+fn display_implementors() {
+    let trait_ref: TraitRef = term("Display(u32)");
+    let candidates: Vec<Ty> = vec![term("u32"), term("String"), fn_ptr()];
+    let implementors = filter_implementors(decls(), &trait_ref, &candidates);
+    expect!["[u32, String]"].assert_eq(&format!("{implementors:?}"));
+}
@@ -0,0 +1,74 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl<ty T> Bar(T) where {Foo(T)}"),
+            term("impl Foo(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+/// `Bar(T) :- Foo(T)`, so assuming `Foo(T)` suffices.
+#[test]
+fn implication_universal() {
+    test_prove(decls(), term("forall<ty T> {} => {if {Foo(T)} Bar(T)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [!ty_1], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// The hypothesis mentions `?T`, so it is proven for a placeholder in its place:
+/// a single solution that holds whatever `?T` turns out to be, rather than one
+/// that also picks `?T = u32` to satisfy the hypothesis.
+#[test]
+fn implication_existential() {
+    test_prove(decls(), term("exists<ty T> {} => {if {Foo(T)} Bar(T)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// Assuming `?T = u32` must not constrain `?T`.
+#[test]
+fn implication_existential_hypothesis_only() {
+    test_prove(decls(), term("exists<ty T> {} => {if {T = u32} Foo(T)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// Without the hypothesis, `Bar(T)` cannot be proven for an arbitrary `T`.
+#[test]
+fn hypothesis_is_needed() {
+    test_prove(decls(), term("forall<ty T> {} => {if {} Bar(T)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {if {} Bar(!ty_0)}, assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: if {} Bar(!ty_0), assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "implies" failed at step #3 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: Bar(!ty_0), assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "positive impl" failed at step #6 (src/file.rs:LL:CC) because
+                    judgment `prove_after { constraints: Constraints { env: Env { variables: [!ty_0, ?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => !ty_0} }, goal: {Foo(?ty_1)}, assumptions: {}, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {Foo(!ty_0)}, assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: Foo(!ty_0), assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Foo <ty> , trait Bar <ty> ], [impl <ty> Bar(^ty0_0) where {Foo(^ty0_0)}, impl Foo(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                                expression evaluated to an empty collection: `decls.trait_invariants()`
+                  the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                    expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
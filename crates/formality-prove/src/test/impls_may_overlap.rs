@@ -0,0 +1,47 @@
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::{
+    decls::{Decls, ImplDecl},
+    impls_may_overlap,
+};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn blanket_impl_overlaps_specific_one() {
+    let a: ImplDecl = term("impl<ty T> Foo(Vec<T>) where {}");
+    let b: ImplDecl = term("impl Foo(Vec<u32>) where {}");
+    assert!(impls_may_overlap(decls(), &a, &b));
+}
+
+#[test]
+fn distinct_types_do_not_overlap() {
+    let a: ImplDecl = term("impl Foo(u32) where {}");
+    let b: ImplDecl = term("impl Foo(i32) where {}");
+    assert!(!impls_may_overlap(decls(), &a, &b));
+}
+
+#[test]
+fn different_traits_do_not_overlap() {
+    let a: ImplDecl = term("impl<ty T> Foo(T) where {}");
+    let b: ImplDecl = term("impl<ty T> Bar(T) where {}");
+    assert!(!impls_may_overlap(decls(), &a, &b));
+}
+
+/// Where-clauses are ignored: `a` never applies to `u32`, which is not `Bar`,
+/// yet the headers still unify.
+#[test]
+fn where_clauses_are_ignored() {
+    let a: ImplDecl = term("impl<ty T> Foo(T) where {Bar(T)}");
+    let b: ImplDecl = term("impl Foo(u32) where {}");
+    assert!(impls_may_overlap(decls(), &a, &b));
+}
@@ -0,0 +1,49 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::grammar::Wcs;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+use crate::prove::{Env, Inconsistency};
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Debug<ty Self> where {}")],
+        impl_decls: vec![term("impl Debug(u32) where {}")],
+        ..Decls::empty()
+    }
+}
+
+/// `T` cannot be both `u32` and `i32`, so the solver fails without searching
+/// and names the conflicting assumptions.
+#[test]
+fn conflicting_bindings_short_circuit() {
+    test_prove(decls(), term("forall<ty T> {T = u32, T = i32} => {Debug(T)}")).assert_err(
+        expect![[r#"
+            judgment `prove { goal: {Debug(!ty_0)}, assumptions: {!ty_0 = u32, !ty_0 = i32} }` failed at the following rule(s):
+              failed at (src/file.rs:LL:CC) because
+                inconsistent assumptions: `!ty_0 = u32` and `!ty_0 = i32` cannot both hold"#]],
+    );
+}
+
+#[test]
+fn consistent_bindings_are_not_flagged() {
+    test_prove(decls(), term("forall<ty T> {T = u32} => {Debug(T)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [!ty_1], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// A single assumption can contradict itself.
+#[test]
+fn is_inconsistent_self_contradiction() {
+    let assumptions: Wcs = term("{u32 = i32}");
+    let Some(Inconsistency { a, b }) = Env::default().is_inconsistent(&assumptions) else {
+        panic!("expected an inconsistency");
+    };
+    expect!["u32 = i32"].assert_eq(&format!("{a:?}"));
+    assert_eq!(a, b);
+}
@@ -0,0 +1,87 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+use crate::test_util::test_prove;
+
+/// `A`, `B`, and `C` are all equated with `u32`, while `D` and `E` are only
+/// equated with each other and `F` is unconstrained.
+#[test]
+fn equivalence_classes() {
+    let result = test_prove(
+        Decls::empty(),
+        term("exists<ty A, ty B, ty C, ty D, ty E, ty F> {} => {A = B, B = C, C = u32, D = E}"),
+    );
+    let graphs: Vec<_> = result.iter().map(|c| c.inference_graph()).collect();
+    let [graph] = &graphs[..] else {
+        panic!("expected one solution, got {graphs:?}");
+    };
+    expect![[r#"
+        digraph inference {
+          subgraph cluster_0 {
+            "?ty_1";
+            "?ty_2";
+            "?ty_3";
+          }
+          "u32" [shape=box];
+          "?ty_1" -> "u32";
+          "?ty_2" -> "u32";
+          "?ty_3" -> "u32";
+          subgraph cluster_1 {
+            "?ty_4";
+            "?ty_5";
+          }
+          subgraph cluster_2 {
+            "?ty_6";
+          }
+        }"#]]
+    .assert_eq(&graph.to_dot());
+}
+
+/// Proving that `B` is `u32` as well merges the class of `A` and `B` with the
+/// value `u32`, and leaves `C` alone.
+#[test]
+fn diff_after_binding() {
+    let graph = |goal: &str| {
+        let result = test_prove(Decls::empty(), term(goal));
+        let graphs: Vec<_> = result.iter().map(|c| c.inference_graph()).collect();
+        let [graph] = &graphs[..] else {
+            panic!("expected one solution, got {graphs:?}");
+        };
+        graph.clone()
+    };
+    let before = graph("exists<ty A, ty B, ty C> {} => {A = B}");
+    let after = graph("exists<ty A, ty B, ty C> {} => {A = B, B = u32}");
+    expect![[r#"
+        InferenceGraphDiff {
+            removed: [
+                EquivalenceClass {
+                    variables: [
+                        ?ty_1,
+                        ?ty_2,
+                    ],
+                    value: None,
+                },
+            ],
+            added: [
+                EquivalenceClass {
+                    variables: [
+                        ?ty_1,
+                        ?ty_2,
+                    ],
+                    value: Some(
+                        Ty(
+                            Ty {
+                                data: RigidTy(
+                                    u32,
+                                ),
+                            },
+                        ),
+                    ),
+                },
+            ],
+        }
+    "#]]
+    .assert_debug_eq(&before.diff(&after));
+}
@@ -6,30 +6,38 @@ use crate::decls::Decls;
 
 use crate::test_util::test_prove;
 
+/// Declares the (non-local) trait used by the tests below.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Debug<ty Self> where {}")],
+        ..Decls::empty()
+    }
+}
+
 #[test]
 fn test_forall_not_local() {
     test_prove(
-        Decls::empty(),
+        decls(),
         term("{} => {for<ty T> @IsLocal(Debug(T))}"),
     ).assert_err(
     expect![[r#"
-        judgment `prove_wc_list { goal: {for <ty> @ IsLocal(Debug(^ty0_0))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+        judgment `prove_wc_list { goal: {for <ty> @ IsLocal(Debug(^ty0_0))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
           the rule "some" failed at step #0 (src/file.rs:LL:CC) because
-            judgment `prove_wc { goal: for <ty> @ IsLocal(Debug(^ty0_0)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+            judgment `prove_wc { goal: for <ty> @ IsLocal(Debug(^ty0_0)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
               the rule "forall" failed at step #2 (src/file.rs:LL:CC) because
-                judgment `prove_wc { goal: @ IsLocal(Debug(!ty_1)), assumptions: {}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                judgment `prove_wc { goal: @ IsLocal(Debug(!ty_1)), assumptions: {}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
                   the rule "trait ref is local" failed at step #0 (src/file.rs:LL:CC) because
-                    judgment `is_local_trait_ref { goal: Debug(!ty_1), assumptions: {}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                    judgment `is_local_trait_ref { goal: Debug(!ty_1), assumptions: {}, env: Env { variables: [!ty_1], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
                       the rule "local trait" failed at step #0 (src/file.rs:LL:CC) because
                         condition evaluted to false: `decls.is_local_trait_id(&goal.trait_id)`
-                          decls = decls(222, [], [], [], [], [], [], {}, {})
+                          decls = decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {})
                           &goal.trait_id = Debug"#]]);
 }
 
 #[test]
 fn test_exists_not_local() {
     test_prove(
-        Decls::empty(),
+        decls(),
         term("exists<ty T> {} => {@IsLocal(Debug(T))}"),
     )
     .assert_ok(expect![[r#"
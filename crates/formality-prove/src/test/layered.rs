@@ -0,0 +1,53 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::{Decls, LayeredDb, OverrideMode};
+
+use crate::test_util::test_prove;
+
+fn base() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Foo<ty Self> where {}")],
+        impl_decls: vec![term("impl Foo(u32) where {}")],
+        ..Decls::empty()
+    }
+}
+
+fn patch() -> Decls {
+    Decls {
+        impl_decls: vec![term("impl Foo(i32) where {}")],
+        ..Decls::empty()
+    }
+}
+
+/// The patch's impl of `Foo` replaces the base's, so only `i32` remains.
+#[test]
+fn higher_layer_replaces_base_clause() {
+    let mut db = LayeredDb::new(base());
+    db.push_layer(patch(), OverrideMode::Override);
+
+    test_prove(db.decls().clone(), term("{} => {Foo(i32)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+    test_prove(db.decls().clone(), term("{} => {Foo(u32)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Foo(u32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(i32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Foo(u32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(i32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+/// Without overriding, the layers' clauses are combined.
+#[test]
+fn extend_keeps_base_clause() {
+    let mut db = LayeredDb::new(base());
+    db.push_layer(patch(), OverrideMode::Extend);
+
+    expect![
+        "decls(222, [trait Foo <ty> ], [impl Foo(u32), impl Foo(i32)], [], [], [], [], {}, {})"
+    ]
+    .assert_eq(&format!("{:?}", db.decls()));
+}
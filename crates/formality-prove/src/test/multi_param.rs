@@ -0,0 +1,65 @@
+use expect_test::expect;
+use formality_macros::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+/// A two-parameter `From` trait with a handful of concrete impls.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait From<ty Self, ty T> where {}")],
+        impl_decls: vec![
+            term("impl From(u32, u8) where {}"),
+            term("impl From(u32, u16) where {}"),
+            term("impl From(u64, u8) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+/// `u32: From<?X>` enumerates every `?X` with a matching impl.
+#[test]
+fn from_unknown_source() {
+    test_prove(decls(), term("exists<ty X> {} => {From(u32, X)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u8} },
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u16} },
+        }
+    "#]]);
+}
+
+/// `?Y: From<u8>` binds the self type symmetrically.
+#[test]
+fn from_unknown_target() {
+    test_prove(decls(), term("exists<ty Y> {} => {From(Y, u8)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u32} },
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u64} },
+        }
+    "#]]);
+}
+
+/// With both positions unknown, every impl contributes a solution.
+#[test]
+fn from_both_unknown() {
+    test_prove(decls(), term("exists<ty X, ty Y> {} => {From(Y, X)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => u8, ?ty_2 => u32} },
+          Constraints { env: Env { variables: [?ty_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => u8, ?ty_2 => u64} },
+          Constraints { env: Env { variables: [?ty_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => u16, ?ty_2 => u32} },
+        }
+    "#]]);
+}
+
+/// A variable repeated across positions must be bound consistently.
+#[test]
+fn from_same_variable_twice() {
+    test_prove(decls(), term("exists<ty X> {} => {From(X, X)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {From(?ty_0, ?ty_0)}, assumptions: {}, env: Env { variables: [?ty_0], bias: Soundness }, decls: decls(222, [trait From <ty, ty> ], [impl From(u32, u8), impl From(u32, u16), impl From(u64, u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: From(?ty_0, ?ty_0), assumptions: {}, env: Env { variables: [?ty_0], bias: Soundness }, decls: decls(222, [trait From <ty, ty> ], [impl From(u32, u8), impl From(u32, u16), impl From(u64, u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
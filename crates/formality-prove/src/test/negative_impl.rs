@@ -0,0 +1,53 @@
+use expect_test::expect;
+use formality_core::{judgment::RuleFailureCause, test};
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Send<ty Self> where {}")],
+        impl_decls: vec![term("impl Send(u32) where {}")],
+        neg_impl_decls: vec![term("impl<ty T> !Send(Rc<T>) where {}")],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn disproven_by_negative_impl() {
+    let result = test_prove(decls(), term("{} => {Send(Rc<u32>)}"));
+    result.assert_err(expect![[r#"
+        judgment `prove { goal: {Send(Rc<u32>)} }` failed at the following rule(s):
+          failed at (src/file.rs:LL:CC) because
+            disproven by `impl <ty> ! Send(Rc<^ty0_0>)`"#]]);
+
+    let failure = result.check_proven().unwrap_err();
+    assert!(failure
+        .failed_rules
+        .iter()
+        .all(|r| matches!(r.cause, RuleFailureCause::DisprovenByNegativeImpl { .. })));
+}
+
+/// `Send(?X)` holds for `?X = u32`, so the negative impl (which would
+/// constrain `?X`) does not disprove it.
+#[test]
+fn negative_impl_does_not_disprove_inference_variable() {
+    test_prove(decls(), term("exists<ty X> {} => {Send(X)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u32} },
+        }
+    "#]]);
+}
+
+/// Without a matching negative impl, the usual failure is reported.
+#[test]
+fn no_rule_applied_without_negative_impl() {
+    test_prove(decls(), term("{} => {Send(i32)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Send(i32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Send <ty> ], [impl Send(u32)], [impl <ty> ! Send(Rc<^ty0_0>)], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Send(i32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Send <ty> ], [impl Send(u32)], [impl <ty> ! Send(Rc<^ty0_0>)], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
@@ -0,0 +1,81 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Display<ty Self> where {}")],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn never_implements_display() {
+    test_prove(decls(), term("{} => {Display(!)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn never_is_well_formed() {
+    test_prove(decls(), term("{} => {@wf(!)}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn never_subtype_of_u32() {
+    test_prove(decls(), term("{} => {! <: u32}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn u32_not_subtype_of_never() {
+    test_prove(decls(), term("{} => {u32 <: !}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {u32 <: !}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: u32 <: !, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "sub via eq" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_eq { a: u32, b: !, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }`
+                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_eq { a: !, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment had no applicable rules: `prove_normalize { p: !, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }`
+                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                        cyclic proof attempt: `prove_eq { a: u32, b: !, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }`, reached through:
+                          `prove_eq { a: !, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }`"#]]);
+}
+
+/// The never rule applies only when the self type is known to be `!`;
+/// it does not make an inference variable ambiguous or infer it to `!`.
+#[test]
+fn never_does_not_apply_to_variables() {
+    test_prove(decls(), term("exists<ty T> {} => {Display(T)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Display(?ty_0)}, assumptions: {}, env: Env { variables: [?ty_0], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Display(?ty_0), assumptions: {}, env: Env { variables: [?ty_0], bias: Soundness }, decls: decls(222, [trait Display <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+#[test]
+fn u32_subtype_of_u32() {
+    test_prove(decls(), term("{} => {u32 <: u32}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
@@ -0,0 +1,64 @@
+use formality_core::Upcast;
+use formality_types::{
+    grammar::{ParameterKind, TraitId, Ty, Wc},
+    rust::term,
+};
+
+use crate::{decls::Decls, Env, ObligationForest, ObligationState};
+
+/// `Foo` is implemented for `u32` and `u64`, `Bar` only for `u32`.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl Foo(u32) where {}"),
+            term("impl Foo(u64) where {}"),
+            term("impl Bar(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+/// `Foo(?X)` stalls, as both impls apply; `Bar(?X)` then binds `?X = u32`,
+/// after which `Foo(u32)` holds.
+#[test]
+fn stalled_obligation_resolves_once_variable_is_bound() {
+    let mut env = Env::default();
+    let x: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let mut forest = ObligationForest::new(decls(), env, ());
+    let foo = forest.register(TraitId::new("Foo").with(&x, ()).is_implemented());
+    let bar = forest.register(TraitId::new("Bar").with(&x, ()).is_implemented());
+    assert_eq!(forest.state(foo), ObligationState::Pending);
+
+    assert_eq!(forest.process_obligations(), 2);
+    assert_eq!(forest.state(foo), ObligationState::Proven);
+    assert_eq!(forest.state(bar), ObligationState::Proven);
+    assert_eq!(format!("{:?}", forest.goal(foo)), "Foo(u32)");
+}
+
+#[test]
+fn ambiguous_obligation_stays_stalled() {
+    let mut env = Env::default();
+    let x: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let mut forest = ObligationForest::new(decls(), env, ());
+    let foo = forest.register(TraitId::new("Foo").with(&x, ()).is_implemented());
+
+    assert_eq!(forest.process_obligations(), 0);
+    assert_eq!(forest.state(foo), ObligationState::Stalled);
+}
+
+/// A parent is only done once its sub-obligations are, and fails with them.
+#[test]
+fn sub_obligation_failure_fails_parent() {
+    let mut forest = ObligationForest::new(decls(), Env::default(), ());
+    let parent = forest.register(term::<Wc>("Foo(u64)"));
+    let child = forest.register_child(parent, term::<Wc>("Bar(u64)"));
+    assert_eq!(forest.roots().collect::<Vec<_>>(), vec![parent]);
+
+    forest.process_obligations();
+    assert!(matches!(forest.state(child), ObligationState::Failed(_)));
+    assert!(matches!(forest.state(parent), ObligationState::Failed(_)));
+}
@@ -0,0 +1,155 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_core::Upcast;
+use formality_types::grammar::{AdtId, ParameterKind, Ty};
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+use crate::prove::{prove_hidden_types, DefiningScope, Env, OpaqueTyDecl};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Iterator<ty Self> where {}")],
+        impl_decls: vec![term("impl<ty T> Iterator(Vec<T>) where {}")],
+        adt_decls: vec![term("adt Vec<ty T> where {}")],
+        ..Decls::empty()
+    }
+}
+
+/// The opaque type of `fn f() -> impl Iterator`.
+fn opaque() -> OpaqueTyDecl {
+    term("opaque F <ty X> {Iterator(X)}")
+}
+
+fn scope(hidden_tys: &[&str]) -> DefiningScope {
+    let mut scope = DefiningScope::new();
+    for ty in hidden_tys {
+        scope.register_hidden_type(&opaque().id, term::<Ty>(ty));
+    }
+    scope
+}
+
+/// Returning a `Vec<u32>` from `-> impl Iterator`.
+#[test]
+fn hidden_type_satisfies_bounds() {
+    prove_hidden_types(
+        decls(),
+        Env::default(),
+        (),
+        &opaque(),
+        &scope(&["Vec<u32>"]),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn hidden_type_violates_bounds() {
+    prove_hidden_types(decls(), Env::default(), (), &opaque(), &scope(&["u32"]))
+        .assert_err(expect![[r#"
+            judgment `prove_wc_list { goal: {Iterator(u32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: Iterator(u32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                  the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                    expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+/// Two return paths that both return a `Vec<u32>`.
+#[test]
+fn return_paths_agree() {
+    prove_hidden_types(
+        decls(),
+        Env::default(),
+        (),
+        &opaque(),
+        &scope(&["Vec<u32>", "Vec<u32>"]),
+    )
+    .assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+/// A return path whose hidden type is only partially known, `Vec<?X>`, is
+/// unified with the others, so `?X` must be `u32`.
+#[test]
+fn return_paths_unify() {
+    let mut env = Env::default();
+    let x: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let mut scope = scope(&["Vec<u32>"]);
+    scope.register_hidden_type(&opaque().id, Ty::rigid(AdtId::new("Vec"), vec![x]));
+    prove_hidden_types(decls(), env, (), &opaque(), &scope).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u32} },
+        }
+    "#]]);
+}
+
+#[test]
+fn return_paths_disagree() {
+    prove_hidden_types(
+        decls(),
+        Env::default(),
+        (),
+        &opaque(),
+        &scope(&["Vec<u32>", "Vec<i32>"]),
+    )
+    .assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Vec<i32> = Vec<u32>, Iterator(Vec<u32>)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Vec<i32> = Vec<u32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+              the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_eq { a: Vec<i32>, b: Vec<u32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment had no applicable rules: `prove_normalize { p: Vec<i32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                  the rule "rigid" failed at step #3 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {i32 = u32}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: i32 = u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                          the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_eq { a: i32, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                              the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment had no applicable rules: `prove_normalize { p: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                              the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                    cyclic proof attempt: `prove_eq { a: i32, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`, reached through:
+                                      `prove_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_eq { a: Vec<u32>, b: Vec<i32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment had no applicable rules: `prove_normalize { p: Vec<u32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                      the rule "rigid" failed at step #3 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {u32 = i32}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: u32 = i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                              the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_eq { a: i32, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }` failed at the following rule(s):
+                                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_normalize { p: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                        cyclic proof attempt: `prove_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`, reached through:
+                                          `prove_eq { a: i32, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`
+                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                        cyclic proof attempt: `prove_eq { a: Vec<i32>, b: Vec<u32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`, reached through:
+                          `prove_eq { a: Vec<u32>, b: Vec<i32>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> ], [impl <ty> Iterator(Vec<^ty0_0>)], [], [], [], [adt Vec <ty> ], {}, {}) }`"#]]);
+}
+
+#[test]
+fn no_hidden_type() {
+    prove_hidden_types(decls(), Env::default(), (), &opaque(), &scope(&[]))
+        .assert_err(expect![[r#"
+            judgment `prove_hidden_types { opaque: opaque F <ty> {Iterator(^ty0_0)} }` failed at the following rule(s):
+              failed at (src/file.rs:LL:CC) because
+                no hidden type registered for `F`"#]]);
+}
@@ -0,0 +1,66 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::{decls::Decls, prove::SolveResult};
+
+use crate::test_util::test_prove;
+
+/// Only `Vec<T>` implements `Foo`, with `<Vec<T> as Foo>::Assoc = T`.
+/// Nothing implements `Bar`.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        impl_decls: vec![term("impl<ty T> Foo(Vec<T>) where {}")],
+        alias_eq_decls: vec![term("alias <ty T> <Vec<T> as Foo>::Assoc = T where {}")],
+        ..Decls::empty()
+    }
+}
+
+/// The only impl normalizes the projection, with `T = Vec<u64>`.
+#[test]
+fn projection_on_inference_variable_normalizes_through_impls() {
+    let result = test_prove(
+        decls(),
+        term("exists<ty T> {} => {<T as Foo>::Assoc = u64}"),
+    );
+    result.assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_2, ?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => Vec<u64>, ?ty_2 => u64} },
+        }
+    "#]]);
+    assert_eq!(SolveResult::of(&result), SolveResult::Proven);
+}
+
+/// No impl normalizes the projection, but one may once `T` is known, so the
+/// answer is ambiguous rather than false.
+#[test]
+fn projection_on_inference_variable_without_impl_is_ambiguous() {
+    let result = test_prove(
+        decls(),
+        term("exists<ty T> {} => {<T as Bar>::Assoc = u64}"),
+    );
+    result.assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: false, substitution: {} },
+        }
+    "#]]);
+    assert_eq!(SolveResult::of(&result), SolveResult::Ambiguous);
+}
+
+/// Once the self type is known, the projection normalizes through the impl.
+#[test]
+fn projection_on_known_type_normalizes() {
+    let result = test_prove(decls(), term("{} => {<Vec<u64> as Foo>::Assoc = u64}"));
+    assert_eq!(SolveResult::of(&result), SolveResult::Proven);
+}
+
+/// No impl provides the projection for `u16`, which is a failure.
+#[test]
+fn projection_without_impl_fails() {
+    let result = test_prove(decls(), term("{} => {<u16 as Foo>::Assoc = u64}"));
+    assert_eq!(SolveResult::of(&result), SolveResult::Disproven);
+}
@@ -0,0 +1,66 @@
+use expect_test::expect;
+use formality_core::{test, Upcast};
+use formality_types::{
+    grammar::{ParameterKind, TraitId, Ty, Wcs},
+    rust::term,
+};
+
+use crate::{decls::Decls, propagate, prove, prove_with_propagation, Env};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Unique<ty Self> where {}"),
+            term("trait Many<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl<ty T> Unique(Vec<T>) where {Bar(T)}"),
+            term("impl Many(u8) where {}"),
+            term("impl Many(u16) where {}"),
+            term("impl Bar(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+fn goal(trait_name: &str) -> (Env, Wcs) {
+    let mut env = Env::default();
+    let var: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let goal: Wcs = TraitId::new(trait_name).with(var, ()).upcast();
+    (env, goal)
+}
+
+#[test]
+fn unique_clause_binds_variable() {
+    // `Unique` has one impl, so `?X` must be `Vec<_>` before any search starts;
+    // the impl's where-clause `Bar(_)` is left for the solver.
+    let (env, goal) = goal("Unique");
+    expect!["Constraints { env: Env { variables: [?ty_3, ?ty_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => Vec<?ty_3>, ?ty_2 => ?ty_3} }"].assert_eq(&format!(
+        "{:?}",
+        propagate(&decls(), &env, &Wcs::t(), &goal)
+    ));
+
+    // Either way, the solution for `?X` is `Vec<u32>`.
+    let with = prove_with_propagation(decls(), &env, (), &goal);
+    let without = prove(decls(), &env, (), &goal);
+    expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_3, ?ty_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => Vec<u32>, ?ty_2 => u32, ?ty_3 => u32} },
+        }
+    "#]].assert_eq(&with.to_string());
+    expect![[r#"
+        {
+          Constraints { env: Env { variables: [?ty_2, ?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => Vec<u32>, ?ty_2 => u32} },
+        }
+    "#]].assert_eq(&without.to_string());
+}
+
+#[test]
+fn several_clauses_bind_nothing() {
+    let (env, goal) = goal("Many");
+    expect!["Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {} }"].assert_eq(&format!(
+        "{:?}",
+        propagate(&decls(), &env, &Wcs::t(), &goal)
+    ));
+}
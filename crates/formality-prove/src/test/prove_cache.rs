@@ -0,0 +1,125 @@
+use formality_core::{test, CacheStats, Upcast};
+use formality_types::{
+    grammar::{ExistentialVar, ParameterKind, TraitId, Ty, Wcs},
+    rust::term,
+};
+
+use crate::{decls::Decls, AmbiguityPolicy, Env, ProveCache};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Foo<ty Self> where {}")],
+        impl_decls: vec![
+            term("impl Foo(u8) where {}"),
+            term("impl Foo(u16) where {}"),
+            term("impl Foo(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+fn query(cache: &mut ProveCache, goal: &str) {
+    let goal: Wcs = term(goal);
+    assert!(cache.prove(decls(), Env::default(), (), goal).is_proven());
+}
+
+#[test]
+fn exceeding_capacity_evicts_least_recently_used() {
+    let mut cache = ProveCache::new(2);
+
+    query(&mut cache, "{Foo(u8)}");
+    query(&mut cache, "{Foo(u16)}");
+    query(&mut cache, "{Foo(u32)}"); // evicts `Foo(u8)`
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 0,
+            misses: 3,
+            evictions: 1
+        }
+    );
+
+    query(&mut cache, "{Foo(u16)}"); // hit; `Foo(u32)` is now the oldest
+    query(&mut cache, "{Foo(u8)}"); // repopulated, evicting `Foo(u32)`
+    query(&mut cache, "{Foo(u8)}"); // hit
+    query(&mut cache, "{Foo(u16)}"); // still cached
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 3,
+            misses: 4,
+            evictions: 2
+        }
+    );
+}
+
+fn ambiguous_query(cache: &mut ProveCache, env: &Env, ty: impl Upcast<Ty>) -> bool {
+    let goal: Wcs = TraitId::new("Foo")
+        .with(ty.upcast(), ())
+        .is_local()
+        .upcast();
+    cache
+        .prove(decls(), env, (), goal)
+        .iter()
+        .any(|c| !c.known_true)
+}
+
+fn env_with_var() -> (Env, ExistentialVar) {
+    let mut env = Env::default();
+    let var = env.fresh_existential(ParameterKind::Ty);
+    (env, var)
+}
+
+#[test]
+fn ambiguous_results_not_cached_by_default() {
+    let (env, var) = env_with_var();
+    let mut cache = ProveCache::new(4);
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert_eq!(cache.stats().hits, 0);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn ambiguous_result_not_reused_after_variable_bound() {
+    let (env, var) = env_with_var();
+    let mut cache = ProveCache::new(4).with_ambiguity_policy(AmbiguityPolicy::UntilBound);
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert_eq!(cache.stats().hits, 1);
+
+    assert_eq!(cache.len(), 1);
+
+    // The client binds `var` to `u8`, invalidating the entry that mentions it;
+    // asking about `var` again solves the goal afresh.
+    let bound_ty: Ty = term("u8");
+    cache.variable_bound(var);
+    assert!(cache.is_empty());
+    assert!(!ambiguous_query(&mut cache, &env, &bound_ty));
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 3,
+            evictions: 0
+        }
+    );
+}
+
+#[test]
+fn ambiguous_result_reused_only_within_query() {
+    let (env, var) = env_with_var();
+    let mut cache = ProveCache::new(4).with_ambiguity_policy(AmbiguityPolicy::WithinQuery);
+    query(&mut cache, "{Foo(u8)}");
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert_eq!(cache.stats().hits, 1);
+
+    // Unambiguous results outlive the query.
+    cache.end_query();
+    assert_eq!(cache.len(), 1);
+    assert!(ambiguous_query(&mut cache, &env, var));
+    assert_eq!(cache.stats().hits, 1);
+}
@@ -0,0 +1,65 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::{
+    grammar::{Ty, Wcs},
+    rust::term,
+};
+
+use crate::{decls::Decls, prove::Env, prove_normalized_eq};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait IntoIterator<ty Self> where {}")],
+        impl_decls: vec![term("impl<ty T> IntoIterator(Vec<T>) where {}")],
+        alias_eq_decls: vec![term(
+            "alias <ty T> <Vec<T> as IntoIterator>::Item = T where {}",
+        )],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn projection_normalizes_to_u32() {
+    let a: Ty = term("<Vec<u32> as IntoIterator>::Item");
+    let b: Ty = term("u32");
+    prove_normalized_eq(decls(), Env::default(), Wcs::t(), a, b).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn scalars_mismatch_structurally() {
+    let a: Ty = term("u32");
+    let b: Ty = term("i32");
+    prove_normalized_eq(decls(), Env::default(), Wcs::t(), a, b).assert_err(expect![[r#"
+        judgment `prove_normalized_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait IntoIterator <ty> ], [impl <ty> IntoIterator(Vec<^ty0_0>)], [], [alias <ty> <Vec<^ty0_0> as IntoIterator>::Item = ^ty0_0], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "normalize-then-compare" failed at step #5 (src/file.rs:LL:CC) because
+            judgment `prove_structurally_eq { a: u32, b: i32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait IntoIterator <ty> ], [impl <ty> IntoIterator(Vec<^ty0_0>)], [], [alias <ty> <Vec<^ty0_0> as IntoIterator>::Item = ^ty0_0], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "rigid" failed at step #0 (src/file.rs:LL:CC) because
+                condition evaluted to false: `a.name == b.name`"#]]);
+}
+
+#[test]
+fn projection_does_not_normalize() {
+    let a: Ty = term("<u32 as IntoIterator>::Item");
+    let b: Ty = term("u32");
+    prove_normalized_eq(decls(), Env::default(), Wcs::t(), a, b).assert_err(expect![[r#"
+        judgment `prove_normalized_eq { a: <u32 as IntoIterator>::Item, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait IntoIterator <ty> ], [impl <ty> IntoIterator(Vec<^ty0_0>)], [], [alias <ty> <Vec<^ty0_0> as IntoIterator>::Item = ^ty0_0], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "normalize-then-compare" failed at step #5 (src/file.rs:LL:CC) because
+            judgment had no applicable rules: `prove_structurally_eq { a: <u32 as IntoIterator>::Item, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait IntoIterator <ty> ], [impl <ty> IntoIterator(Vec<^ty0_0>)], [], [alias <ty> <Vec<^ty0_0> as IntoIterator>::Item = ^ty0_0], [], [], {}, {}) }`"#]]);
+}
+
+/// `<u32 as IntoIterator>::Item` cannot be normalized, so it is rigid: equal to
+/// the same alias on a type equal to `u32`.
+#[test]
+fn rigid_projections_compare_structurally() {
+    let a: Ty = term("<u32 as IntoIterator>::Item");
+    let b: Ty = term("<<Vec<u32> as IntoIterator>::Item as IntoIterator>::Item");
+    prove_normalized_eq(decls(), Env::default(), Wcs::t(), a, b).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
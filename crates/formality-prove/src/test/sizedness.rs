@@ -0,0 +1,122 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Pointee<ty Self> where {}"),
+            term("trait MetaSized<ty Self> where {Pointee(Self)}"),
+            term("trait Sized<ty Self> where {MetaSized(Self)}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn slice_is_meta_sized() {
+    test_prove(decls(), term("{} => {MetaSized([u8])}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn slice_is_not_sized() {
+    test_prove(decls(), term("{} => {Sized([u8])}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Sized([u8])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Sized([u8]), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: MetaSized(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: Pointee(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`"#]]);
+}
+
+#[test]
+fn everything_is_pointee() {
+    test_prove(decls(), term("{} => {Pointee([[u8]])}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn tuple_takes_sizedness_of_last_element() {
+    test_prove(decls(), term("{} => {MetaSized((u32, [u8]))}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+    test_prove(decls(), term("{} => {MetaSized(([u8], u32))}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {MetaSized(([u8], u32))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: MetaSized(([u8], u32)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "built-in sizedness" failed at step #1 (src/file.rs:LL:CC) because
+                judgment `prove_wc_list { goal: {MetaSized(u32), Sized([u8])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "some" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Sized([u8])}, assumptions: {}, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {Sized([u8])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: Sized([u8]), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: MetaSized(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+                              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: Pointee(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                judgment had no applicable rules: `prove_via { goal: MetaSized(([u8], u32)), via: Pointee(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+              the rule "trait implied bound" failed at step #4 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => ([u8], u32)} }, goal: {Sized(?ty_1)}, assumptions: {}, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {Sized(([u8], u32))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Sized(([u8], u32)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "built-in sizedness" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `prove_wc_list { goal: {Sized(u32), Sized([u8])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "some" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Sized([u8])}, assumptions: {}, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc_list { goal: {Sized([u8])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment `prove_wc { goal: Sized([u8]), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                            judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: MetaSized(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+                                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                            judgment had no applicable rules: `prove_via { goal: Sized([u8]), via: Pointee(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_via { goal: Sized(([u8], u32)), via: MetaSized(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_via { goal: Sized(([u8], u32)), via: Pointee(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Pointee <ty> , trait MetaSized <ty> where {Pointee(^ty0_0)}, trait Sized <ty> where {MetaSized(^ty0_0)}], [], [], [], [], [], {}, {}) }`"#]]);
+}
+
+#[test]
+fn sized_parameter_is_meta_sized() {
+    test_prove(decls(), term("forall<ty T> {Sized(T)} => {MetaSized(T)}"))
+        .assert_ok(expect![[r#"
+            {
+              Constraints { env: Env { variables: [!ty_1], bias: Soundness }, known_true: true, substitution: {} },
+            }
+        "#]]);
+}
+
+#[test]
+fn without_meta_sized_scalars_need_impls() {
+    // The built-in rules are opt-in: a program that only knows `Sized` is unaffected.
+    let decls = Decls {
+        trait_decls: vec![term("trait Sized<ty Self> where {}")],
+        ..Decls::empty()
+    };
+    test_prove(decls, term("{} => {Sized(u32)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Sized(u32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Sized(u32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
@@ -0,0 +1,151 @@
+use formality_core::{
+    fixed_point::with_iteration_limit,
+    judgment::{with_fuel, FailedRule, RuleFailureCause},
+    set, test, ProvenSet,
+};
+use formality_types::rust::term;
+
+use crate::{decls::Decls, test_util::test_prove, Constraints, SolveResult, Trivalent};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Foo<ty Self> where {}")],
+        impl_decls: vec![term("impl Foo(u32) where {}")],
+        ..Decls::empty()
+    }
+}
+
+fn classify(assertion: &str) -> SolveResult {
+    SolveResult::of(&test_prove(decls(), term(assertion)))
+}
+
+#[test]
+fn proven_is_true() {
+    let result = classify("{} => {Foo(u32)}");
+    assert_eq!(result, SolveResult::Proven);
+    assert_eq!(result.truth(), Trivalent::True);
+    assert!(result.is_definitely_true());
+    assert!(!result.is_definitely_false());
+}
+
+#[test]
+fn disproven_is_false() {
+    let result = classify("{} => {Foo(i32)}");
+    assert_eq!(result, SolveResult::Disproven);
+    assert_eq!(result.truth(), Trivalent::False);
+    assert!(!result.is_definitely_true());
+    assert!(result.is_definitely_false());
+}
+
+#[test]
+fn ambiguous_is_unknown() {
+    let result = classify("exists<ty X> {} => {@IsLocal(Foo(X))}");
+    assert_eq!(result, SolveResult::Ambiguous);
+    assert_eq!(result.truth(), Trivalent::Unknown);
+    assert!(!result.is_definitely_true());
+    assert!(!result.is_definitely_false());
+}
+
+#[test]
+fn overflow_is_unknown() {
+    let overflow: ProvenSet<Constraints> = ProvenSet::failed_rules(
+        "prove",
+        set![FailedRule::new(RuleFailureCause::Overflow { limit: 1 })],
+    );
+    let result = SolveResult::of(&overflow);
+    assert_eq!(result, SolveResult::Overflow);
+    assert_eq!(result.truth(), Trivalent::Unknown);
+    assert!(!result.is_definitely_true());
+    assert!(!result.is_definitely_false());
+}
+
+#[test]
+fn out_of_fuel_is_overflow() {
+    let (result, remaining) = with_fuel(10, || classify("{} => {Foo(u32), Foo(u32)}"));
+    assert_eq!(remaining, 0);
+    assert_eq!(result, SolveResult::Overflow);
+}
+
+/// Running out of fuel after finding some of the solutions of a goal with many
+/// (here, one per impl of `Foo`) is not a proof, as the rest are unknown.
+#[test]
+fn running_out_of_fuel_is_never_proven() {
+    let decls = Decls {
+        impl_decls: vec![
+            term("impl Foo(u32) where {}"),
+            term("impl Foo(i32) where {}"),
+        ],
+        ..decls()
+    };
+    let classify = |budget| {
+        with_fuel(budget, || {
+            SolveResult::of(&test_prove(
+                decls.clone(),
+                term("exists<ty T> {} => {Foo(T)}"),
+            ))
+        })
+    };
+    let (result, remaining) = classify(1000);
+    assert_eq!(result, SolveResult::Proven);
+    for budget in 0..1000 - remaining {
+        assert_eq!(classify(budget).0, SolveResult::Overflow, "budget {budget}");
+    }
+}
+
+#[test]
+fn fuel_to_spare() {
+    let (result, remaining) = with_fuel(1000, || classify("{} => {Foo(u32)}"));
+    assert_eq!(result, SolveResult::Proven);
+    assert!(remaining > 0);
+}
+
+/// `Foo(Vec<T>)` needs `Foo(Vec<Vec<T>>)` and so on, until the goal is too
+/// large and the solver gives up.
+fn expanding_decls() -> Decls {
+    Decls {
+        max_size: 10,
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {}"),
+            term("trait Bar<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl<ty T> Foo(Vec<T>) where {Foo(Vec<Vec<T>>)}"),
+            term("impl Bar(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn overflow_in_one_conjunct_is_overflow() {
+    let result = test_prove(expanding_decls(), term("{} => {Bar(u32), Foo(Vec<u32>)}"));
+    assert!(result.iter().all(|c| c.overflow));
+    assert_eq!(SolveResult::of(&result), SolveResult::Overflow);
+}
+
+#[test]
+fn genuine_ambiguity_in_one_conjunct_is_ambiguous() {
+    let result = classify("exists<ty X> {} => {Foo(u32), @IsLocal(Foo(X))}");
+    assert_eq!(result, SolveResult::Ambiguous);
+}
+
+/// A proof found in spite of a subgoal that gave up at a limit may be only one
+/// of the solutions, so it is not a proof either.
+#[test]
+fn proof_past_a_limit_is_overflow() {
+    let decls = Decls {
+        trait_decls: vec![term("trait Foo<ty Self> where {}")],
+        impl_decls: vec![
+            term("impl<ty T> Foo(T) where {Foo(T)}"),
+            term("impl Foo(u32) where {}"),
+        ],
+        ..Decls::empty()
+    };
+    let classify = |limit| {
+        with_iteration_limit(limit, || {
+            SolveResult::of(&test_prove(decls.clone(), term("{} => {Foo(u32)}")))
+        })
+    };
+    assert_eq!(classify(1), SolveResult::Overflow);
+    assert_eq!(classify(10), SolveResult::Proven);
+}
@@ -0,0 +1,72 @@
+use expect_test::expect;
+use formality_core::fixed_point::with_stats;
+use formality_core::judgment::{with_failure_collector, FailureCollector};
+use formality_macros::test;
+use formality_types::grammar::Wcs;
+use formality_types::rust::term;
+
+use crate::{
+    decls::Decls,
+    prove::{prove, with_solver_configuration, SolverConfiguration},
+};
+
+/// Proving `Foo(u32)` or `Bar(u32)` requires proving `Baz(u32)`, the
+/// where-clause of their traits, under the same assumptions.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Foo<ty Self> where {Baz(Self)}"),
+            term("trait Bar<ty Self> where {Baz(Self)}"),
+            term("trait Baz<ty Self> where {}"),
+        ],
+        impl_decls: vec![
+            term("impl Foo(u32) where {}"),
+            term("impl Bar(u32) where {}"),
+            term("impl Baz(u32) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+/// The number of `prove_wc` goals evaluated while proving `goal`.
+fn prove_wc_evaluations(goal: &str) -> u64 {
+    let goal: Wcs = term(goal);
+    let (result, stats) = with_stats(|| prove(decls(), (), (), goal));
+    assert!(result.is_proven());
+    stats["prove_wc"].cache_misses
+}
+
+/// With the memo, proving both goals at once reuses the solution of
+/// `Baz(u32)` from the first conjunct.
+#[test]
+fn shared_subgoal_solved_once() {
+    with_solver_configuration(SolverConfiguration::Memoized, || {
+        let foo = prove_wc_evaluations("{Foo(u32)}");
+        let bar = prove_wc_evaluations("{Bar(u32)}");
+        let both = prove_wc_evaluations("{Foo(u32), Bar(u32)}");
+        expect!["foo: 19, bar: 19, both: 36"]
+            .assert_eq(&format!("foo: {foo}, bar: {bar}, both: {both}"));
+    });
+}
+
+/// By default, `Baz(u32)` is solved again for the second conjunct.
+#[test]
+fn shared_subgoal_solved_twice_by_default() {
+    let foo = prove_wc_evaluations("{Foo(u32)}");
+    let bar = prove_wc_evaluations("{Bar(u32)}");
+    let both = prove_wc_evaluations("{Foo(u32), Bar(u32)}");
+    expect!["foo: 28, bar: 28, both: 55"]
+        .assert_eq(&format!("foo: {foo}, bar: {bar}, both: {both}"));
+}
+
+/// A failure collector sees the rules of every subgoal, so the memo is not
+/// used while one is installed.
+#[test]
+fn memo_bypassed_while_collecting_failures() {
+    with_solver_configuration(SolverConfiguration::Memoized, || {
+        with_failure_collector(&FailureCollector::new(), || {
+            let both = prove_wc_evaluations("{Foo(u32), Bar(u32)}");
+            expect!["both: 55"].assert_eq(&format!("both: {both}"));
+        })
+    });
+}
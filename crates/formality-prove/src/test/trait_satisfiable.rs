@@ -0,0 +1,84 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::{grammar::TraitId, rust::term};
+
+use crate::{decls::Decls, trait_satisfiable};
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait Red<ty Self> where {}"),
+            term("trait Blue<ty Self> where {}"),
+            term("trait Paint<ty Self> where {Red(Self)}"),
+            term("trait Purple<ty Self> where {Red(Self), Blue(Self)}"),
+        ],
+        impl_decls: vec![
+            term("impl Red(u32) where {}"),
+            term("impl Blue(u8) where {}"),
+        ],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn satisfiable_where_clauses() {
+    let trait_id: TraitId = term("Paint");
+    trait_satisfiable(decls(), trait_id).assert_ok(expect![[r#"
+        {
+          (),
+        }
+    "#]]);
+}
+
+#[test]
+fn contradictory_where_clauses() {
+    // No type is both `Red` and `Blue`.
+    let trait_id: TraitId = term("Purple");
+    trait_satisfiable(decls(), trait_id).assert_err(expect![[r#"
+        judgment `trait_satisfiable { trait_id: Purple, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "satisfiable" failed at step #4 (src/file.rs:LL:CC) because
+            judgment `prove_wc_list { goal: {Blue(?ty_0), Red(?ty_0)}, assumptions: {}, env: Env { variables: [?ty_0], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "some" failed at step #1 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_0], bias: Soundness }, known_true: true, substitution: {?ty_0 => u8} }, goal: {Red(?ty_0)}, assumptions: {}, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {Red(u8)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Red(u8), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "positive impl" failed at step #5 (src/file.rs:LL:CC) because
+                            judgment `prove_wc_list { goal: {u8 = u32}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_wc { goal: u8 = u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_eq { a: u8, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_normalize { p: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment `prove_eq { a: u32, b: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                          the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                            judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                                          the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                            cyclic proof attempt: `prove_eq { a: u8, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`, reached through:
+                                              `prove_eq { a: u32, b: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                            judgment had no applicable rules: `prove_via { goal: Red(u8), via: Blue(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #4 (src/file.rs:LL:CC) because
+                            judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u8} }, goal: {Paint(?ty_1)}, assumptions: {}, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_wc_list { goal: {Paint(u8)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc { goal: Paint(u8), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                      the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_via { goal: Paint(u8), via: Blue(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                                      the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_via { goal: Paint(u8), via: Red(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                          the rule "trait implied bound" failed at step #4 (src/file.rs:LL:CC) because
+                            judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u8} }, goal: {Purple(?ty_1)}, assumptions: {}, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_wc_list { goal: {Purple(u8)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc { goal: Purple(u8), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                      the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_via { goal: Purple(u8), via: Blue(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`
+                                      the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_via { goal: Purple(u8), via: Red(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Red <ty> , trait Blue <ty> , trait Paint <ty> where {Red(^ty0_0)}, trait Purple <ty> where {Blue(^ty0_0), Red(^ty0_0)}], [impl Red(u32), impl Blue(u8)], [], [], [], [], {}, {}) }`"#]]);
+}
@@ -0,0 +1,63 @@
+use expect_test::expect;
+use formality_core::test;
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+/// `Ord` has the supertrait `PartialOrd`, which only `u32` implements.
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![
+            term("trait PartialOrd<ty Self> where {}"),
+            term("trait Ord<ty Self> where {PartialOrd(Self)}"),
+        ],
+        impl_decls: vec![term("impl PartialOrd(u32) where {}")],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn well_formed_trait_ref() {
+    test_prove(decls(), term("{} => {@WellFormedTraitRef(Ord(u32))}")).assert_ok(expect![[r#"
+        {
+          Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} },
+        }
+    "#]]);
+}
+
+#[test]
+fn unsatisfiable_supertrait_is_not_well_formed() {
+    test_prove(decls(), term("{} => {@WellFormedTraitRef(Ord(u64))}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {@ WellFormedTraitRef(Ord(u64))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: @ WellFormedTraitRef(Ord(u64)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait well formed" failed at step #2 (src/file.rs:LL:CC) because
+                judgment `prove_wc_list { goal: {PartialOrd(u64)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_wc { goal: PartialOrd(u64), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                      the rule "positive impl" failed at step #5 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {u64 = u32}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: u64 = u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_eq { a: u64, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment had no applicable rules: `prove_normalize { p: u64, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }`
+                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_eq { a: u32, b: u64, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }`
+                                      the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                        cyclic proof attempt: `prove_eq { a: u64, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }`, reached through:
+                                          `prove_eq { a: u32, b: u64, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }`
+                      the rule "trait implied bound" failed at step #4 (src/file.rs:LL:CC) because
+                        judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u64} }, goal: {Ord(?ty_1)}, assumptions: {}, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                          the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `prove_wc_list { goal: {Ord(u64)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_wc { goal: Ord(u64), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }` failed at the following rule(s):
+                                  the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                                    judgment had no applicable rules: `prove_via { goal: Ord(u64), via: PartialOrd(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait PartialOrd <ty> , trait Ord <ty> where {PartialOrd(^ty0_0)}], [impl PartialOrd(u32)], [], [], [], [], {}, {}) }`"#]]);
+}
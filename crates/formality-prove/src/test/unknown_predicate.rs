@@ -0,0 +1,47 @@
+use expect_test::expect;
+use formality_core::{judgment::RuleFailureCause, test};
+use formality_types::rust::term;
+
+use crate::decls::Decls;
+
+use crate::test_util::test_prove;
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Debug<ty Self> where {}")],
+        ..Decls::empty()
+    }
+}
+
+#[test]
+fn undeclared_trait() {
+    let result = test_prove(decls(), term("{} => {Dbug(u32)}"));
+    result.assert_err(expect![[r#"
+        judgment `prove { goal: {Dbug(u32)} }` failed at the following rule(s):
+          failed at (src/file.rs:LL:CC) because
+            `trait Dbug` is not declared"#]]);
+
+    let failure = result.check_proven().unwrap_err();
+    assert!(failure
+        .failed_rules
+        .iter()
+        .all(|r| matches!(r.cause, RuleFailureCause::UnknownPredicate { .. })));
+}
+
+#[test]
+fn undeclared_trait_under_binder() {
+    test_prove(decls(), term("{} => {for<ty T> if {Debug(T)} Dbug(T)}")).assert_err(expect![[r#"
+        judgment `prove { goal: {for <ty> if {Debug(^ty0_0)} Dbug(^ty0_0)} }` failed at the following rule(s):
+          failed at (src/file.rs:LL:CC) because
+            `trait Dbug` is not declared"#]]);
+}
+
+#[test]
+fn declared_trait_without_impl() {
+    test_prove(decls(), term("{} => {Debug(u32)}")).assert_err(expect![[r#"
+        judgment `prove_wc_list { goal: {Debug(u32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Debug(u32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Debug <ty> ], [], [], [], [], [], {}, {}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
@@ -0,0 +1,99 @@
+use formality_core::test;
+use formality_types::grammar::{Wc, Wcs};
+use formality_types::rust::term;
+
+use crate::decls::{Decls, ImplDecl};
+use crate::prove::{verify_proof, Env, ProofError, ProofStep, ProofTree};
+
+fn vec_impl() -> ImplDecl {
+    term("impl<ty T> Debug(Vec<T>) where {Debug(T)}")
+}
+
+fn u32_impl() -> ImplDecl {
+    term("impl Debug(u32) where {}")
+}
+
+fn decls() -> Decls {
+    Decls {
+        trait_decls: vec![term("trait Debug<ty Self> where {}")],
+        impl_decls: vec![vec_impl(), u32_impl()],
+        ..Decls::empty()
+    }
+}
+
+fn impl_step(goal: &str, impl_decl: ImplDecl, args: &str, premises: Vec<ProofTree>) -> ProofTree {
+    ProofTree {
+        goal: term(goal),
+        step: ProofStep::Impl {
+            impl_decl,
+            args: term(args),
+        },
+        premises,
+    }
+}
+
+#[test]
+fn valid_proof_verifies() {
+    let tree = impl_step(
+        "Debug(Vec<u32>)",
+        vec_impl(),
+        "[u32]",
+        vec![impl_step("Debug(u32)", u32_impl(), "[]", vec![])],
+    );
+    assert_eq!(
+        verify_proof(decls(), Env::default(), Wcs::t(), &tree),
+        Ok(())
+    );
+}
+
+/// The premise proves `Debug(u32)`, but the impl instantiated with `i32`
+/// requires `Debug(i32)`.
+#[test]
+fn wrong_premise_is_rejected() {
+    let tree = impl_step(
+        "Debug(Vec<i32>)",
+        vec_impl(),
+        "[i32]",
+        vec![impl_step("Debug(u32)", u32_impl(), "[]", vec![])],
+    );
+    assert_eq!(
+        verify_proof(decls(), Env::default(), Wcs::t(), &tree),
+        Err(ProofError::WrongPremises {
+            goal: term("Debug(Vec<i32>)"),
+            expected: term("{Debug(i32)}"),
+            found: term("{Debug(u32)}"),
+        })
+    );
+}
+
+#[test]
+fn unmade_assumption_is_rejected() {
+    let goal: Wc = term("Debug(i32)");
+    let tree = ProofTree {
+        goal: goal.clone(),
+        step: ProofStep::Assumption,
+        premises: vec![],
+    };
+    assert_eq!(
+        verify_proof(decls(), Env::default(), Wcs::t(), &tree),
+        Err(ProofError::NotAnAssumption { goal: goal.clone() })
+    );
+    assert_eq!(verify_proof(decls(), Env::default(), goal, &tree), Ok(()));
+}
+
+/// The impl exists, but `decls` do not declare its trait, so the proof is
+/// rejected rather than the verifier panicking.
+#[test]
+fn undeclared_trait_is_rejected() {
+    let decls = Decls {
+        trait_decls: vec![],
+        ..decls()
+    };
+    let tree = impl_step("Debug(u32)", u32_impl(), "[]", vec![]);
+    assert_eq!(
+        verify_proof(decls, Env::default(), Wcs::t(), &tree),
+        Err(ProofError::IllFormedTraitRef {
+            goal: term("Debug(u32)")
+        })
+    );
+}
@@ -1,13 +1,16 @@
 use std::sync::Arc;
 
-use formality_core::{term, Upcast};
-use formality_prove::Safety;
+use formality_core::{
+    parse::{ActiveVariant, CoreParse, ParseResult, Parser, Scope},
+    term, Set, Upcast,
+};
+use formality_prove::{Defaultness, Fundamentalness, Safety};
 use formality_types::{
     grammar::{
         AdtId, AliasTy, AssociatedItemId, Binder, Const, ConstData, CrateId, Fallible, FieldId,
         FnId, Lt, Parameter, Relation, TraitId, TraitRef, Ty, Wc, Wcs,
     },
-    rust::Term,
+    rust::{FormalityLang, Term},
 };
 
 use crate::grammar::mir::MirFnBody;
@@ -26,6 +29,17 @@ impl Program {
         self.crates.iter().flat_map(|c| &c.items)
     }
 
+    /// All ADTs declared in any crate, as [`Adt`]s.
+    pub fn adts_from_all_crates(&self) -> impl Iterator<Item = Adt> + '_ {
+        self.items_from_all_crates()
+            .filter_map(|crate_item| match crate_item {
+                CrateItem::Struct(s) => Some(s.to_adt()),
+                CrateItem::Enum(e) => Some(e.to_adt()),
+                CrateItem::Union(u) => Some(u.to_adt()),
+                _ => None,
+            })
+    }
+
     pub fn trait_named(&self, trait_id: &TraitId) -> Fallible<&Trait> {
         let mut traits: Vec<&Trait> = self
             .items_from_all_crates()
@@ -50,6 +64,13 @@ pub struct Crate {
     pub items: Vec<CrateItem>,
 }
 
+// FIXME: type-alias-impl-trait items (`type Foo = impl Trait;`) are not modeled.
+// Lowering one would give an `OpaqueTyDecl` for its bounds and a
+// `DefiningScope` holding the hidden type of each defining use, to be checked
+// with `formality_prove::prove_hidden_types`. But finding the defining uses,
+// and their hidden types, needs an opaque type in `TyData` and a type check of
+// MIR fn bodies, neither of which exists yet (`MirFnBody` is parsed but never
+// checked).
 #[term]
 pub enum CrateItem {
     #[cast]
@@ -57,6 +78,8 @@ pub enum CrateItem {
     #[cast]
     Enum(Enum),
     #[cast]
+    Union(Union),
+    #[cast]
     Trait(Trait),
     #[cast]
     TraitImpl(TraitImpl),
@@ -79,8 +102,9 @@ pub struct TestBoundData {
     pub goals: Vec<WhereClause>,
 }
 
-#[term(struct $id $binder)]
+#[term($?fundamentalness struct $id $binder)]
 pub struct Struct {
+    pub fundamentalness: Fundamentalness,
     pub id: AdtId,
     pub binder: Binder<StructBoundData>,
 }
@@ -90,15 +114,18 @@ impl Struct {
         let (
             vars,
             StructBoundData {
+                defaults,
                 where_clauses,
                 fields,
             },
         ) = self.binder.open();
         Adt {
+            fundamentalness: self.fundamentalness.clone(),
             id: self.id.clone(),
             binder: Binder::new(
                 vars,
                 AdtBoundData {
+                    defaults,
                     where_clauses,
                     variants: vec![Variant {
                         name: VariantId::for_struct(),
@@ -110,12 +137,22 @@ impl Struct {
     }
 }
 
-#[term($:where $,where_clauses { $,fields })]
+#[term($:default $,defaults $:where $,where_clauses { $,fields })]
 pub struct StructBoundData {
+    pub defaults: Vec<DefaultArg>,
     pub where_clauses: Vec<WhereClause>,
     pub fields: Vec<Field>,
 }
 
+/// A default for one of an ADT's generic parameters, e.g., `U = Vec<T>`
+/// for `struct S<T, U = Vec<T>>`. The default may only refer to the parameters
+/// declared before `param`.
+#[term($param = $value)]
+pub struct DefaultArg {
+    pub param: Parameter,
+    pub value: Parameter,
+}
+
 #[term($name : $ty)]
 pub struct Field {
     pub name: FieldName,
@@ -148,22 +185,44 @@ pub struct Enum {
 impl Enum {
     pub fn to_adt(&self) -> Adt {
         Adt {
+            fundamentalness: Fundamentalness::NotFundamental,
             id: self.id.clone(),
             binder: self.binder.clone(),
         }
     }
 }
 
-/// Not directly part of the grammar, but structs/enums
+/// A union has a single variant, like a struct, but only one of its fields is
+/// live at a time. Since fields are never dropped, each must be `Copy`.
+#[term(union $id $binder)]
+pub struct Union {
+    pub id: AdtId,
+    pub binder: Binder<StructBoundData>,
+}
+
+impl Union {
+    pub fn to_adt(&self) -> Adt {
+        Struct {
+            fundamentalness: Fundamentalness::NotFundamental,
+            id: self.id.clone(),
+            binder: self.binder.clone(),
+        }
+        .to_adt()
+    }
+}
+
+/// Not directly part of the grammar, but structs/enums/unions
 /// can be converted to this.
-#[term(adt $id $binder)]
+#[term($?fundamentalness adt $id $binder)]
 pub struct Adt {
+    pub fundamentalness: Fundamentalness,
     pub id: AdtId,
     pub binder: Binder<AdtBoundData>,
 }
 
-#[term($:where $,where_clauses { $,variants })]
+#[term($:default $,defaults $:where $,where_clauses { $,variants })]
 pub struct AdtBoundData {
+    pub defaults: Vec<DefaultArg>,
     pub where_clauses: Vec<WhereClause>,
     pub variants: Vec<Variant>,
 }
@@ -174,13 +233,25 @@ pub struct Variant {
     pub fields: Vec<Field>,
 }
 
-#[term($?safety trait $id $binder)]
+#[term($?safety $?autoness trait $id $binder)]
 pub struct Trait {
     pub safety: Safety,
+    pub autoness: Autoness,
     pub id: TraitId,
     pub binder: TraitBinder<TraitBoundData>,
 }
 
+/// Mark a trait as an `auto` trait. Every ADT implements an auto trait if all
+/// of its fields do, unless it has its own (positive or negative) impl. Arrays
+/// implement it if their element type does, or if they are empty.
+#[term]
+#[derive(Default)]
+pub enum Autoness {
+    #[default]
+    NotAuto,
+    Auto,
+}
+
 // NB: TraitBinder is a manually implemented Term
 // that binds the `Self` variable.
 #[derive(Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -256,8 +327,9 @@ pub struct AssociatedTyBoundData {
     pub where_clauses: Vec<WhereClause>,
 }
 
-#[term($?safety impl $binder)]
+#[term($?defaultness $?safety impl $binder)]
 pub struct TraitImpl {
+    pub defaultness: Defaultness,
     pub safety: Safety,
     pub binder: Binder<TraitImplBoundData>,
 }
@@ -341,6 +413,7 @@ impl WhereClause {
                     .not_implemented()
                     .upcast(),
             ),
+            WhereClauseData::IsImplementedWith(..) => None,
             WhereClauseData::AliasEq(_, _) => None,
             WhereClauseData::Outlives(_, _) => None,
             WhereClauseData::ForAll(binder) => {
@@ -349,6 +422,7 @@ impl WhereClause {
                 Some(Wc::for_all(&vars, wc))
             }
             WhereClauseData::TypeOfConst(_, _) => None,
+            WhereClauseData::MaybeSized(_) => None,
         }
     }
 
@@ -357,6 +431,17 @@ impl WhereClause {
             WhereClauseData::IsImplemented(self_ty, trait_id, parameters) => {
                 trait_id.with(self_ty, parameters).well_formed().upcast()
             }
+            WhereClauseData::IsImplementedWith(self_ty, trait_id, args) => {
+                let trait_wf: Wc = trait_id
+                    .with(self_ty, &args.parameters)
+                    .well_formed()
+                    .upcast();
+                let binding_wfs = args.bindings.iter().map(|binding| {
+                    let ty_param: Parameter = (&binding.ty).upcast();
+                    ty_param.well_formed().upcast()
+                });
+                std::iter::once(trait_wf).chain(binding_wfs).collect()
+            }
             WhereClauseData::AliasEq(alias_ty, ty) => {
                 let alias_param: Parameter = alias_ty.upcast();
                 let ty_param: Parameter = ty.upcast();
@@ -390,7 +475,7 @@ impl WhereClause {
                     ConstData::Value(_, t) => {
                         wcs.push(Relation::equals(ty, t));
                     }
-                    ConstData::Variable(_) => {}
+                    ConstData::Variable(_) | ConstData::Op(..) => {}
                 }
                 // FIXME(oli-obk): prove that there is no `TypeOfConst` bound for a different type.
                 let ct_param: Parameter = ct.upcast();
@@ -399,6 +484,7 @@ impl WhereClause {
                 wcs.push(ty_param.well_formed());
                 wcs.into_iter().map(|r| r.upcast()).collect()
             }
+            WhereClauseData::MaybeSized(ty) => ty.well_formed().upcast(),
         }
     }
 }
@@ -408,6 +494,11 @@ pub enum WhereClauseData {
     #[grammar($v0 : $v1 $<?v2>)]
     IsImplemented(Ty, TraitId, Vec<Parameter>),
 
+    /// `T: Trait<P, Item = U>`, which requires `T: Trait<P>` and also
+    /// constrains the associated type `<T as Trait<P>>::Item` to be `U`.
+    #[grammar($v0 : $v1 $v2)]
+    IsImplementedWith(Ty, TraitId, TraitBoundArgs),
+
     #[grammar($v0 => $v1)]
     AliasEq(AliasTy, Ty),
 
@@ -419,6 +510,82 @@ pub enum WhereClauseData {
 
     #[grammar(type_of_const $v0 is $v1)]
     TypeOfConst(Const, Ty),
+
+    /// Opts the given type parameter out of the implicit `Sized` bound
+    /// (see [`crate::sized`]).
+    #[grammar($v0 : ?Sized)]
+    MaybeSized(Ty),
+}
+
+/// The generic arguments of a trait bound that constrains at least one of the
+/// trait's associated types, e.g. the `<Item = u32>` in `I: Iterator<Item = u32>`.
+/// Bounds without associated type bindings are [`WhereClauseData::IsImplemented`].
+#[term(< $,parameters $,bindings >)]
+#[customize(parse, debug)]
+pub struct TraitBoundArgs {
+    pub parameters: Vec<Parameter>,
+    pub bindings: Vec<AssociatedTyBinding>,
+}
+
+impl CoreParse<FormalityLang> for TraitBoundArgs {
+    fn parse<'t>(scope: &Scope<FormalityLang>, text: &'t str) -> ParseResult<'t, Self> {
+        Parser::single_variant(scope, text, "TraitBoundArgs", |p| {
+            p.expect_char('<')?;
+
+            // As in Rust, the trait's own parameters come first and the
+            // associated type bindings last.
+            let mut parameters = vec![];
+            while !starts_binding(p) {
+                parameters.push(p.nonterminal()?);
+                p.expect_char(',')?;
+            }
+            let bindings = p.comma_nonterminal()?;
+
+            p.expect_char('>')?;
+            Ok(TraitBoundArgs {
+                parameters,
+                bindings,
+            })
+        })
+    }
+}
+
+/// True if the input continues with `Id =`, the start of an [`AssociatedTyBinding`].
+fn starts_binding(p: &ActiveVariant<'_, '_, FormalityLang>) -> bool {
+    p.reject(
+        |p| {
+            let _: AssociatedItemId = p.nonterminal()?;
+            p.expect_char('=')
+        },
+        |()| Set::new(),
+    )
+    .is_err()
+}
+
+impl std::fmt::Debug for TraitBoundArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let TraitBoundArgs {
+            parameters,
+            bindings,
+        } = self;
+        write!(f, "<")?;
+        for parameter in parameters {
+            write!(f, "{parameter:?}, ")?;
+        }
+        for (binding, i) in bindings.iter().zip(0..) {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{binding:?}")?;
+        }
+        write!(f, ">")
+    }
+}
+
+#[term($id = $ty)]
+pub struct AssociatedTyBinding {
+    pub id: AssociatedItemId,
+    pub ty: Ty,
 }
 
 #[term($data)]
@@ -5,5 +5,6 @@ use formality_types::rust::FormalityLang;
 
 pub mod grammar;
 pub mod prove;
+pub mod sized;
 mod test;
 mod trait_binder;
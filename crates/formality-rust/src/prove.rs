@@ -1,13 +1,14 @@
 use crate::grammar::{
     Adt, AdtBoundData, AssociatedTy, AssociatedTyBoundData, AssociatedTyValue,
-    AssociatedTyValueBoundData, Crate, CrateItem, ImplItem, NegTraitImpl, NegTraitImplBoundData,
-    Program, Trait, TraitBoundData, TraitImpl, TraitImplBoundData, TraitItem, WhereBound,
-    WhereBoundData, WhereClause, WhereClauseData,
+    AssociatedTyValueBoundData, Autoness, Crate, CrateItem, ImplItem, NegTraitImpl,
+    NegTraitImplBoundData, Program, Trait, TraitBoundData, TraitImpl, TraitImplBoundData,
+    TraitItem, WhereBound, WhereBoundData, WhereClause, WhereClauseData,
 };
 use formality_core::{seq, Set, To, Upcast, Upcasted};
 use formality_prove as prove;
 use formality_types::grammar::{
-    AdtId, AliasTy, Binder, BoundVar, ParameterKind, Predicate, Relation, TraitId, Ty, Wc, Wcs,
+    AdtId, AliasTy, AssociatedItemId, Binder, BoundVar, Const, Parameter, ParameterKind, Predicate,
+    Relation, RigidName, RigidTy, Scalar, ScalarId, TraitId, Ty, TyData, Variable, Wc, Wcs,
 };
 
 impl Program {
@@ -30,7 +31,76 @@ impl Program {
     }
 
     fn impl_decls(&self) -> Vec<prove::ImplDecl> {
-        self.crates.iter().flat_map(|c| c.impl_decls()).collect()
+        self.crates
+            .iter()
+            .flat_map(|c| c.impl_decls())
+            .chain(self.auto_trait_impl_decls())
+            .collect()
+    }
+
+    /// For each auto trait and each ADT without its own impl of that trait,
+    /// an impl stating that the ADT implements the trait if the fields of all
+    /// of its variants do. Arrays implement each auto trait if their element
+    /// type does, whatever their length, and empty arrays always do.
+    fn auto_trait_impl_decls(&self) -> Vec<prove::ImplDecl> {
+        let auto_trait_ids: Vec<&TraitId> = self
+            .items_from_all_crates()
+            .filter_map(|item| match item {
+                CrateItem::Trait(t) if t.autoness == Autoness::Auto => Some(&t.id),
+                _ => None,
+            })
+            .collect();
+
+        let mut decls = vec![];
+        for trait_id in auto_trait_ids {
+            for Adt { id, binder, .. } in self.adts_from_all_crates() {
+                if self.has_explicit_impl(trait_id, &id) {
+                    continue;
+                }
+                let (vars, AdtBoundData { variants, .. }) = binder.open();
+                let field_bounds: Wcs = variants
+                    .iter()
+                    .flat_map(|variant| &variant.fields)
+                    .map(|field| trait_id.with(&field.ty, ()))
+                    .upcasted()
+                    .collect();
+                let adt_ty = Ty::rigid(&id, &vars);
+                decls.push(prove::ImplDecl {
+                    defaultness: prove::Defaultness::Final,
+                    safety: prove::Safety::Safe,
+                    binder: Binder::new(
+                        &vars,
+                        prove::ImplDeclBoundData {
+                            trait_ref: trait_id.with(adt_ty, ()),
+                            where_clause: field_bounds,
+                        },
+                    ),
+                });
+            }
+            decls.extend(auto_trait_array_impl_decls(trait_id));
+        }
+        decls
+    }
+
+    /// True if some crate has a positive or negative impl of `trait_id` for the ADT `adt_id`.
+    fn has_explicit_impl(&self, trait_id: &TraitId, adt_id: &AdtId) -> bool {
+        let is_adt = |ty: &Ty| {
+            matches!(
+                ty.data(),
+                TyData::RigidTy(RigidTy { name: RigidName::AdtId(id), .. }) if id == adt_id
+            )
+        };
+        self.items_from_all_crates().any(|item| match item {
+            CrateItem::TraitImpl(i) => {
+                let data = i.binder.peek();
+                data.trait_id == *trait_id && is_adt(&data.self_ty)
+            }
+            CrateItem::NegTraitImpl(i) => {
+                let data = i.binder.peek();
+                data.trait_id == *trait_id && is_adt(&data.self_ty)
+            }
+            _ => false,
+        })
     }
 
     fn neg_impl_decls(&self) -> Vec<prove::NegImplDecl> {
@@ -43,7 +113,7 @@ impl Program {
     fn alias_eq_decls(&self) -> Vec<prove::AliasEqDecl> {
         self.crates
             .iter()
-            .flat_map(|c| c.alias_eq_decls())
+            .flat_map(|c| c.alias_eq_decls(self))
             .collect()
     }
 
@@ -54,6 +124,37 @@ impl Program {
             .collect()
     }
 
+    /// The where-clauses declared on the associated type `item_id` of `trait_id`,
+    /// instantiated with the given trait and associated type parameters.
+    /// Normalizing the associated type must prove these.
+    fn associated_ty_where_clauses(
+        &self,
+        trait_id: &TraitId,
+        item_id: &AssociatedItemId,
+        trait_parameters: &[Parameter],
+        assoc_parameters: &[impl Upcast<Parameter>],
+    ) -> Vec<WhereClause> {
+        // Ill-formed references to the trait are reported by the checker, not here.
+        let Ok(Trait { binder, .. }) = self.trait_named(trait_id) else {
+            return vec![];
+        };
+        let Ok(TraitBoundData { trait_items, .. }) = binder.instantiate_with(trait_parameters)
+        else {
+            return vec![];
+        };
+        trait_items
+            .iter()
+            .filter_map(|trait_item| match trait_item {
+                TraitItem::AssociatedTy(AssociatedTy { id, binder }) if id == item_id => binder
+                    .instantiate_with(assoc_parameters)
+                    .ok()
+                    .map(|data| data.where_clauses),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
     fn adt_decls(&self) -> Vec<prove::AdtDecl> {
         self.crates.iter().flat_map(|c| c.adt_decls()).collect()
     }
@@ -75,12 +176,45 @@ impl Program {
     }
 }
 
+/// `impl<ty T, const N> Trait([T; N]) where T: Trait` and `impl<ty T> Trait([T; 0])`
+/// for the auto trait `trait_id`.
+fn auto_trait_array_impl_decls(trait_id: &TraitId) -> Vec<prove::ImplDecl> {
+    let element = BoundVar::fresh(ParameterKind::Ty);
+    let len = BoundVar::fresh(ParameterKind::Const);
+    let empty = Const::valtree(Scalar::new(0), ScalarId::Usize);
+    let array_ty = |len: Parameter| Ty::rigid(RigidName::Array, seq![element.upcast(), len]);
+    let impl_decl = |vars: Vec<Variable>, len: Parameter, where_clause: Wcs| prove::ImplDecl {
+        defaultness: prove::Defaultness::Final,
+        safety: prove::Safety::Safe,
+        binder: Binder::new(
+            vars,
+            prove::ImplDeclBoundData {
+                trait_ref: trait_id.with(array_ty(len), ()),
+                where_clause,
+            },
+        ),
+    };
+    vec![
+        impl_decl(
+            seq![element.upcast(), len.upcast()],
+            len.upcast(),
+            trait_id.with(element, ()).upcast(),
+        ),
+        impl_decl(seq![element.upcast()], empty.upcast(), Wcs::t()),
+    ]
+}
+
 impl Crate {
     fn trait_decls(&self) -> Vec<prove::TraitDecl> {
         self.items
             .iter()
             .flat_map(|item| match item {
-                CrateItem::Trait(Trait { id, binder, safety }) => {
+                CrateItem::Trait(Trait {
+                    id,
+                    binder,
+                    safety,
+                    autoness,
+                }) => {
                     let (
                         vars,
                         TraitBoundData {
@@ -89,6 +223,11 @@ impl Crate {
                         },
                     ) = binder.open();
                     Some(prove::TraitDecl {
+                        // Only cycles through auto traits are accepted.
+                        coinduction: match autoness {
+                            Autoness::Auto => prove::Coinduction::Coinductive,
+                            Autoness::NotAuto => prove::Coinduction::Inductive,
+                        },
                         safety: safety.clone(),
                         id: id.clone(),
                         binder: Binder::new(
@@ -111,7 +250,11 @@ impl Crate {
         self.items
             .iter()
             .flat_map(|item| match item {
-                CrateItem::TraitImpl(TraitImpl { binder, safety }) => {
+                CrateItem::TraitImpl(TraitImpl {
+                    defaultness,
+                    binder,
+                    safety,
+                }) => {
                     let (
                         vars,
                         TraitImplBoundData {
@@ -123,6 +266,7 @@ impl Crate {
                         },
                     ) = binder.open();
                     Some(prove::ImplDecl {
+                        defaultness: defaultness.clone(),
                         safety: safety.clone(),
                         binder: Binder::new(
                             vars,
@@ -168,11 +312,11 @@ impl Crate {
             .collect()
     }
 
-    fn alias_eq_decls(&self) -> Vec<prove::AliasEqDecl> {
+    fn alias_eq_decls(&self, program: &Program) -> Vec<prove::AliasEqDecl> {
         self.items
             .iter()
             .flat_map(|item| match item {
-                CrateItem::TraitImpl(TraitImpl { binder, safety: _ }) => {
+                CrateItem::TraitImpl(TraitImpl { binder, .. }) => {
                     let (
                         impl_vars,
                         TraitImplBoundData {
@@ -197,6 +341,12 @@ impl Crate {
                                     ty,
                                 },
                             ) = binder.open();
+                            let trait_assoc_wc = program.associated_ty_where_clauses(
+                                &trait_id,
+                                item_id,
+                                &seq![self_ty.to(), ..trait_parameters.iter().cloned()],
+                                &assoc_vars,
+                            );
                             Some(prove::AliasEqDecl {
                                 binder: Binder::new(
                                     (&impl_vars, &assoc_vars),
@@ -212,7 +362,7 @@ impl Crate {
                                             ],
                                         ),
                                         ty,
-                                        where_clause: (&impl_wc, assoc_wc).to_wcs(),
+                                        where_clause: (&impl_wc, assoc_wc, trait_assoc_wc).to_wcs(),
                                     },
                                 ),
                             })
@@ -230,6 +380,7 @@ impl Crate {
             .flat_map(|item| match item {
                 CrateItem::Trait(Trait {
                     safety: _,
+                    autoness: _,
                     id: trait_id,
                     binder,
                 }) => {
@@ -293,26 +444,38 @@ impl Crate {
             .flat_map(|item| match item {
                 CrateItem::Struct(s) => Some(s.to_adt()),
                 CrateItem::Enum(e) => Some(e.to_adt()),
+                CrateItem::Union(u) => Some(u.to_adt()),
                 _ => None,
             })
-            .map(|Adt { id, binder }| {
-                let (
-                    vars,
-                    AdtBoundData {
-                        where_clauses,
-                        variants: _,
-                    },
-                ) = binder.open();
-                prove::AdtDecl {
-                    id: id.clone(),
-                    binder: Binder::new(
+            .map(
+                |Adt {
+                     fundamentalness,
+                     id,
+                     binder,
+                 }| {
+                    let (
                         vars,
-                        prove::AdtDeclBoundData {
-                            where_clause: where_clauses.iter().flat_map(|wc| wc.to_wcs()).collect(),
+                        AdtBoundData {
+                            defaults: _,
+                            where_clauses,
+                            variants: _,
                         },
-                    ),
-                }
-            })
+                    ) = binder.open();
+                    prove::AdtDecl {
+                        fundamentalness: fundamentalness.clone(),
+                        id: id.clone(),
+                        binder: Binder::new(
+                            vars,
+                            prove::AdtDeclBoundData {
+                                where_clause: where_clauses
+                                    .iter()
+                                    .flat_map(|wc| wc.to_wcs())
+                                    .collect(),
+                            },
+                        ),
+                    }
+                },
+            )
             .collect()
     }
 
@@ -322,6 +485,7 @@ impl Crate {
             .flat_map(|item| match item {
                 CrateItem::Struct(v) => Some(v.id.clone()),
                 CrateItem::Enum(v) => Some(v.id.clone()),
+                CrateItem::Union(v) => Some(v.id.clone()),
                 CrateItem::Trait(_) => None,
                 CrateItem::TraitImpl(_) => None,
                 CrateItem::NegTraitImpl(_) => None,
@@ -412,6 +576,20 @@ impl ToWcs for WhereClause {
             WhereClauseData::IsImplemented(self_ty, trait_id, parameters) => {
                 trait_id.with(self_ty, parameters).upcast()
             }
+            WhereClauseData::IsImplementedWith(self_ty, trait_id, args) => {
+                let trait_parameters = seq![self_ty.to(), ..args.parameters.iter().cloned()];
+                // The binding is lowered to an equality (rather than `AliasEq`)
+                // so that normalization can use it when it is assumed.
+                let alias_eqs = args.bindings.iter().map(|binding| {
+                    let alias: Ty =
+                        AliasTy::associated_ty(trait_id, &binding.id, 0, &trait_parameters)
+                            .upcast();
+                    Relation::equals(alias, &binding.ty).upcast()
+                });
+                std::iter::once(trait_id.with(self_ty, &args.parameters).upcast())
+                    .chain(alias_eqs)
+                    .collect()
+            }
             WhereClauseData::AliasEq(alias_ty, ty) => {
                 Predicate::AliasEq(alias_ty.clone(), ty.clone()).upcast()
             }
@@ -426,6 +604,8 @@ impl ToWcs for WhereClause {
             WhereClauseData::TypeOfConst(ct, ty) => {
                 Predicate::ConstHasType(ct.clone(), ty.clone()).upcast()
             }
+            // `?Sized` only suppresses the implicit bound, it asserts nothing.
+            WhereClauseData::MaybeSized(_) => Wcs::t(),
         }
     }
 }
@@ -0,0 +1,190 @@
+//! Elaboration of the implicit `Sized` bound.
+//!
+//! In Rust, every type parameter is `Sized` unless it opts out with `?Sized`
+//! (the `Self` parameter of a trait is the exception). Rather than teaching each
+//! part of the lowering about that rule, we elaborate the program once up front
+//! so that every implicit bound appears as an ordinary where-clause.
+
+use std::sync::Arc;
+
+use formality_core::Upcast;
+use formality_types::{
+    grammar::{Binder, BoundVar, ParameterKind, TraitId, Ty},
+    rust::Term,
+};
+
+use crate::grammar::{
+    AdtBoundData, Crate, CrateItem, Enum, Fn, FnBoundData, ImplItem, NegTraitImpl,
+    NegTraitImplBoundData, Program, Struct, StructBoundData, Trait, TraitBinder, TraitBoundData,
+    TraitImpl, TraitImplBoundData, TraitItem, Union, WhereClause, WhereClauseData,
+};
+
+impl Program {
+    /// Returns a copy of this program in which every type parameter that is
+    /// implicitly `Sized` carries an explicit `T: Sized` where-clause.
+    ///
+    /// Programs that do not declare a `Sized` trait are returned unchanged,
+    /// since there is nothing for the bound to refer to.
+    pub fn elaborate_sized(&self) -> Program {
+        if self.trait_named(&TraitId::sized()).is_err() {
+            return self.clone();
+        }
+
+        Program {
+            crates: self.crates.iter().map(|c| c.elaborate_sized()).collect(),
+        }
+    }
+}
+
+impl Crate {
+    fn elaborate_sized(&self) -> Crate {
+        Crate {
+            id: self.id.clone(),
+            items: self.items.iter().map(|i| i.elaborate_sized()).collect(),
+        }
+    }
+}
+
+impl CrateItem {
+    fn elaborate_sized(&self) -> CrateItem {
+        match self {
+            CrateItem::Struct(Struct {
+                fundamentalness,
+                id,
+                binder,
+            }) => Struct {
+                fundamentalness: fundamentalness.clone(),
+                id: id.clone(),
+                binder: elaborate_binder(binder, 0, |data: &mut StructBoundData| {
+                    &mut data.where_clauses
+                }),
+            }
+            .upcast(),
+            CrateItem::Enum(Enum { id, binder }) => Enum {
+                id: id.clone(),
+                binder: elaborate_binder(binder, 0, |data: &mut AdtBoundData| {
+                    &mut data.where_clauses
+                }),
+            }
+            .upcast(),
+            CrateItem::Union(Union { id, binder }) => Union {
+                id: id.clone(),
+                binder: elaborate_binder(binder, 0, |data: &mut StructBoundData| {
+                    &mut data.where_clauses
+                }),
+            }
+            .upcast(),
+            CrateItem::Trait(Trait {
+                safety,
+                autoness,
+                id,
+                binder,
+            }) => {
+                // The `Self` parameter of a trait is not implicitly sized.
+                let explicit_binder =
+                    elaborate_binder(&binder.explicit_binder, 1, |data: &mut TraitBoundData| {
+                        &mut data.where_clauses
+                    });
+                let (vars, data) = explicit_binder.open();
+                let data = TraitBoundData {
+                    where_clauses: data.where_clauses,
+                    trait_items: data
+                        .trait_items
+                        .iter()
+                        .map(|item| match item {
+                            TraitItem::Fn(f) => f.elaborate_sized().upcast(),
+                            TraitItem::AssociatedTy(_) => item.clone(),
+                        })
+                        .collect(),
+                };
+                Trait {
+                    safety: safety.clone(),
+                    autoness: autoness.clone(),
+                    id: id.clone(),
+                    binder: TraitBinder {
+                        explicit_binder: Binder::new(vars, data),
+                    },
+                }
+                .upcast()
+            }
+            CrateItem::TraitImpl(TraitImpl {
+                defaultness,
+                safety,
+                binder,
+            }) => {
+                let binder = elaborate_binder(binder, 0, |data: &mut TraitImplBoundData| {
+                    &mut data.where_clauses
+                });
+                let (vars, mut data) = binder.open();
+                data.impl_items = data
+                    .impl_items
+                    .iter()
+                    .map(|item| match item {
+                        ImplItem::Fn(f) => f.elaborate_sized().upcast(),
+                        ImplItem::AssociatedTyValue(_) => item.clone(),
+                    })
+                    .collect();
+                TraitImpl {
+                    defaultness: defaultness.clone(),
+                    safety: safety.clone(),
+                    binder: Binder::new(vars, data),
+                }
+                .upcast()
+            }
+            CrateItem::NegTraitImpl(NegTraitImpl { safety, binder }) => NegTraitImpl {
+                safety: safety.clone(),
+                binder: elaborate_binder(binder, 0, |data: &mut NegTraitImplBoundData| {
+                    &mut data.where_clauses
+                }),
+            }
+            .upcast(),
+            CrateItem::Fn(f) => f.elaborate_sized().upcast(),
+            CrateItem::Test(_) => self.clone(),
+        }
+    }
+}
+
+impl Fn {
+    fn elaborate_sized(&self) -> Fn {
+        Fn {
+            id: self.id.clone(),
+            binder: elaborate_binder(&self.binder, 0, |data: &mut FnBoundData| {
+                &mut data.where_clauses
+            }),
+        }
+    }
+}
+
+/// Adds `T: Sized` to the where-clauses of `binder` for each type parameter `T`
+/// (skipping the first `skip` parameters) that does not appear in a `T: ?Sized` clause.
+fn elaborate_binder<T: Term>(
+    binder: &Binder<T>,
+    skip: usize,
+    where_clauses: impl FnOnce(&mut T) -> &mut Vec<WhereClause>,
+) -> Binder<T> {
+    let (vars, mut data) = binder.open();
+    let wcs = where_clauses(&mut data);
+
+    let opted_out: Vec<Ty> = wcs
+        .iter()
+        .filter_map(|wc| match wc.data() {
+            WhereClauseData::MaybeSized(ty) => Some(ty.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let implicit_bounds: Vec<WhereClause> = vars
+        .iter()
+        .skip(skip)
+        .filter(|var| var.kind == ParameterKind::Ty)
+        .map(|&var: &BoundVar| -> Ty { var.upcast() })
+        .filter(|ty| !opted_out.contains(ty))
+        .map(|ty| WhereClause {
+            data: Arc::new(WhereClauseData::IsImplemented(ty, TraitId::sized(), vec![])),
+        })
+        .filter(|wc| !wcs.contains(wc))
+        .collect();
+
+    wcs.extend(implicit_bounds);
+    Binder::new(vars, data)
+}
@@ -1,3 +1,4 @@
+mod simplify;
 mod valtree;
 
 use super::{Parameter, Ty, Variable};
@@ -28,7 +29,7 @@ impl Const {
 
     pub fn as_variable(&self) -> Option<Variable> {
         match self.data() {
-            ConstData::Value(_, _) => None,
+            ConstData::Value(_, _) | ConstData::Op(..) => None,
             ConstData::Variable(var) => Some(*var),
         }
     }
@@ -36,7 +37,7 @@ impl Const {
     pub fn as_value(&self) -> Option<(ValTree, Ty)> {
         match self.data() {
             ConstData::Value(v, t) => Some((v.clone(), t.clone())),
-            ConstData::Variable(_) => None,
+            ConstData::Variable(_) | ConstData::Op(..) => None,
         }
     }
 }
@@ -48,6 +49,19 @@ pub enum ConstData {
 
     #[variable]
     Variable(Variable),
+
+    /// An arithmetic expression like `{N + 1_usize}`, see [`Const::simplify`].
+    #[grammar({$v0 $v1 $v2})]
+    Op(Const, ConstOp, Const),
+}
+
+#[term]
+#[derive(Copy)]
+pub enum ConstOp {
+    #[grammar(+)]
+    Add,
+    #[grammar(*)]
+    Mul,
 }
 
 impl DowncastTo<ConstData> for Const {
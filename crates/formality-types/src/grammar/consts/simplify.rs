@@ -0,0 +1,166 @@
+//! Symbolic simplification of constant expressions.
+
+use crate::grammar::{RigidName, ScalarId, Ty, TyData};
+
+use super::{Const, ConstData, ConstOp, Scalar};
+
+/// The largest literal `k` for which `k * c` is expanded into a sum of `k` copies of `c`.
+const MAX_EXPANDED_FACTOR: u128 = 16;
+
+impl Const {
+    /// Rewrites the arithmetic in this constant into a canonical form, so that
+    /// expressions that are equal by the rules below become syntactically equal
+    /// (e.g., `{N + 0_usize}` and `N`, or `{2_usize * N}` and `{N + N}`).
+    /// The solver simplifies constants before equating them. The rules, applied
+    /// bottom-up, are:
+    ///
+    /// * two literals of the same unsigned integer type are added or multiplied,
+    ///   unless the result does not fit the type;
+    /// * `c + 0` and `c * 1` become `c`, and `c * 0` becomes `0`;
+    /// * `k * c` with a literal `k` of at most 16 becomes `c + ... + c`;
+    /// * the operands of `*`, and the summands of nested `+`, are sorted, with
+    ///   the literals of a sum last.
+    ///
+    /// Every rule removes an operation or reorders operands, so simplification
+    /// terminates. Anything else (e.g., distributing `*` over `+`) is left alone,
+    /// so equalities that need it are not discharged.
+    pub fn simplify(&self) -> Const {
+        match self.data() {
+            ConstData::Value(..) | ConstData::Variable(_) => self.clone(),
+            ConstData::Op(a, ConstOp::Add, b) => {
+                let mut summands = vec![];
+                push_summands(&a.simplify(), &mut summands);
+                push_summands(&b.simplify(), &mut summands);
+                sum(summands)
+            }
+            ConstData::Op(a, ConstOp::Mul, b) => product(a.simplify(), b.simplify()),
+        }
+    }
+}
+
+fn push_summands(c: &Const, summands: &mut Vec<Const>) {
+    match c.data() {
+        ConstData::Op(a, ConstOp::Add, b) => {
+            push_summands(a, summands);
+            push_summands(b, summands);
+        }
+        _ => summands.push(c.clone()),
+    }
+}
+
+/// Builds the canonical sum of `summands`, each of which is simplified and not itself a sum.
+fn sum(summands: Vec<Const>) -> Const {
+    let mut terms: Vec<Const> = vec![];
+    let mut literals: Vec<(u128, Ty)> = vec![];
+    for c in summands {
+        match integer_literal(&c) {
+            Some((bits, ty)) => match literals.iter_mut().find(|(_, t)| *t == ty) {
+                Some(literal) => match checked(&ty, literal.0.checked_add(bits)) {
+                    Some(total) => literal.0 = total,
+                    None => terms.push(c),
+                },
+                None => literals.push((bits, ty)),
+            },
+            None => terms.push(c),
+        }
+    }
+
+    terms.sort();
+    let zero = literals.iter().find(|(bits, _)| *bits == 0).cloned();
+    terms.extend(
+        literals
+            .into_iter()
+            .filter(|(bits, _)| *bits != 0)
+            .map(|(bits, ty)| Const::valtree(Scalar::new(bits), ty)),
+    );
+
+    let Some(mut result) = terms.pop() else {
+        // Only zeros were added.
+        let (bits, ty) = zero.unwrap();
+        return Const::valtree(Scalar::new(bits), ty);
+    };
+    while let Some(term) = terms.pop() {
+        result = Const::new(ConstData::Op(term, ConstOp::Add, result));
+    }
+    result
+}
+
+/// Builds the canonical product of `a` and `b`, which are simplified.
+fn product(a: Const, b: Const) -> Const {
+    match (integer_literal(&a), integer_literal(&b)) {
+        (Some((x, x_ty)), Some((y, y_ty))) if x_ty == y_ty => {
+            if let Some(bits) = checked(&x_ty, x.checked_mul(y)) {
+                return Const::valtree(Scalar::new(bits), x_ty);
+            }
+        }
+        (Some((k, _)), _) => return scale(k, a, b),
+        (_, Some((k, _))) => return scale(k, b, a),
+        _ => {}
+    }
+
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    Const::new(ConstData::Op(a, ConstOp::Mul, b))
+}
+
+/// Builds `k * c`, where `literal` is the literal `k`.
+fn scale(k: u128, literal: Const, c: Const) -> Const {
+    match k {
+        0 => literal,
+        1 => c,
+        _ if k <= MAX_EXPANDED_FACTOR => {
+            let mut summands = vec![];
+            for _ in 0..k {
+                push_summands(&c, &mut summands);
+            }
+            sum(summands)
+        }
+        _ => {
+            let (a, b) = if literal <= c {
+                (literal, c)
+            } else {
+                (c, literal)
+            };
+            Const::new(ConstData::Op(a, ConstOp::Mul, b))
+        }
+    }
+}
+
+/// The value and type of `c`, if it is a literal of an integer type.
+fn integer_literal(c: &Const) -> Option<(u128, Ty)> {
+    let (value, ty) = c.as_value()?;
+    let super::ValTree::Leaf(scalar) = value else {
+        return None;
+    };
+    match scalar_id(&ty)? {
+        ScalarId::Bool => None,
+        _ => Some((scalar.bits(), ty)),
+    }
+}
+
+/// `bits`, if it is in the range of `ty`. Signed types are never folded, as
+/// their literals are not given in two's complement.
+fn checked(ty: &Ty, bits: Option<u128>) -> Option<u128> {
+    let max: u128 = match scalar_id(ty)? {
+        ScalarId::U8 => u8::MAX.into(),
+        ScalarId::U16 => u16::MAX.into(),
+        ScalarId::U32 => u32::MAX.into(),
+        ScalarId::U64 | ScalarId::Usize => u64::MAX.into(),
+        ScalarId::I8
+        | ScalarId::I16
+        | ScalarId::I32
+        | ScalarId::I64
+        | ScalarId::Isize
+        | ScalarId::Bool => return None,
+    };
+    bits.filter(|&bits| bits <= max)
+}
+
+fn scalar_id(ty: &Ty) -> Option<ScalarId> {
+    match ty.data() {
+        TyData::RigidTy(r) => match &r.name {
+            RigidName::ScalarId(s) => Some(s.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
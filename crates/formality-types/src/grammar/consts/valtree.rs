@@ -34,6 +34,10 @@ impl Scalar {
     pub fn new(bits: u128) -> Self {
         Self { bits }
     }
+
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
 }
 
 impl UpcastFrom<Bool> for ValTree {
@@ -6,3 +6,44 @@ id!(TraitId);
 id!(AssociatedItemId);
 id!(CrateId);
 id!(FieldId);
+
+impl TraitId {
+    /// The `Sized` trait, which type parameters implement by default.
+    pub fn sized() -> TraitId {
+        TraitId::new("Sized")
+    }
+
+    /// The `MetaSized` trait, for types whose size can be computed from their
+    /// metadata (e.g. `[T]`). `Sized` implies it.
+    pub fn meta_sized() -> TraitId {
+        TraitId::new("MetaSized")
+    }
+
+    /// The `Pointee` trait, which every type implements. `MetaSized` implies it.
+    pub fn pointee() -> TraitId {
+        TraitId::new("Pointee")
+    }
+
+    /// The `Copy` trait.
+    pub fn copy() -> TraitId {
+        TraitId::new("Copy")
+    }
+
+    /// The `Deref` trait, whose `Target` type method resolution dereferences to.
+    pub fn deref() -> TraitId {
+        TraitId::new("Deref")
+    }
+
+    /// The `Termination` trait, which the return type of an entry function
+    /// such as `main` must implement.
+    pub fn termination() -> TraitId {
+        TraitId::new("Termination")
+    }
+}
+
+impl AssociatedItemId {
+    /// The `Target` type of the `Deref` trait.
+    pub fn deref_target() -> AssociatedItemId {
+        AssociatedItemId::new("Target")
+    }
+}
@@ -3,12 +3,14 @@ use std::sync::Arc;
 
 mod debug_impls;
 mod parse_impls;
+mod shared_debug;
 mod term_impls;
-use formality_core::{DowncastTo, To, Upcast, UpcastFrom};
+use formality_core::{DowncastTo, Set, To, Upcast, UpcastFrom};
+pub use shared_debug::SharedDebug;
 
 use super::{
     consts::Const, AdtId, AssociatedItemId, Binder, BoundVar, ExistentialVar, FnId, TraitId,
-    UniversalVar, Variable,
+    TraitRef, UniversalVar, Variable,
 };
 
 #[term]
@@ -83,6 +85,25 @@ impl Ty {
         }
         .upcast()
     }
+
+    pub fn never() -> Ty {
+        RigidTy {
+            name: RigidName::Never,
+            parameters: vec![],
+        }
+        .upcast()
+    }
+
+    /// True if this is the never type `!` (and not, say, a variable that might be `!`).
+    pub fn is_never(&self) -> bool {
+        matches!(
+            self.data(),
+            TyData::RigidTy(RigidTy {
+                name: RigidName::Never,
+                ..
+            })
+        )
+    }
 }
 
 impl UpcastFrom<TyData> for Ty {
@@ -153,6 +174,12 @@ pub enum RigidName {
     #[grammar(&($v0))]
     Ref(RefKind),
     Tuple(usize),
+    /// `[T; N]`, with parameters `[T, N]`.
+    Array,
+    /// `[T]`, with parameters `[T]`.
+    Slice,
+    /// The never type `!`.
+    Never,
     FnPtr(usize),
     FnDef(FnId),
 }
@@ -235,6 +262,29 @@ pub struct AssociatedTyName {
     pub item_arity: usize,
 }
 
+/// A trait object type like `dyn Foo<u32> + Send`, written `dyn Foo<u32> [Send]`.
+///
+/// FIXME: trait objects are not yet a variant of `TyData`; for now they only
+/// appear in judgments about trait object coercions.
+#[term(dyn $principal_id $<?principal_parameters> $[?auto_traits])]
+pub struct DynTy {
+    /// The principal trait.
+    pub principal_id: TraitId,
+
+    /// The parameters of the principal trait, excluding the self type.
+    pub principal_parameters: Parameters,
+
+    /// The auto traits (e.g., `Send`) carried alongside the principal trait.
+    pub auto_traits: Set<TraitId>,
+}
+
+impl DynTy {
+    /// The principal trait reference with `self_ty` as its self type.
+    pub fn principal_with(&self, self_ty: impl Upcast<Ty>) -> TraitRef {
+        self.principal_id.with(self_ty, &self.principal_parameters)
+    }
+}
+
 #[term]
 pub enum PredicateTy {
     ForAll(Binder<Ty>),
@@ -1,40 +1,134 @@
-use super::{AliasName, AliasTy, AssociatedTyName, Parameter, RefKind, RigidName, RigidTy};
+use formality_core::Upcast;
+
+use super::{
+    AliasName, AliasTy, AssociatedTyName, LtData, Parameter, RefKind, RigidName, RigidTy, Ty,
+    TyData, Variable,
+};
 use std::fmt::Debug;
 
+/// Picks the parameters that are printed as some other text, see [`replacing`].
+pub(super) type Replace<'a> = &'a dyn Fn(&Parameter) -> Option<String>;
+
+/// Formats `parameter` like its `Debug` impl, except that it and each
+/// parameter nested in it that `replace` has some text for is printed as
+/// that text instead.
+pub(super) fn replacing<'a>(parameter: &'a Parameter, replace: Replace<'a>) -> impl Debug + 'a {
+    Replacing { parameter, replace }
+}
+
+struct Replacing<'a> {
+    parameter: &'a Parameter,
+    replace: Replace<'a>,
+}
+
+impl Debug for Replacing<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(text) = (self.replace)(self.parameter) {
+            return write!(f, "{text}");
+        }
+        match self.parameter {
+            Parameter::Ty(ty) => match ty.data() {
+                TyData::RigidTy(r) => fmt_rigid_ty(r, f, self.replace),
+                TyData::AliasTy(a) => fmt_alias_ty(a, f, self.replace),
+                TyData::PredicateTy(_) | TyData::Variable(_) => write!(f, "{ty:?}"),
+            },
+            Parameter::Lt(_) | Parameter::Const(_) => write!(f, "{:?}", self.parameter),
+        }
+    }
+}
+
+impl Ty {
+    /// Returns a value whose `Debug` output writes the inference variables in
+    /// this type as `_`, for diagnostics, where their names mean nothing.
+    pub fn debug_with_holes(&self) -> impl Debug + '_ {
+        WithHoles(self.upcast())
+    }
+}
+
+struct WithHoles(Parameter);
+
+impl Debug for WithHoles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hole = |p: &Parameter| {
+            let variable = match p {
+                Parameter::Ty(ty) => match ty.data() {
+                    TyData::Variable(v) => v,
+                    _ => return None,
+                },
+                Parameter::Lt(lt) => match lt.data() {
+                    LtData::Variable(v) => v,
+                    LtData::Static => return None,
+                },
+                Parameter::Const(_) => return None,
+            };
+            matches!(variable, Variable::ExistentialVar(_)).then(|| "_".to_string())
+        };
+        let ty = replacing(&self.0, &hole);
+        write!(f, "{ty:?}")
+    }
+}
+
 // ANCHOR: RigidTy_impl
 impl Debug for RigidTy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let RigidTy { name, parameters } = self;
-        match name {
-            RigidName::AdtId(name) => {
+        fmt_rigid_ty(self, f, &|_| None)
+    }
+}
+
+fn fmt_rigid_ty(
+    rigid_ty: &RigidTy,
+    f: &mut std::fmt::Formatter<'_>,
+    replace: Replace<'_>,
+) -> std::fmt::Result {
+    let RigidTy { name, parameters } = rigid_ty;
+    let p = |i: usize| replacing(&parameters[i], replace);
+    match name {
+        RigidName::AdtId(name) => {
+            write!(
+                f,
+                "{:?}{:?}",
+                name,
+                PrettyParameters::new("<", ">", parameters, replace)
+            )
+        }
+        RigidName::ScalarId(s) if parameters.is_empty() => {
+            write!(f, "{:?}", s)
+        }
+        RigidName::Ref(RefKind::Shared) if parameters.len() == 2 => {
+            write!(f, "&{:?} {:?}", p(0), p(1))
+        }
+        RigidName::Ref(RefKind::Mut) if parameters.len() == 2 => {
+            write!(f, "&mut {:?} {:?}", p(0), p(1))
+        }
+        RigidName::Tuple(arity) if parameters.len() == *arity => {
+            if *arity != 0 {
                 write!(
                     f,
-                    "{:?}{:?}",
-                    name,
-                    PrettyParameters::new("<", ">", parameters)
+                    "{:?}",
+                    PrettyParameters::new("(", ")", parameters, replace)
                 )
+            } else {
+                // PrettyParameters would skip the separators
+                // for 0 arity
+                write!(f, "()")
             }
-            RigidName::ScalarId(s) if parameters.is_empty() => {
-                write!(f, "{:?}", s)
-            }
-            RigidName::Ref(RefKind::Shared) if parameters.len() == 2 => {
-                write!(f, "&{:?} {:?}", parameters[0], parameters[1])
-            }
-            RigidName::Ref(RefKind::Mut) if parameters.len() == 2 => {
-                write!(f, "&mut {:?} {:?}", parameters[0], parameters[1])
-            }
-            RigidName::Tuple(arity) if parameters.len() == *arity => {
-                if *arity != 0 {
-                    write!(f, "{:?}", PrettyParameters::new("(", ")", parameters))
-                } else {
-                    // PrettyParameters would skip the separators
-                    // for 0 arity
-                    write!(f, "()")
-                }
-            }
-            _ => {
-                write!(f, "{:?}{:?}", name, PrettyParameters::angle(parameters))
-            }
+        }
+        RigidName::Never if parameters.is_empty() => {
+            write!(f, "!")
+        }
+        RigidName::Array if parameters.len() == 2 => {
+            write!(f, "[{:?}; {:?}]", p(0), p(1))
+        }
+        RigidName::Slice if parameters.len() == 1 => {
+            write!(f, "[{:?}]", p(0))
+        }
+        _ => {
+            write!(
+                f,
+                "{:?}{:?}",
+                name,
+                PrettyParameters::angle(parameters, replace)
+            )
         }
     }
 }
@@ -42,27 +136,35 @@ impl Debug for RigidTy {
 
 impl Debug for AliasTy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let AliasTy { name, parameters } = self;
-        match name {
-            AliasName::AssociatedTyId(AssociatedTyName {
+        fmt_alias_ty(self, f, &|_| None)
+    }
+}
+
+fn fmt_alias_ty(
+    alias_ty: &AliasTy,
+    f: &mut std::fmt::Formatter<'_>,
+    replace: Replace<'_>,
+) -> std::fmt::Result {
+    let AliasTy { name, parameters } = alias_ty;
+    match name {
+        AliasName::AssociatedTyId(AssociatedTyName {
+            trait_id,
+            item_id,
+            item_arity,
+        }) => {
+            let (trait_parameters, item_parameters) =
+                parameters.split_at(parameters.len() - item_arity);
+            let (self_parameter, other_parameters) = trait_parameters.split_at(1);
+            // Grr, wish we would remember the number of parameters assigned to each position.
+            write!(
+                f,
+                "<{:?} as {:?}{:?}>::{:?}{:?}",
+                replacing(&self_parameter[0], replace),
                 trait_id,
+                PrettyParameters::angle(other_parameters, replace),
                 item_id,
-                item_arity,
-            }) => {
-                let (trait_parameters, item_parameters) =
-                    parameters.split_at(parameters.len() - item_arity);
-                let (self_parameter, other_parameters) = trait_parameters.split_at(1);
-                // Grr, wish we would remember the number of parameters assigned to each position.
-                write!(
-                    f,
-                    "<{:?} as {:?}{:?}>::{:?}{:?}",
-                    self_parameter[0],
-                    trait_id,
-                    PrettyParameters::angle(other_parameters),
-                    item_id,
-                    PrettyParameters::angle(item_parameters),
-                )
-            }
+                PrettyParameters::angle(item_parameters, replace),
+            )
         }
     }
 }
@@ -71,14 +173,20 @@ struct PrettyParameters<'a> {
     open: &'a str,
     close: &'a str,
     p: &'a [Parameter],
+    replace: Replace<'a>,
 }
 impl<'a> PrettyParameters<'a> {
-    fn new(open: &'a str, close: &'a str, p: &'a [Parameter]) -> Self {
-        Self { open, close, p }
+    fn new(open: &'a str, close: &'a str, p: &'a [Parameter], replace: Replace<'a>) -> Self {
+        Self {
+            open,
+            close,
+            p,
+            replace,
+        }
     }
 
-    fn angle(p: &'a [Parameter]) -> Self {
-        Self::new("<", ">", p)
+    fn angle(p: &'a [Parameter], replace: Replace<'a>) -> Self {
+        Self::new("<", ">", p, replace)
     }
 }
 
@@ -88,9 +196,9 @@ impl Debug for PrettyParameters<'_> {
             Ok(())
         } else {
             write!(f, "{}", self.open)?;
-            write!(f, "{:?}", self.p[0])?;
+            write!(f, "{:?}", replacing(&self.p[0], self.replace))?;
             for p in &self.p[1..] {
-                write!(f, ", {:?}", p)?;
+                write!(f, ", {:?}", replacing(p, self.replace))?;
             }
             write!(f, "{}", self.close)?;
             Ok(())
@@ -7,7 +7,7 @@ use formality_core::Upcast;
 use formality_core::{seq, Set};
 
 use crate::grammar::{
-    AdtId, AssociatedItemId, Bool, ConstData, RefKind, RigidName, Scalar, TraitId,
+    AdtId, AssociatedItemId, Bool, Const, ConstData, ConstOp, RefKind, RigidName, Scalar, TraitId,
 };
 
 use super::{AliasTy, AssociatedTyName, Lt, Parameter, ParameterKind, RigidTy, ScalarId, Ty};
@@ -70,6 +70,36 @@ impl CoreParse<Rust> for RigidTy {
                     parameters: types.upcast(),
                 })
             });
+
+            parser.parse_variant("Never", Precedence::default(), |p| {
+                p.expect_char('!')?;
+                Ok(RigidTy {
+                    name: RigidName::Never,
+                    parameters: vec![],
+                })
+            });
+
+            parser.parse_variant("Array", Precedence::default(), |p| {
+                p.expect_char('[')?;
+                let ty: Ty = p.nonterminal()?;
+                p.expect_char(';')?;
+                let len: Const = p.nonterminal()?;
+                p.expect_char(']')?;
+                Ok(RigidTy {
+                    name: RigidName::Array,
+                    parameters: seq![ty.upcast(), len.upcast()],
+                })
+            });
+
+            parser.parse_variant("Slice", Precedence::default(), |p| {
+                p.expect_char('[')?;
+                let ty: Ty = p.nonterminal()?;
+                p.expect_char(']')?;
+                Ok(RigidTy {
+                    name: RigidName::Slice,
+                    parameters: seq![ty.upcast()],
+                })
+            });
         })
     }
 }
@@ -135,6 +165,15 @@ impl CoreParse<Rust> for ConstData {
                 let ty: Ty = p.nonterminal()?;
                 Ok(ConstData::Value(Scalar::new(n).upcast(), ty))
             });
+
+            parser.parse_variant("Op", Precedence::default(), |p| {
+                p.expect_char('{')?;
+                let a: Const = p.nonterminal()?;
+                let op: ConstOp = p.nonterminal()?;
+                let b: Const = p.nonterminal()?;
+                p.expect_char('}')?;
+                Ok(ConstData::Op(a, op, b))
+            });
         })
     }
 }
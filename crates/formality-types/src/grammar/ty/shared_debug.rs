@@ -0,0 +1,90 @@
+use formality_core::{Map, Upcast};
+use std::fmt::Debug;
+
+use super::{debug_impls::replacing, AliasTy, Parameter, RigidTy, Ty, TyData};
+
+#[cfg(test)]
+mod test;
+
+/// Debug formatting for a type that prints each repeated sub-type only once.
+///
+/// Every non-trivial sub-type that occurs more than once is given a name `#N`
+/// and printed once as a definition `#N = ...;` ahead of the term, with later
+/// occurrences printed as `#N`. Definitions appear innermost first, so the
+/// output can be read top to bottom. Sharing is detected by structural
+/// equality, so it matches what an interned representation would share.
+pub struct SharedDebug<'a> {
+    ty: &'a Ty,
+}
+
+impl Ty {
+    /// Returns a value whose `Debug` output shows shared sub-types as back-references.
+    pub fn shared_debug(&self) -> SharedDebug<'_> {
+        SharedDebug { ty: self }
+    }
+}
+
+impl Debug for SharedDebug<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut counts = Map::new();
+        let mut order = vec![];
+        count_occurrences(self.ty, &mut counts, &mut order);
+
+        let shared: Vec<Parameter> = order
+            .into_iter()
+            .filter(|ty| counts[ty] > 1)
+            .map(|ty| ty.upcast())
+            .collect();
+        let names: Map<&Parameter, String> = shared
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| (ty, format!("#{}", index + 1)))
+            .collect();
+
+        for ty in &shared {
+            // Within its own definition, a shared type is spelled out.
+            let name_children = |p: &Parameter| {
+                if p == ty {
+                    None
+                } else {
+                    names.get(p).cloned()
+                }
+            };
+            let definition = replacing(ty, &name_children);
+            write!(f, "{} = {definition:?}; ", names[ty])?;
+        }
+        let name = |p: &Parameter| names.get(p).cloned();
+        let ty: Parameter = self.ty.upcast();
+        let term = replacing(&ty, &name);
+        write!(f, "{term:?}")
+    }
+}
+
+/// Counts how often each type with parameters occurs in `ty`.
+/// Repeated occurrences are not descended into, so the sub-types of a shared
+/// type are counted once per distinct use. `order` records each type after its
+/// sub-types, the first time it is seen.
+fn count_occurrences(ty: &Ty, counts: &mut Map<Ty, usize>, order: &mut Vec<Ty>) {
+    let parameters = match ty.data() {
+        TyData::RigidTy(RigidTy { parameters, .. })
+        | TyData::AliasTy(AliasTy { parameters, .. }) => parameters,
+        TyData::PredicateTy(_) | TyData::Variable(_) => return,
+    };
+
+    if parameters.is_empty() {
+        return;
+    }
+
+    if let Some(count) = counts.get_mut(ty) {
+        *count += 1;
+        return;
+    }
+
+    counts.insert(ty.clone(), 1);
+    for parameter in parameters {
+        if let Parameter::Ty(p) = parameter {
+            count_occurrences(p, counts, order);
+        }
+    }
+    order.push(ty.clone());
+}
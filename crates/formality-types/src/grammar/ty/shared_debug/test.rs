@@ -0,0 +1,24 @@
+use expect_test::expect;
+use formality_core::test;
+
+use crate::grammar::Ty;
+use crate::rust::term;
+
+#[test]
+fn shared_sub_term_printed_once() {
+    let ty: Ty = term("(Vec<u32>, Vec<u32>, u32)");
+    expect!["#1 = Vec<u32>; (#1, #1, u32)"].assert_eq(&format!("{:?}", ty.shared_debug()));
+}
+
+#[test]
+fn nested_sharing() {
+    let ty: Ty = term("(Pair<Vec<u32>, Vec<u32>>, Pair<Vec<u32>, Vec<u32>>)");
+    expect!["#1 = Vec<u32>; #2 = Pair<#1, #1>; (#2, #2)"]
+        .assert_eq(&format!("{:?}", ty.shared_debug()));
+}
+
+#[test]
+fn no_sharing() {
+    let ty: Ty = term("Vec<u32>");
+    expect!["Vec<u32>"].assert_eq(&format!("{:?}", ty.shared_debug()));
+}
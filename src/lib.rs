@@ -12,10 +12,13 @@ use std::{path::PathBuf, sync::Arc};
 
 use clap::Parser;
 use formality_check::check_all_crates;
-use formality_core::Set;
-use formality_prove::{test_util::TestAssertion, Constraints};
+use formality_core::{ProvenSet, Set};
+use formality_prove::{test_util::TestAssertion, Constraints, Env};
 use formality_rust::grammar::Program;
-use formality_types::rust::try_term;
+use formality_types::{
+    grammar::{Wc, Wcs},
+    rust::{term, try_term},
+};
 
 #[cfg(test)]
 mod test;
@@ -78,8 +81,39 @@ pub fn test_where_clause(program: &str, assertion: &str) -> anyhow::Result<Set<C
     formality_core::with_tracing_logs(|| {
         let program: Program = try_term(program)?;
         check_all_crates(&program)?;
+        let program = program.elaborate_sized();
         let assertion: Arc<TestAssertion> = try_term(assertion)?;
         let decls = program.to_prove_decls();
         Ok(formality_prove::test_util::test_prove(decls, assertion).into_set()?)
     })
 }
+
+/// Panics unless `goal` can be proven from `assumptions` with the impls and
+/// traits of `program`, printing why the proof failed. The assumptions and
+/// goal are where-clauses like `Foo(T)`. A name without a declaration, like
+/// `T`, parses as a rigid type that the program does not declare rather than
+/// as a type parameter. It differs from every other type and no impl applies
+/// to it, but a goal that needs it to be well-formed panics, as there is no
+/// declaration to check it against.
+pub fn assert_provable_under(assumptions: &[&str], goal: &str, program: &Program) {
+    let result = prove_under(assumptions, goal, program);
+    if !result.iter().any(|c| c.known_true) {
+        panic!("`{goal}` is not provable under {assumptions:?}:\n{result}");
+    }
+}
+
+/// Panics if `goal` can be proven from `assumptions`, printing the solutions;
+/// see [`assert_provable_under`].
+pub fn assert_not_provable_under(assumptions: &[&str], goal: &str, program: &Program) {
+    let result = prove_under(assumptions, goal, program);
+    if result.iter().any(|c| c.known_true) {
+        panic!("`{goal}` is provable under {assumptions:?}:\n{result}");
+    }
+}
+
+fn prove_under(assumptions: &[&str], goal: &str, program: &Program) -> ProvenSet<Constraints> {
+    let assumptions: Wcs = assumptions.iter().map(|a| term::<Wc>(a)).collect();
+    let goal: Wc = term(goal);
+    let decls = program.elaborate_sized().to_prove_decls();
+    formality_prove::prove(decls, Env::default(), assumptions, goal)
+}
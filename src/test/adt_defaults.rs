@@ -0,0 +1,69 @@
+#![allow(non_snake_case)]
+
+use formality_core::test;
+
+#[test]
+fn default_satisfies_bound() {
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Foo {}
+                impl Foo for u32 {}
+                struct Vec<ty T> {}
+                struct S<ty T, ty U> default U = Vec<T> where T: Foo { }
+                struct Z<ty T> default T = u32 where T: Foo { }
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn default_violates_bound() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Foo {}
+                impl Foo for u32 {}
+                struct S<ty T> default T = i32 where T: Foo { }
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            check_adt_default(!ty_1 = i32)
+
+            Caused by:
+                judgment `prove_wc_list { goal: {@ wf(i32), Foo(i32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(u32)], [], [], [], [adt S <ty> where {Foo(^ty0_0)}], {Foo}, {S}) }` failed at the following rule(s):
+                  the rule "some" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Foo(i32)}, assumptions: {}, decls: decls(222, [trait Foo <ty> ], [impl Foo(u32)], [], [], [], [adt S <ty> where {Foo(^ty0_0)}], {Foo}, {S}) }` failed at the following rule(s):
+                      the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {Foo(i32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(u32)], [], [], [], [adt S <ty> where {Foo(^ty0_0)}], {Foo}, {S}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: Foo(i32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(u32)], [], [], [], [adt S <ty> where {Foo(^ty0_0)}], {Foo}, {S}) }` failed at the following rule(s):
+                              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]
+    )
+}
+
+#[test]
+fn default_refers_to_later_parameter() {
+    crate::assert_err!(
+        [
+            crate core {
+                struct S<ty T, ty U> default T = U { }
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            check_adt_default(!ty_1 = !ty_2)
+
+            Caused by:
+                the default refers to `!ty_2`, which is not declared before `!ty_1`"#]]
+    )
+}
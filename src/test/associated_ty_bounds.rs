@@ -0,0 +1,157 @@
+#![allow(non_snake_case)]
+
+use formality_core::test_util::ResultTestExt;
+
+const PROGRAM: &str = "[
+    crate core {
+        trait Iterator {
+            type Item : [];
+        }
+
+        trait IsU32 {}
+        impl IsU32 for u32 {}
+
+        trait Sum where Self: Iterator, <Self as Iterator>::Item: IsU32 {}
+        impl<ty I> Sum for I where I: Iterator<Item = u32> {}
+
+        struct Counter {}
+        impl Iterator for Counter {
+            type Item = u32;
+        }
+
+        struct Bytes {}
+        impl Iterator for Bytes {
+            type Item = u8;
+        }
+    }
+]";
+
+#[test]
+fn bound_uses_projection_equality() {
+    // Checking the `Sum` impl has to prove `<I as Iterator>::Item: IsU32`,
+    // which only follows from the `Item = u32` binding in its where-clause.
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Iterator {
+                    type Item : [];
+                }
+
+                trait IsU32 {}
+                impl IsU32 for u32 {}
+
+                trait Sum where Self: Iterator, <Self as Iterator>::Item: IsU32 {}
+                impl<ty I> Sum for I where I: Iterator<Item = u32> {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn bound_without_binding_does_not_prove_projection() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Iterator {
+                    type Item : [];
+                }
+
+                trait IsU32 {}
+                impl IsU32 for u32 {}
+
+                trait Sum where Self: Iterator, <Self as Iterator>::Item: IsU32 {}
+                impl<ty I> Sum for I where I: Iterator {}
+            }
+        ]
+
+        [
+            "IsU32(<!ty_0 as Iterator>::Item)",
+        ]
+
+        expect_test::expect![[r#"
+            check_trait_impl(impl <ty> Sum for ^ty0_0 where ^ty0_0 : Iterator { })
+
+            Caused by:
+                judgment `prove_wc_list { goal: {Sum(!ty_0)}, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_wc { goal: Sum(!ty_0), assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                      the rule "positive impl" failed at step #7 (src/file.rs:LL:CC) because
+                        judgment `prove_after { constraints: Constraints { env: Env { variables: [!ty_0, ?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => !ty_0} }, goal: {IsU32(<?ty_1 as Iterator>::Item), Iterator(?ty_1)}, assumptions: {Iterator(!ty_0)}, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                          the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `prove_wc_list { goal: {IsU32(<!ty_0 as Iterator>::Item), Iterator(!ty_0)}, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_wc { goal: IsU32(<!ty_0 as Iterator>::Item), assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                                  the rule "positive impl" failed at step #5 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc_list { goal: {<!ty_0 as Iterator>::Item = u32}, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment `prove_wc { goal: <!ty_0 as Iterator>::Item = u32, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                                          the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                            judgment `prove_eq { a: <!ty_0 as Iterator>::Item, b: u32, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                                              the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                                judgment `prove_normalize { p: <!ty_0 as Iterator>::Item, assumptions: {Iterator(!ty_0)}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {Iterator(^ty0_0)}], [], [], [], [], {IsU32, Iterator, Sum}, {}) }` failed at the following rule(s):
+                                                  the rule "normalize-via-impl" failed at step #0 (src/file.rs:LL:CC) because
+                                                    expression evaluated to an empty collection: `decls.alias_eq_decls(&a.name)`"#]]
+    )
+}
+
+#[test]
+fn bound_requires_projection_equality() {
+    crate::test_where_clause(PROGRAM, "{} => { Sum(Counter) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+    crate::test_where_clause(PROGRAM, "{} => { Sum(Bytes) }").assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {Sum(Bytes)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Sum(Bytes), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+              the rule "positive impl" failed at step #6 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => Bytes} }, goal: {<?ty_1 as Iterator>::Item = u32, Iterator(?ty_1)}, assumptions: {}, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {<Bytes as Iterator>::Item = u32, Iterator(Bytes)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: <Bytes as Iterator>::Item = u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                          the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_eq { a: <Bytes as Iterator>::Item, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                              the rule "normalize-l" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {u8 = u32}, assumptions: {}, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc_list { goal: {u8 = u32}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                        judgment `prove_wc { goal: u8 = u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                          the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                                            judgment `prove_eq { a: u8, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                              the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                                judgment had no applicable rules: `prove_normalize { p: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`
+                                              the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                                judgment `prove_eq { a: u32, b: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`
+                                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                                    cyclic proof attempt: `prove_eq { a: u8, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`, reached through:
+                                                      `prove_eq { a: u32, b: u8, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`
+                              the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_eq { a: u32, b: <Bytes as Iterator>::Item, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }` failed at the following rule(s):
+                                  the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment had no applicable rules: `prove_normalize { p: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`
+                                  the rule "symmetric" failed at step #0 (src/file.rs:LL:CC) because
+                                    cyclic proof attempt: `prove_eq { a: <Bytes as Iterator>::Item, b: u32, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`, reached through:
+                                      `prove_eq { a: u32, b: <Bytes as Iterator>::Item, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`
+              the rule "trait implied bound" failed at step #3 (src/file.rs:LL:CC) because
+                judgment had no applicable rules: `prove_via { goal: Sum(Bytes), via: Iterator(?ty_1), assumptions: {}, env: Env { variables: [?ty_1], bias: Soundness }, decls: decls(222, [trait Iterator <ty> , trait IsU32 <ty> , trait Sum <ty> where {IsU32(<^ty0_0 as Iterator>::Item), Iterator(^ty0_0)}], [impl IsU32(u32), impl <ty> Sum(^ty0_0) where {<^ty0_0 as Iterator>::Item = u32, Iterator(^ty0_0)}, impl Iterator(Counter), impl Iterator(Bytes)], [], [alias <Counter as Iterator>::Item = u32, alias <Bytes as Iterator>::Item = u8], [], [adt Counter , adt Bytes ], {IsU32, Iterator, Sum}, {Bytes, Counter}) }`"#]]);
+}
+
+#[test]
+fn binding_follows_trait_parameters() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Convert<ty T> {
+                    type Output : [];
+                }
+                fn convert<ty I>(I) -> () where I: Convert<u8, Output = u32> { trusted }
+            }
+        ]",
+    );
+
+    expect_test::expect!["[crate core { trait Convert <ty, ty> { type Output : [] ; } fn convert <ty> (^ty0_0) -> () where ^ty0_0 : Convert <u8, Output = u32> { trusted} }]"].assert_eq(&format!("{:?}", program));
+}
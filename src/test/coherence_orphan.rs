@@ -279,3 +279,63 @@ fn CoreTraitLocal_for_AliasToKnown_in_Foo() {
     expect_test::expect!["()"]
     )
 }
+
+#[test]
+fn CoreTrait_for_Box_LocalType_in_Foo() {
+    crate::assert_ok!(
+        [
+            crate core {
+                trait CoreTrait {}
+                fundamental struct Box<ty T> {}
+            },
+            crate foo {
+                struct LocalType {}
+                impl CoreTrait for Box<LocalType> {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn CoreTrait_for_Vec_LocalType_in_Foo() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait CoreTrait {}
+                struct Vec<ty T> {}
+            },
+            crate foo {
+                struct LocalType {}
+                impl CoreTrait for Vec<LocalType> {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            orphan_check(impl CoreTrait for Vec<LocalType> { })
+
+            Caused by:
+                judgment `prove_wc_list { goal: {@ IsLocal(CoreTrait(Vec<LocalType>))}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType}) }` failed at the following rule(s):
+                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_wc { goal: @ IsLocal(CoreTrait(Vec<LocalType>)), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType}) }` failed at the following rule(s):
+                      the rule "trait ref is local" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `is_local_trait_ref { goal: CoreTrait(Vec<LocalType>), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType}) }` failed at the following rule(s):
+                          the rule "local parameter" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `is_local_parameter { goal: Vec<LocalType>, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType}) }` failed at the following rule(s):
+                              the rule "fundamental rigid type" failed at step #0 (src/file.rs:LL:CC) because
+                                condition evaluted to false: `is_fundamental(&decls, &name)`
+                                  &decls = decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType})
+                                  &name = (adt Vec)
+                              the rule "local rigid type" failed at step #0 (src/file.rs:LL:CC) because
+                                condition evaluted to false: `decls.is_local_adt_id(&a)`
+                                  decls = decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType})
+                                  &a = Vec
+                          the rule "local trait" failed at step #0 (src/file.rs:LL:CC) because
+                            condition evaluted to false: `decls.is_local_trait_id(&goal.trait_id)`
+                              decls = decls(222, [trait CoreTrait <ty> ], [impl CoreTrait(Vec<LocalType>)], [], [], [], [adt Vec <ty> , adt LocalType ], {}, {LocalType})
+                              &goal.trait_id = CoreTrait"#]]
+    )
+}
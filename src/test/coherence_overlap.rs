@@ -74,7 +74,8 @@ fn foo_crate_cannot_assume_CoreStruct_does_not_impl_CoreTrait() {
         expect_test::expect![[r#"
             impls may overlap:
             impl <ty> FooTrait for ^ty0_0 where ^ty0_0 : CoreTrait { }
-            impl FooTrait for CoreStruct { }"#]]
+            impl FooTrait for CoreStruct { }
+            both apply to `CoreStruct`"#]]
     )
 }
 
@@ -130,7 +131,8 @@ fn u32_T_where_T_Is_impls() {
         expect_test::expect![[r#"
             impls may overlap:
             impl Foo for u32 { }
-            impl <ty> Foo for ^ty0_0 where ^ty0_0 : Is { }"#]]
+            impl <ty> Foo for ^ty0_0 where ^ty0_0 : Is { }
+            both apply to `u32`"#]]
     )
 }
 
@@ -210,7 +212,8 @@ fn u32_T_impls() {
         expect_test::expect![[r#"
             impls may overlap:
             impl Foo for u32 { }
-            impl <ty> Foo for ^ty0_0 { }"#]]
+            impl <ty> Foo for ^ty0_0 { }
+            both apply to `u32`"#]]
     )
 }
 
@@ -236,7 +239,8 @@ fn T_and_T_bar() {
         expect_test::expect![[r#"
             impls may overlap:
             impl <ty> Foo for ^ty0_0 { }
-            impl <ty> Foo for ^ty0_0 where ^ty0_0 : Bar { }"#]]
+            impl <ty> Foo for ^ty0_0 where ^ty0_0 : Bar { }
+            both apply to `_`"#]]
     }
 }
 
@@ -264,7 +268,8 @@ fn T_and_Local_Bar_T() {
         expect_test::expect![[r#"
             impls may overlap:
             impl <ty> Foo for ^ty0_0 { }
-            impl <ty> Foo for ^ty0_0 where LocalType : Bar <^ty0_0> { }"#]]
+            impl <ty> Foo for ^ty0_0 where LocalType : Bar <^ty0_0> { }
+            both apply to `_`"#]]
     }
 }
 
@@ -331,6 +336,324 @@ fn is_local_with_unconstrained_self_ty_blanket_impl() {
         expect_test::expect![[r#"
             impls may overlap:
             impl <ty, ty> Overlap <^ty0_1> for ^ty0_0 where <^ty0_0 as Project>::Assoc : Foo <^ty0_1> { }
-            impl <ty> Overlap <LocalType> for ^ty0_0 { }"#]]
+            impl <ty> Overlap <LocalType> for ^ty0_0 { }
+            both apply to `_`"#]]
     }
 }
+
+#[test]
+fn coherence_report_lists_only_overlapping_pair() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Foo {}
+                trait Bar {}
+                trait Baz {}
+                struct A {}
+                struct B {}
+
+                impl Foo for A {}
+                impl Foo for B {}
+                impl<ty T> Bar for T {}
+                impl Bar for A {}
+                impl Baz for A {}
+            }
+        ]",
+    );
+    let report = formality_check::check_all_coherence(&program);
+    let violations: Vec<String> = report
+        .violations
+        .iter()
+        .map(|v| match v {
+            formality_check::CoherenceViolation::Orphan { item, .. } => format!("orphan: {item:?}"),
+            formality_check::CoherenceViolation::Overlap { impl_a, impl_b, .. } => {
+                format!("overlap: {impl_a:?} / {impl_b:?}")
+            }
+        })
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "overlap: impl Bar for A { } / impl <ty> Bar for ^ty0_0 { }",
+        ]
+    "#]]
+    .assert_debug_eq(&violations);
+}
+
+#[test]
+fn overlap_witness() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Foo {}
+                struct Vec<ty T> {}
+
+                impl<ty T> Foo for Vec<T> {}
+                impl Foo for Vec<u32> {}
+            }
+        ]",
+    );
+    let impls: Vec<formality_rust::grammar::TraitImpl> = program
+        .items_from_all_crates()
+        .filter_map(|item| match item {
+            formality_rust::grammar::CrateItem::TraitImpl(i) => Some(i.clone()),
+            _ => None,
+        })
+        .collect();
+    let formality_check::Overlap::Yes { witness } =
+        formality_check::impls_overlap(&program, &impls[0], &impls[1])
+    else {
+        panic!("expected the impls to overlap");
+    };
+    expect_test::expect!["Vec<u32>"].assert_eq(&format!("{witness:?}"));
+}
+
+/// The impls would overlap for `Vec<u32>` if `u32: Bar`, but the impl of `Bar`
+/// is implicitly for `T: Sized`, which only `u8` is.
+#[test]
+fn overlap_respects_implicit_sized_bound() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Sized {}
+                impl Sized for u8 {}
+
+                trait Bar {}
+                impl<ty T> Bar for T {}
+
+                trait Foo {}
+                struct Vec<ty T> {}
+                impl<ty T> Foo for Vec<T> where T: Bar {}
+                impl Foo for Vec<u32> {}
+            }
+        ]",
+    );
+    let impls: Vec<formality_rust::grammar::TraitImpl> = program
+        .items_from_all_crates()
+        .filter_map(|item| match item {
+            formality_rust::grammar::CrateItem::TraitImpl(i)
+                if *i.trait_id() == formality_types::grammar::TraitId::new("Foo") =>
+            {
+                Some(i.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        formality_check::impls_overlap(&program, &impls[0], &impls[1]),
+        formality_check::Overlap::No
+    );
+}
+
+/// A program where `A` requires `Marker`, which `S` (the only type implementing
+/// `B`) opts out of, as well as traits `C` and `D` that `u32` both implements,
+/// and traits `E` and `F` that are exclusive only for some types.
+fn exclusion_program() -> formality_rust::grammar::Program {
+    formality_types::rust::term(
+        "[
+            crate core {
+                trait Marker {}
+                struct S {}
+                struct Vec<ty T> {}
+                impl !Marker for S {}
+
+                trait A {}
+                trait B {}
+                impl<ty T> A for T where T: Marker {}
+                impl B for S {}
+
+                trait C {}
+                trait D {}
+                impl C for u32 {}
+                impl D for u32 {}
+
+                trait E {}
+                trait F {}
+                impl<ty T> E for Vec<T> where T: Marker {}
+                impl<ty T> F for Vec<T> {}
+            }
+        ]",
+    )
+}
+
+#[test]
+fn negative_impl_makes_traits_mutually_exclusive() {
+    let program = exclusion_program();
+    let exclusive = |a: &str, b: &str| {
+        formality_check::mutually_exclusive(
+            &program,
+            &formality_types::grammar::TraitId::new(a),
+            &formality_types::grammar::TraitId::new(b),
+        )
+    };
+    assert!(exclusive("A", "B"));
+    assert!(exclusive("B", "A"));
+}
+
+#[test]
+fn unrelated_traits_are_not_mutually_exclusive() {
+    let program = exclusion_program();
+    let exclusive = |a: &str, b: &str| {
+        formality_check::mutually_exclusive(
+            &program,
+            &formality_types::grammar::TraitId::new(a),
+            &formality_types::grammar::TraitId::new(b),
+        )
+    };
+    assert!(!exclusive("C", "D"));
+
+    // `Vec<S>` can only implement `F`, but `Vec<u32>` may implement both.
+    assert!(!exclusive("E", "F"));
+}
+
+/// Only `u8` is `Sized`, so the blanket impl of `A`, which is implicitly for
+/// `T: Sized`, does not apply to `u32`, the only type implementing `B`.
+#[test]
+fn implicit_sized_bound_makes_traits_mutually_exclusive() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Sized {}
+                impl Sized for u8 {}
+
+                trait A {}
+                trait B {}
+                impl<ty T> A for T {}
+                impl B for u32 {}
+            }
+        ]",
+    );
+    assert!(formality_check::mutually_exclusive(
+        &program,
+        &formality_types::grammar::TraitId::new("A"),
+        &formality_types::grammar::TraitId::new("B"),
+    ));
+}
+
+#[test]
+fn blanket_impl_overlaps_Box_Local() {
+    crate::assert_err!(
+        // `T = Box<Local>` satisfies both impls.
+        [
+            crate core {
+                fundamental struct Box<ty T> {}
+            },
+            crate foo {
+                trait Foo {}
+                struct Local {}
+                impl<ty T> Foo for T {}
+                impl Foo for Box<Local> {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            impl <ty> Foo for ^ty0_0 { }
+            impl Foo for Box<Local> { }
+            both apply to `Box<Local>`"#]]
+    )
+}
+
+#[test]
+fn CoreTrait_blanket_impl_does_not_overlap_Box_Local() {
+    crate::assert_ok!(
+        //@check-pass
+        // `Box` is fundamental, so `Box<Local>` is local and only `foo`
+        // may implement `CoreTrait` for it; it does not.
+        [
+            crate core {
+                trait CoreTrait {}
+                fundamental struct Box<ty T> {}
+            },
+            crate foo {
+                trait Foo {}
+                struct Local {}
+                impl<ty T> Foo for T where T: CoreTrait {}
+                impl Foo for Box<Local> {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn CoreTrait_blanket_impl_overlaps_Vec_Local() {
+    crate::assert_err!(
+        // `Vec` is not fundamental, so `core` may add `impl<T> CoreTrait for Vec<T>`.
+        [
+            crate core {
+                trait CoreTrait {}
+                struct Vec<ty T> {}
+            },
+            crate foo {
+                trait Foo {}
+                struct Local {}
+                impl<ty T> Foo for T where T: CoreTrait {}
+                impl Foo for Vec<Local> {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            impl <ty> Foo for ^ty0_0 where ^ty0_0 : CoreTrait { }
+            impl Foo for Vec<Local> { }
+            both apply to `Vec<Local>`"#]]
+    )
+}
+
+#[test]
+fn CoreTrait_blanket_impl_overlaps_Box_T() {
+    crate::assert_err!(
+        // A downstream crate may implement `CoreTrait` for `Box<Downstream>`.
+        [
+            crate core {
+                trait CoreTrait {}
+                fundamental struct Box<ty T> {}
+            },
+            crate foo {
+                trait Foo {}
+                impl<ty T> Foo for T where T: CoreTrait {}
+                impl<ty T> Foo for Box<T> {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            impl <ty> Foo for ^ty0_0 where ^ty0_0 : CoreTrait { }
+            impl <ty> Foo for Box<^ty0_0> { }
+            both apply to `Box<_>`"#]]
+    )
+}
+
+#[test]
+fn LocalTrait_blanket_impl_overlaps_Box_T() {
+    crate::assert_err!(
+        // `LocalTrait` is local, but a downstream crate may still implement it for
+        // `Box<Downstream>`, which is local to that crate because `Box` is fundamental.
+        [
+            crate core {
+                fundamental struct Box<ty T> {}
+            },
+            crate foo {
+                trait Foo {}
+                trait LocalTrait {}
+                impl<ty T> Foo for T where T: LocalTrait {}
+                impl<ty T> Foo for Box<T> {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            impl <ty> Foo for ^ty0_0 where ^ty0_0 : LocalTrait { }
+            impl <ty> Foo for Box<^ty0_0> { }
+            both apply to `Box<_>`"#]]
+    )
+}
@@ -0,0 +1,132 @@
+use formality_rust::grammar::Program;
+use formality_types::grammar::{DynTy, FnId, TraitId};
+
+/// `duplicate` returns `Self`, which a trait object cannot do, but it is
+/// gated by `Self: Sized` and so is simply unavailable on `dyn Shape`.
+#[test]
+fn sized_gated_method_is_excluded() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Shape {
+                    fn area<lt a>(&a Self) -> u32;
+                    fn duplicate<lt a>(&a Self) -> Self where Self: Sized;
+                }
+            }
+        ]",
+    );
+    let shape = TraitId::new("Shape");
+
+    formality_check::check_dyn_compatible(&program, &shape).unwrap();
+    expect_test::expect![[r#"
+        [
+            area,
+        ]
+    "#]]
+    .assert_debug_eq(&formality_check::dyn_callable_methods(&program, &shape).unwrap());
+}
+
+/// Without the `Self: Sized` gate, `duplicate` makes `Shape` dyn incompatible.
+#[test]
+fn ungated_method_returning_self() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Shape {
+                    fn area<lt a>(&a Self) -> u32;
+                    fn duplicate<lt a>(&a Self) -> Self;
+                }
+            }
+        ]",
+    );
+    let shape = TraitId::new("Shape");
+
+    let error = formality_check::check_dyn_compatible(&program, &shape).unwrap_err();
+    expect_test::expect![[r#"
+        check_dyn_compatible(Shape)
+
+        Caused by:
+            0: check_dyn_compatible_fn(duplicate)
+            1: the return type of the method mentions `Self`; add `where Self: Sized` to exclude it from trait objects"#]].assert_eq(&format!("{error:?}"));
+}
+
+/// A trait that requires `Self: Sized` cannot be used as a trait object at all.
+#[test]
+fn sized_trait_is_dyn_incompatible() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Shape where Self: Sized {
+                    fn area<lt a>(&a Self) -> u32;
+                }
+            }
+        ]",
+    );
+    let shape = TraitId::new("Shape");
+
+    let error = formality_check::check_dyn_compatible(&program, &shape).unwrap_err();
+    expect_test::expect![[r#"
+        check_dyn_compatible(Shape)
+
+        Caused by:
+            the trait requires `Self: Sized`"#]]
+    .assert_eq(&format!("{error:?}"));
+}
+
+/// A call on `dyn Shape` resolves to `area`, but not to the `Self: Sized`
+/// gated `duplicate`.
+#[test]
+fn dyn_method_call_skips_sized_gated_method() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Shape {
+                    fn area<lt a>(&a Self) -> u32;
+                    fn duplicate<lt a>(&a Self) -> Self where Self: Sized;
+                }
+            }
+        ]",
+    );
+    let dyn_ty: DynTy = formality_types::rust::term("dyn Shape");
+
+    let resolve = |method: &str| {
+        formality_check::dyn_method_candidate(&program, &dyn_ty, &FnId::new(method)).unwrap()
+    };
+    expect_test::expect![[r#"
+        (
+            Some(
+                Shape,
+            ),
+            None,
+        )
+    "#]]
+    .assert_debug_eq(&(resolve("area"), resolve("duplicate")));
+}
+
+/// `dyn Shape [Eq]` is not well-formed, as `Eq` is not an auto trait.
+#[test]
+fn dyn_ty_with_non_auto_trait() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Shape {
+                    fn area<lt a>(&a Self) -> u32;
+                }
+                trait Eq {}
+                auto trait Send {}
+            }
+        ]",
+    );
+
+    formality_check::check_dyn_ty(&program, &formality_types::rust::term("dyn Shape [Send]"))
+        .unwrap();
+    let error =
+        formality_check::check_dyn_ty(&program, &formality_types::rust::term("dyn Shape [Eq]"))
+            .unwrap_err();
+    expect_test::expect![[r#"
+        check_dyn_ty(dyn Shape [Eq])
+
+        Caused by:
+            `Eq` is not an auto trait"#]]
+    .assert_eq(&format!("{error:?}"));
+}
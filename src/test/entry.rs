@@ -0,0 +1,54 @@
+use formality_core::test_util::normalize_paths;
+use formality_rust::grammar::Program;
+use formality_types::grammar::FnId;
+
+/// `()` and `Result<(), E>` implement `Termination`; `u32` does not.
+fn program() -> Program {
+    formality_types::rust::term(
+        "[
+            crate core {
+                trait Termination {}
+                struct Result<ty T, ty E> {}
+                impl Termination for () {}
+                impl<ty E> Termination for Result<(), E> {}
+
+                fn main() -> () { trusted }
+                fn main_result() -> Result<(), u32> { trusted }
+                fn main_u32() -> u32 { trusted }
+                fn main_args(u32) -> () { trusted }
+            }
+        ]",
+    )
+}
+
+#[test]
+fn entry_returning_termination_types() {
+    let program = program();
+    formality_check::check_entry(&program, &FnId::new("main")).unwrap();
+    formality_check::check_entry(&program, &FnId::new("main_result")).unwrap();
+}
+
+#[test]
+fn entry_returning_non_termination_type() {
+    let error = formality_check::check_entry(&program(), &FnId::new("main_u32")).unwrap_err();
+    expect_test::expect![[r#"
+        check_entry(main_u32)
+
+        Caused by:
+            judgment `prove_wc_list { goal: {Termination(u32)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Termination <ty> ], [impl Termination(()), impl <ty> Termination(Result<(), ^ty0_0>)], [], [], [], [adt Result <ty, ty> ], {Termination}, {Result}) }` failed at the following rule(s):
+              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: Termination(u32), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Termination <ty> ], [impl Termination(()), impl <ty> Termination(Result<(), ^ty0_0>)], [], [], [], [adt Result <ty, ty> ], {Termination}, {Result}) }` failed at the following rule(s):
+                  the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                    expression evaluated to an empty collection: `decls.trait_invariants()`"#]].assert_eq(&normalize_paths(format!("{error:?}")));
+}
+
+#[test]
+fn entry_taking_arguments() {
+    let error = formality_check::check_entry(&program(), &FnId::new("main_args")).unwrap_err();
+    expect_test::expect![[r#"
+        check_entry(main_args)
+
+        Caused by:
+            the entry function must not take arguments"#]]
+    .assert_eq(&normalize_paths(format!("{error:?}")));
+}
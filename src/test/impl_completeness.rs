@@ -0,0 +1,97 @@
+#![allow(non_snake_case)]
+
+use formality_core::test;
+
+#[test]
+fn all_items_provided_or_defaulted() {
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Foo {
+                    type Assoc : [];
+                    fn required() -> ();
+                    fn defaulted() -> () { trusted }
+                }
+
+                impl Foo for u32 {
+                    type Assoc = ();
+                    fn required() -> () { trusted }
+                }
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn missing_required_fn() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Foo {
+                    fn provided() -> ();
+                    fn forgotten() -> ();
+                }
+
+                impl Foo for u32 {
+                    fn provided() -> () { trusted }
+                }
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            check_trait_impl(impl Foo for u32 { fn provided () -> () { trusted} })
+
+            Caused by:
+                not all trait items implemented, missing: fn `forgotten`"#]]
+    )
+}
+
+#[test]
+fn missing_assoc_ty() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Foo {
+                    type Assoc : [];
+                }
+
+                impl Foo for u32 {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            check_trait_impl(impl Foo for u32 { })
+
+            Caused by:
+                not all trait items implemented, missing: type `Assoc`"#]]
+    )
+}
+
+#[test]
+fn specializing_impl_inherits_items() {
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Foo {
+                    fn required() -> ();
+                }
+
+                default impl<ty T> Foo for T {
+                    fn required() -> () { trusted }
+                }
+
+                impl Foo for u32 {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
@@ -0,0 +1,253 @@
+use formality_core::Upcast;
+use formality_prove::Env;
+use formality_rust::grammar::Program;
+use formality_types::grammar::{
+    AdtId, AliasTy, AssociatedItemId, FnId, Lt, ParameterKind, TraitId, Ty,
+};
+
+/// `method` takes `&Self`, so on a `&T` receiver it resolves to `T: Trait`
+/// directly, or else after dereferencing and re-borrowing.
+#[test]
+fn trait_method_on_ref_receiver() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Trait {
+                    fn method<lt a>(&a Self) -> ();
+                }
+            }
+        ]",
+    );
+
+    let mut env = Env::default();
+    let t: Ty = env.fresh_universal(ParameterKind::Ty).upcast();
+    let assumptions = TraitId::new("Trait").with(&t, ()).is_implemented();
+    let receiver = t.ref_ty(Lt::static_());
+
+    let candidates = formality_check::method_candidates(
+        &program,
+        &env,
+        assumptions,
+        &receiver,
+        &FnId::new("method"),
+    );
+    let candidates: Vec<String> = candidates
+        .iter()
+        .map(|c| format!("{:?} {:?} {:?}", c.autoderefs, c.autoref, c.trait_ref))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "0 None Trait(!ty_1)",
+            "1 Some(shared) Trait(!ty_1)",
+        ]
+    "#]]
+    .assert_debug_eq(&candidates);
+}
+
+/// Without `T: Trait`, there is nothing to resolve to.
+#[test]
+fn trait_method_not_implemented() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Trait {
+                    fn method<lt a>(&a Self) -> ();
+                }
+            }
+        ]",
+    );
+
+    let mut env = Env::default();
+    let t: Ty = env.fresh_universal(ParameterKind::Ty).upcast();
+    let receiver = t.ref_ty(Lt::static_());
+
+    let candidates =
+        formality_check::method_candidates(&program, &env, (), &receiver, &FnId::new("method"));
+    assert_eq!(candidates, vec![]);
+}
+
+/// `Box<Vec<u8>>` dereferences to `Vec<u8>` and then to `[u8]`, which does
+/// not dereference any further.
+#[test]
+fn deref_chain_through_deref_impls() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Deref {
+                    type Target : [];
+                }
+
+                struct Box<ty T> {}
+                impl<ty T> Deref for Box<T> {
+                    type Target = T;
+                }
+
+                struct Vec<ty T> {}
+                impl<ty T> Deref for Vec<T> {
+                    type Target = [T];
+                }
+            }
+        ]",
+    );
+
+    let ty: Ty = formality_types::rust::term("Box<Vec<u8>>");
+    let chain: Vec<String> = formality_check::deref_chain(&program, &Env::default(), (), &ty)
+        .tys
+        .iter()
+        .map(|ty| format!("{ty:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "Box<Vec<u8>>",
+            "Vec<u8>",
+            "[u8]",
+        ]
+    "#]]
+    .assert_debug_eq(&chain);
+}
+
+/// `Box<T>` dereferences to the type parameter `T` itself.
+#[test]
+fn deref_chain_to_universal() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Deref {
+                    type Target : [];
+                }
+
+                struct Box<ty T> {}
+                impl<ty T> Deref for Box<T> {
+                    type Target = T;
+                }
+            }
+        ]",
+    );
+
+    let mut env = Env::default();
+    let t: Ty = env.fresh_universal(ParameterKind::Ty).upcast();
+    let ty = Ty::rigid(AdtId::new("Box"), vec![t.clone()]);
+    let chain: Vec<String> = formality_check::deref_chain(&program, &env, (), &ty)
+        .tys
+        .iter()
+        .map(|ty| format!("{ty:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "Box<!ty_1>",
+            "!ty_1",
+        ]
+    "#]]
+    .assert_debug_eq(&chain);
+}
+
+/// `Wrap<T>` dereferences to `Wrap<Wrap<T>>`, and so on without end, so the
+/// chain is cut off.
+#[test]
+fn deref_chain_through_expanding_deref() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Deref {
+                    type Target : [];
+                }
+
+                struct Wrap<ty T> {}
+                impl<ty T> Deref for Wrap<T> {
+                    type Target = Wrap<Wrap<T>>;
+                }
+            }
+        ]",
+    );
+
+    let ty: Ty = formality_types::rust::term("Wrap<u8>");
+    let chain = formality_check::deref_chain(&program, &Env::default(), (), &ty);
+    assert_eq!(chain.tys.len(), formality_check::MAX_AUTODEREFS + 1);
+}
+
+/// `Box<?X>` dereferences to the unknown `?X`, where the chain has to stop
+/// until `?X` is known.
+#[test]
+fn deref_chain_to_inference_variable_is_ambiguous() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Deref {
+                    type Target : [];
+                }
+
+                struct Box<ty T> {}
+                impl<ty T> Deref for Box<T> {
+                    type Target = T;
+                }
+            }
+        ]",
+    );
+
+    let mut env = Env::default();
+    let x: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let ty = Ty::rigid(AdtId::new("Box"), vec![x]);
+    let chain = formality_check::deref_chain(&program, &env, (), &ty);
+    expect_test::expect![[r#"
+        DerefChain {
+            tys: [
+                Ty {
+                    data: RigidTy(
+                        Box<?ty_1>,
+                    ),
+                },
+            ],
+            ambiguous: true,
+        }
+    "#]]
+    .assert_debug_eq(&chain);
+}
+
+/// For a receiver `<?X as Get>::Value`, the impl of `Get` for `u8`
+/// normalizes it, which gives candidates known to apply.
+#[test]
+fn trait_method_on_unnormalized_receiver_normalizes_through_impls() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Get {
+                    type Value : [];
+                }
+
+                impl Get for u8 {
+                    type Value = u32;
+                }
+
+                trait Trait {
+                    fn method(Self) -> ();
+                }
+
+                impl Trait for u32 {
+                    fn method(u32) -> () { trusted }
+                }
+            }
+        ]",
+    );
+
+    let mut env = Env::default();
+    let x: Ty = env.fresh_existential(ParameterKind::Ty).upcast();
+    let receiver: Ty = AliasTy::associated_ty(
+        TraitId::new("Get"),
+        AssociatedItemId::new("Value"),
+        0,
+        vec![x],
+    )
+    .upcast();
+    let candidates: Vec<String> =
+        formality_check::method_candidates(&program, &env, (), &receiver, &FnId::new("method"))
+            .iter()
+            .map(|c| format!("{:?} {:?}", c.trait_ref, c.ambiguous))
+            .collect();
+    expect_test::expect![[r#"
+        [
+            "Trait(u32) false",
+            "Trait(<u8 as Get>::Value) false",
+        ]
+    "#]]
+    .assert_debug_eq(&candidates);
+}
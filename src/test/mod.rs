@@ -1,10 +1,24 @@
 #![allow(non_snake_case)]
 
+mod adt_defaults;
+mod associated_ty_bounds;
 mod coherence_orphan;
 mod coherence_overlap;
 mod consts;
 mod decl_safety;
+mod dyn_compat;
+mod entry;
 mod functions;
+mod impl_completeness;
+mod methods;
+mod perfect_derive;
+mod program_clauses;
+mod prove_under;
+mod sized;
+mod specialization;
+mod unreachable_impls;
+mod wf_report;
+mod what_if;
 
 #[test]
 fn parser() {
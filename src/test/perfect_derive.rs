@@ -0,0 +1,54 @@
+use formality_rust::grammar::Program;
+use formality_types::grammar::{AdtId, TraitId};
+
+/// `Vec<T>: Clone` requires `T: Clone`, while `Rc<T>: Clone` holds for any `T`.
+fn program() -> Program {
+    formality_types::rust::term(
+        "[
+            crate core {
+                trait Clone {}
+                struct Vec<ty T> {}
+                struct Rc<ty T> {}
+                impl<ty T> Clone for Vec<T> where T: Clone {}
+                impl<ty T> Clone for Rc<T> {}
+
+                struct S<ty T> { field: Vec<T> }
+                struct Shared<ty T> { field: Rc<T> }
+            }
+        ]",
+    )
+}
+
+/// The perfect-derive bound `Vec<T>: Clone` needs `T: Clone`, so the
+/// conservative impl is already minimal.
+#[test]
+fn parameter_bound_needed_by_field() {
+    let derive =
+        formality_check::perfect_derive(&program(), &AdtId::new("S"), &TraitId::new("Clone"))
+            .unwrap();
+    expect_test::expect![[r#"
+        conservative: impl <ty> Clone for S<^ty0_0> where ^ty0_0 : Clone { }
+        perfect: impl <ty> Clone for S<^ty0_0> where Vec<^ty0_0> : Clone { }
+        minimal: impl <ty> Clone for S<^ty0_0> where ^ty0_0 : Clone { }"#]]
+    .assert_eq(&format!(
+        "conservative: {:?}\nperfect: {:?}\nminimal: {:?}",
+        derive.conservative, derive.perfect, derive.minimal
+    ));
+    assert_eq!(derive.minimal, derive.conservative);
+}
+
+/// `Rc<T>: Clone` holds without `T: Clone`, so the minimal impl drops it.
+#[test]
+fn parameter_bound_not_needed_by_field() {
+    let derive =
+        formality_check::perfect_derive(&program(), &AdtId::new("Shared"), &TraitId::new("Clone"))
+            .unwrap();
+    expect_test::expect![[r#"
+        conservative: impl <ty> Clone for Shared<^ty0_0> where ^ty0_0 : Clone { }
+        perfect: impl <ty> Clone for Shared<^ty0_0> where Rc<^ty0_0> : Clone { }
+        minimal: impl <ty> Clone for Shared<^ty0_0> { }"#]]
+    .assert_eq(&format!(
+        "conservative: {:?}\nperfect: {:?}\nminimal: {:?}",
+        derive.conservative, derive.perfect, derive.minimal
+    ));
+}
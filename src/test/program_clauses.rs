@@ -0,0 +1,123 @@
+use formality_rust::grammar::Program;
+use formality_types::grammar::Wcs;
+
+/// A snapshot of everything a small program lowers to, including the trait
+/// invariant from `Ord`'s supertrait and the rule for `Iterator::Item`.
+#[test]
+fn lowered_clauses() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait PartialOrd {}
+                trait Ord where Self: PartialOrd {}
+                trait Iterator {
+                    type Item : [];
+                }
+
+                struct Counter {}
+                impl PartialOrd for u32 {}
+                impl Ord for u32 {}
+                impl !Ord for Counter {}
+                impl Iterator for Counter {
+                    type Item = u32;
+                }
+            }
+        ]",
+    );
+    let clauses: Vec<String> = program
+        .to_prove_decls()
+        .program_clauses()
+        .iter()
+        .map(|c| format!("{c:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "impl PartialOrd(u32)",
+            "impl Ord(u32)",
+            "impl Iterator(Counter)",
+            "impl ! Ord(Counter)",
+            "alias <Counter as Iterator>::Item = u32",
+            "trait_invariant(<ty> Ord(^ty0_0) => PartialOrd(^ty0_0))",
+            "trait PartialOrd <ty> ",
+            "trait Ord <ty> where {PartialOrd(^ty0_0)}",
+            "trait Iterator <ty> ",
+        ]
+    "#]]
+    .assert_debug_eq(&clauses);
+}
+
+/// The value of `Item` in a generic impl lowers to an alias clause through
+/// which the solver normalizes the projection for a concrete `Vec`.
+#[test]
+fn associated_type_value_normalizes() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Iterator {
+                    type Item : [];
+                }
+
+                struct Vec<ty T> {}
+                impl<ty T> Iterator for Vec<T> {
+                    type Item = T;
+                }
+            }
+        ]",
+    );
+    let decls = program.to_prove_decls();
+    let clauses: Vec<String> = decls
+        .program_clauses()
+        .iter()
+        .map(|c| format!("{c:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "impl <ty> Iterator(Vec<^ty0_0>)",
+            "alias <ty> <Vec<^ty0_0> as Iterator>::Item = ^ty0_0",
+            "trait Iterator <ty> ",
+        ]
+    "#]]
+    .assert_debug_eq(&clauses);
+
+    let goal: Wcs = formality_types::rust::term("{<Vec<i32> as Iterator>::Item = i32}");
+    assert!(formality_prove::prove(&decls, (), (), &goal).is_proven());
+    let goal: Wcs = formality_types::rust::term("{<Vec<i32> as Iterator>::Item = u32}");
+    assert!(!formality_prove::prove(&decls, (), (), &goal).is_proven());
+}
+
+/// A negative impl lowers to a clause of its own, from which the solver
+/// proves that the type does not implement the trait.
+#[test]
+fn negative_impl_proves_not_implemented() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Send {}
+                impl !Send for i32 {}
+                impl Send for u32 {}
+            }
+        ]",
+    );
+    let decls = program.to_prove_decls();
+    let clauses: Vec<String> = decls
+        .program_clauses()
+        .iter()
+        .map(|c| format!("{c:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "impl Send(u32)",
+            "impl ! Send(i32)",
+            "trait Send <ty> ",
+        ]
+    "#]]
+    .assert_debug_eq(&clauses);
+
+    let proven = |goal: &str| {
+        let goal: Wcs = formality_types::rust::term(goal);
+        formality_prove::prove(&decls, (), (), &goal).is_proven()
+    };
+    assert!(proven("{!Send(i32)}"));
+    assert!(!proven("{Send(i32)}"));
+    assert!(!proven("{!Send(u32)}"));
+}
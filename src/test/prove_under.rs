@@ -0,0 +1,48 @@
+use formality_core::test;
+use formality_rust::grammar::Program;
+use formality_types::rust::term;
+
+use crate::{assert_not_provable_under, assert_provable_under};
+
+const PROGRAM: &str = "[
+    crate core {
+        trait Super {}
+        trait Sub where Self: Super {}
+        struct Local {}
+        impl Super for Local {}
+    }
+]";
+
+#[test]
+fn supertrait_is_elaborated_from_assumption() {
+    let program: Program = term(PROGRAM);
+    assert_provable_under(&["Sub(T)"], "Super(T)", &program);
+}
+
+#[test]
+fn subtrait_does_not_follow_from_supertrait() {
+    let program: Program = term(PROGRAM);
+    assert_not_provable_under(&["Super(T)"], "Sub(T)", &program);
+}
+
+#[test]
+fn impls_of_the_program_are_used() {
+    let program: Program = term(PROGRAM);
+    assert_provable_under(&[], "Super(Local)", &program);
+    assert_not_provable_under(&[], "Super(T)", &program);
+}
+
+#[test]
+#[should_panic(expected = "`Sub(Local)` is not provable under []")]
+fn failure_is_reported() {
+    let program: Program = term(PROGRAM);
+    assert_provable_under(&[], "Sub(Local)", &program);
+}
+
+/// `T` and `U` are not declared, so they parse as two distinct rigid types.
+#[test]
+fn undeclared_names_are_distinct_types() {
+    let program: Program = term(PROGRAM);
+    assert_provable_under(&[], "T = T", &program);
+    assert_not_provable_under(&[], "T = U", &program);
+}
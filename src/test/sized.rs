@@ -0,0 +1,122 @@
+#![allow(non_snake_case)]
+
+use formality_rust::grammar::Program;
+use formality_types::rust::term;
+
+#[test]
+fn elaborate_fn_params() {
+    let program: Program = term(
+        "[
+            crate core {
+                trait Sized {}
+                fn sized<ty T>(T) -> () { trusted }
+                fn maybe_sized<ty T>(T) -> () where T: ?Sized { trusted }
+            }
+        ]",
+    );
+
+    expect_test::expect!["[crate core { trait Sized <ty> { } fn sized <ty> (^ty0_0) -> () where ^ty0_0 : Sized { trusted} fn maybe_sized <ty> (^ty0_0) -> () where ^ty0_0 :? Sized { trusted} }]"]
+    .assert_eq(&format!("{:?}", program.elaborate_sized()));
+}
+
+#[test]
+fn elaborate_without_sized_trait_is_identity() {
+    let program: Program = term(
+        "[
+            crate core {
+                fn sized<ty T>(T) -> () { trusted }
+            }
+        ]",
+    );
+
+    assert_eq!(program.elaborate_sized(), program);
+}
+
+#[test]
+fn maybe_sized_param_in_sized_position() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Sized {}
+                struct Foo<ty T> {}
+                fn foo<ty T>(Foo<T>) -> () where T: ?Sized { trusted }
+            }
+        ]
+
+        [
+            "Sized(!ty_0)",
+        ]
+
+        expect_test::expect![[r#"
+            judgment `prove_wc_list { goal: {@ wf(Foo<!ty_0>)}, assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: @ wf(Foo<!ty_0>), assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+                  the rule "parameter well formed" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_wf { goal: Foo<!ty_0>, assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+                      the rule "ADT" failed at step #3 (src/file.rs:LL:CC) because
+                        judgment `prove_after { constraints: Constraints { env: Env { variables: [!ty_0], bias: Soundness }, known_true: true, substitution: {} }, goal: {Sized(!ty_0)}, assumptions: {}, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+                          the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                            judgment `prove_wc_list { goal: {Sized(!ty_0)}, assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+                              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                judgment `prove_wc { goal: Sized(!ty_0), assumptions: {}, env: Env { variables: [!ty_0], bias: Soundness }, decls: decls(222, [trait Sized <ty> ], [], [], [], [], [adt Foo <ty> where {Sized(^ty0_0)}], {Sized}, {Foo}) }` failed at the following rule(s):
+                                  the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                                    expression evaluated to an empty collection: `decls.trait_invariants()`"#]]
+    )
+}
+
+#[test]
+fn recursive_type_through_indirection() {
+    crate::assert_ok!(
+        //@check-pass
+        // `List` refers to itself through `Box`, which does not contain its
+        // parameter by value, so it has a finite size. Its field types are
+        // well-formed given `T: Sized`, its implicit bound.
+        [
+            crate core {
+                trait Sized {}
+                struct Box<ty T> where T: ?Sized {}
+                impl<ty T> Sized for Box<T> where T: ?Sized {}
+                enum Option<ty T> { None {}, Some { value: T } }
+                struct List<ty T> { next: Option<Box<List<T>>>, val: T }
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn recursive_type_by_value() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Sized {}
+                struct Bad { b: Bad }
+            }
+        ]
+
+        [
+            "infinite size",
+        ]
+
+        expect_test::expect!["recursive type `Bad` has infinite size: field `b` contains it without an indirection"]
+    )
+}
+
+#[test]
+fn recursive_type_through_other_type() {
+    crate::assert_err!(
+        [
+            crate core {
+                struct Wrapper<ty T> { value: (u32, T) }
+                enum Bad { Leaf {}, Node { children: [Wrapper<Bad>; 2_usize] } }
+            }
+        ]
+
+        [
+            "infinite size",
+        ]
+
+        expect_test::expect!["recursive type `Bad` has infinite size: field `children` contains it without an indirection"]
+    )
+}
@@ -0,0 +1,161 @@
+#![allow(non_snake_case)]
+
+use formality_core::test;
+
+#[test]
+fn default_impl_specialized_by_u32() {
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Foo {}
+                default impl<ty T> Foo for T {}
+                impl Foo for u32 {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn default_impl_specialized_under_where_clause() {
+    crate::assert_ok!(
+        //@check-pass
+        // The specializing impl only applies to `Bar` types, and the default impl
+        // applies to all of them.
+        [
+            crate core {
+                trait Foo {}
+                trait Bar {}
+                default impl<ty T> Foo for T {}
+                impl<ty T> Foo for Vec<T> where T: Bar {}
+                struct Vec<ty T> {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn non_default_impl_cannot_be_specialized() {
+    crate::assert_err!(
+        [
+            crate core {
+                trait Foo {}
+                impl<ty T> Foo for T {}
+                impl Foo for u32 {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            impl <ty> Foo for ^ty0_0 { }
+            impl Foo for u32 { }
+            both apply to `u32`"#]]
+    )
+}
+
+#[test]
+fn default_impl_not_more_general() {
+    crate::assert_err!(
+        // The impls overlap at `(u32, u32)` but neither applies whenever the
+        // other does, so being `default` does not help.
+        [
+            crate core {
+                trait Foo {}
+                default impl<ty T> Foo for (T, u32) {}
+                impl<ty T> Foo for (u32, T) {}
+            }
+        ]
+
+        [ /* TODO */ ]
+
+        expect_test::expect![[r#"
+            impls may overlap:
+            default impl <ty> Foo for (^ty0_0, u32) { }
+            impl <ty> Foo for (u32, ^ty0_0) { }
+            both apply to `(u32, u32)`"#]]
+    )
+}
+
+#[test]
+fn default_impl_specialized_by_array_length() {
+    crate::assert_ok!(
+        //@check-pass
+        [
+            crate core {
+                trait Foo {}
+                default impl<const N> Foo for [u8; N] where type_of_const N is usize {}
+                impl Foo for [u8; 0_usize] {}
+            }
+        ]
+
+        expect_test::expect!["()"]
+    )
+}
+
+#[test]
+fn select_impl_by_array_length() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Foo {}
+                default impl<const N> Foo for [u8; N] where type_of_const N is usize {}
+                impl Foo for [u8; 0_usize] {}
+            }
+        ]",
+    );
+    let select = |trait_ref: &str| {
+        let trait_ref: formality_types::grammar::TraitRef = formality_types::rust::term(trait_ref);
+        formality_check::select_impl(&program, &trait_ref).map(|i| format!("{i:?}"))
+    };
+
+    expect_test::expect![[r#"
+        Some(
+            "impl Foo for [u8; const value(0, usize)] { }",
+        )
+    "#]]
+    .assert_debug_eq(&select("Foo([u8; 0_usize])"));
+    expect_test::expect![[r#"
+        Some(
+            "default impl <const> Foo for [u8; const ^const0_0] where type_of_const ^const0_0 is usize { }",
+        )
+    "#]]
+    .assert_debug_eq(&select("Foo([u8; 3_usize])"));
+}
+
+/// Only `u8` is `Sized`, so the blanket impl, which is implicitly for
+/// `T: Sized`, supplies `Foo(u8)` but not `Foo(u32)`.
+#[test]
+fn select_impl_respects_implicit_sized_bound() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Sized {}
+                impl Sized for u8 {}
+
+                trait Foo {}
+                impl<ty T> Foo for T {}
+            }
+        ]",
+    );
+    let select = |trait_ref: &str| {
+        let trait_ref: formality_types::grammar::TraitRef = formality_types::rust::term(trait_ref);
+        formality_check::select_impl(&program, &trait_ref).map(|i| format!("{i:?}"))
+    };
+
+    expect_test::expect![[r#"
+        Some(
+            "impl <ty> Foo for ^ty0_0 where ^ty0_0 : Sized { }",
+        )
+    "#]]
+    .assert_debug_eq(&select("Foo(u8)"));
+    expect_test::expect![[r#"
+        None
+    "#]]
+    .assert_debug_eq(&select("Foo(u32)"));
+}
@@ -0,0 +1,63 @@
+use formality_rust::grammar::Program;
+
+/// No type is both `Red` and `Blue`, so the impl for `T: Red + Blue` never
+/// applies, while the one for `T: Red` applies to `u32`.
+#[test]
+fn contradictory_where_clauses_are_flagged() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Red {}
+                trait Blue {}
+                trait Paint {}
+                trait Purple {}
+
+                impl Red for u32 {}
+                impl Blue for u8 {}
+                impl<ty T> Paint for T where T: Red {}
+                impl<ty T> Purple for T where T: Red, T: Blue {}
+            }
+        ]",
+    );
+    let unreachable: Vec<String> = formality_check::lint_unreachable_impls(&program)
+        .iter()
+        .map(|i| format!("{i:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "impl <ty> Purple for ^ty0_0 where ^ty0_0 : Red, ^ty0_0 : Blue { }",
+        ]
+    "#]]
+    .assert_debug_eq(&unreachable);
+}
+
+/// Only `u8` is `Sized`, and the impl of `Red` is implicitly for `T: Sized`,
+/// so `Vec<u32>` is not `Red` and the impl of `Paint` never applies.
+#[test]
+fn implicit_sized_bounds_are_taken_into_account() {
+    let program: Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Sized {}
+                impl Sized for u8 {}
+
+                struct Vec<ty T> {}
+                trait Red {}
+                trait Paint {}
+
+                impl<ty T> Red for Vec<T> {}
+                impl Paint for Vec<u32> where Vec<u32>: Red {}
+            }
+        ]",
+    );
+    let unreachable: Vec<String> = formality_check::lint_unreachable_impls(&program)
+        .iter()
+        .map(|i| format!("{i:?}"))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "impl Paint for Vec<u32> where Vec<u32> : Red { }",
+        ]
+    "#]]
+    .assert_debug_eq(&unreachable);
+}
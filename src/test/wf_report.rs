@@ -0,0 +1,31 @@
+/// `A` and `B` are each ill-formed on their own, so both are reported, while
+/// the well-formed items around them are not.
+#[test]
+fn independent_failures_are_all_reported() {
+    let program: formality_rust::grammar::Program = formality_types::rust::term(
+        "[
+            crate core {
+                trait Foo {}
+                impl Foo for u32 {}
+                struct Bar<ty T> where T: Foo {}
+
+                struct A { x: Bar<u8> }
+                struct Fine { x: Bar<u32> }
+                struct B { y: Bar<i32> }
+            }
+        ]",
+    );
+    let report = formality_check::check_program_wf(&program);
+    let failures: Vec<String> = report
+        .failures
+        .iter()
+        .map(|f| format!("{:?}: {:?}", f.crate_id, f.item))
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "core: struct A { x : Bar<u8> }",
+            "core: struct B { y : Bar<i32> }",
+        ]
+    "#]]
+    .assert_debug_eq(&failures);
+}
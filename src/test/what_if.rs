@@ -0,0 +1,39 @@
+use formality_check::what_if;
+use formality_core::test;
+use formality_rust::grammar::{Program, TraitImpl};
+use formality_types::{grammar::Wc, rust::term};
+
+const PROGRAM: &str = "[
+    crate core {
+        trait Foo {}
+        struct Bar {}
+        impl Foo for u32 {}
+    }
+]";
+
+#[test]
+fn added_impl_makes_goal_provable() {
+    let program: Program = term(PROGRAM);
+    let added: TraitImpl = term("impl Foo for Bar {}");
+    let goal: Wc = term("Foo(Bar)");
+    let result = what_if(&program, vec![added], (), (), goal);
+    assert!(!result.before.is_proven());
+    assert!(result.after.is_proven());
+    assert!(result.coherence_error.is_none());
+}
+
+#[test]
+fn added_impl_that_overlaps_is_flagged() {
+    let program: Program = term(PROGRAM);
+    let added: TraitImpl = term("impl<ty T> Foo for T {}");
+    let goal: Wc = term("Foo(Bar)");
+    let result = what_if(&program, vec![added], (), (), goal);
+    assert!(!result.before.is_proven());
+    assert!(result.after.is_proven());
+    expect_test::expect![[r#"
+        impls may overlap:
+        impl Foo for u32 { }
+        impl <ty> Foo for ^ty0_0 { }
+        both apply to `u32`"#]]
+    .assert_eq(&format!("{:?}", result.coherence_error.unwrap()));
+}
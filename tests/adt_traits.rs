@@ -0,0 +1,151 @@
+use a_mir_formality::{test_program_ok, test_where_clause};
+use formality_core::test_util::ResultTestExt;
+
+const ADTS: &str = "[
+    crate test {
+        auto trait Send {}
+
+        impl Send for u32 {}
+
+        struct Rc {}
+
+        impl !Send for Rc {}
+
+        enum Plain {
+            Number { value: u32 },
+            Nothing {},
+        }
+
+        enum Message {
+            Text { len: u32 },
+            Shared { rc: Rc },
+        }
+
+        enum Opt<ty T> {
+            Some { value: T },
+            None {},
+        }
+    }
+]";
+
+#[test]
+fn enum_with_send_fields_is_send() {
+    test_where_clause(ADTS, "{} => { Send(Plain) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn enum_with_non_send_field_in_one_variant_is_not_send() {
+    test_where_clause(ADTS, "{} => { Send(Message) }").assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {Send(Message)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Send(Message), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+              the rule "positive impl" failed at step #6 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }, goal: {Send(Rc), Send(u32)}, assumptions: {Send(Message)}, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {Send(Rc), Send(u32)}, assumptions: {Send(Message)}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Send(Rc), assumptions: {Send(Message)}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                          the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                            expression evaluated to an empty collection: `decls.trait_invariants()`
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+#[test]
+fn generic_enum_is_send_if_its_parameter_is() {
+    test_where_clause(ADTS, "{} => { Send(Opt<u32>) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+    test_where_clause(ADTS, "{} => { Send(Opt<Rc>) }").assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {Send(Opt<Rc>)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Send(Opt<Rc>), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+              the rule "positive impl" failed at step #6 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => Rc} }, goal: {Send(?ty_1)}, assumptions: {Send(Opt<Rc>)}, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {Send(Rc)}, assumptions: {Send(Opt<Rc>)}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Send(Rc), assumptions: {Send(Opt<Rc>)}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                          the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                            expression evaluated to an empty collection: `decls.trait_invariants()`
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+#[test]
+fn array_is_send_if_its_element_is() {
+    test_where_clause(ADTS, "{} => { Send([u32; 4_usize]) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+    test_where_clause(ADTS, "{} => { Send([Rc; 4_usize]) }").assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {Send([Rc; const value(4, usize)])}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Send([Rc; const value(4, usize)]), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+              the rule "positive impl" failed at step #6 (src/file.rs:LL:CC) because
+                judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1, ?const_2], bias: Soundness }, known_true: true, substitution: {?ty_1 => Rc, ?const_2 => const value(4, usize)} }, goal: {Send(?ty_1)}, assumptions: {Send([Rc; const value(4, usize)])}, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                  the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_wc_list { goal: {Send(Rc)}, assumptions: {Send([Rc; const value(4, usize)])}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                      the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_wc { goal: Send(Rc), assumptions: {Send([Rc; const value(4, usize)])}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [coinductive trait Send <ty> ], [impl Send(u32), impl Send(Plain) where {Send(u32)}, impl Send(Message) where {Send(Rc), Send(u32)}, impl <ty> Send(Opt<^ty0_0>) where {Send(^ty0_0)}, impl <ty, const> Send([^ty0_0; const ^const0_1]) where {Send(^ty0_0)}, impl <ty> Send([^ty0_0; const value(0, usize)])], [impl ! Send(Rc)], [], [], [adt Rc , adt Plain , adt Message , adt Opt <ty> ], {Send}, {Message, Opt, Plain, Rc}) }` failed at the following rule(s):
+                          the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                            expression evaluated to an empty collection: `decls.trait_invariants()`
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
+
+#[test]
+fn empty_array_is_send_whatever_its_element() {
+    test_where_clause(ADTS, "{} => { Send([Rc; 0_usize]) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn array_of_symbolic_length_is_send() {
+    test_where_clause(ADTS, "forall<const N> {} => { Send([u32; N]) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [!const_1], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn valid_enum_is_well_formed() {
+    test_where_clause(ADTS, "{} => { @wf(Opt<Plain>) }").assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn union_with_copy_fields() {
+    test_program_ok(
+        "[
+            crate test {
+                trait Copy {}
+                impl Copy for u32 {}
+                union Bits { int: u32 }
+            }
+        ]",
+    )
+    .assert_ok(expect_test::expect!["()"]);
+}
+
+#[test]
+fn union_without_copy_trait() {
+    test_program_ok(
+        "[
+            crate test {
+                struct Rc {}
+                union Bits { rc: Rc }
+            }
+        ]",
+    )
+    .assert_ok(expect_test::expect!["()"]);
+}
+
+#[test]
+fn union_with_field_needing_drop() {
+    test_program_ok(
+        "[
+            crate test {
+                trait Copy {}
+                struct Rc {}
+                union Bits { rc: Rc }
+            }
+        ]",
+    )
+    .assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {Copy(Rc)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> ], [], [], [], [], [adt Rc , adt Bits ], {Copy}, {Bits, Rc}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: Copy(Rc), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> ], [], [], [], [], [adt Rc , adt Bits ], {Copy}, {Bits, Rc}) }` failed at the following rule(s):
+              the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
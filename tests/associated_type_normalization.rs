@@ -19,3 +19,48 @@ fn test_mirror_normalizes_u32_to_u32() {
         expect_test::expect!["{Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => u32} }, Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => <u32 as Mirror>::Assoc} }}"],
     );
 }
+
+const COPY_ASSOC: &str = "[
+    crate core {
+        trait Copy {}
+
+        trait Get {
+            type Assoc : [] where Self: Copy;
+        }
+
+        struct Foo {}
+
+        impl Copy for u32 {}
+
+        impl<ty T> Get for T {
+            type Assoc = T;
+        }
+    }
+]";
+
+#[test]
+fn test_assoc_where_clause_satisfied() {
+    test_where_clause(COPY_ASSOC, "{} => {<u32 as Get>::Assoc = u32}")
+        .assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn test_assoc_where_clause_unsatisfied() {
+    test_where_clause(COPY_ASSOC, "{} => {<Foo as Get>::Assoc = Foo}")
+        .assert_err(expect_test::expect![[r#"
+            judgment `prove_wc_list { goal: {<Foo as Get>::Assoc = Foo}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+              the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wc { goal: <Foo as Get>::Assoc = Foo, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                  the rule "eq" failed at step #0 (src/file.rs:LL:CC) because
+                    judgment `prove_eq { a: <Foo as Get>::Assoc, b: Foo, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                      the rule "normalize-l" failed at step #0 (src/file.rs:LL:CC) because
+                        judgment `prove_normalize { p: <Foo as Get>::Assoc, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                          the rule "normalize-via-impl" failed at step #6 (src/file.rs:LL:CC) because
+                            judgment `prove_after { constraints: Constraints { env: Env { variables: [?ty_1], bias: Soundness }, known_true: true, substitution: {?ty_1 => Foo} }, goal: {Copy(?ty_1)}, assumptions: {}, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                              the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment `prove_wc_list { goal: {Copy(Foo)}, assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                                  the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                                    judgment `prove_wc { goal: Copy(Foo), assumptions: {}, env: Env { variables: [], bias: Soundness }, decls: decls(222, [trait Copy <ty> , trait Get <ty> ], [impl Copy(u32), impl <ty> Get(^ty0_0)], [], [alias <ty> <^ty0_0 as Get>::Assoc = ^ty0_0 where {Copy(^ty0_0)}], [], [adt Foo ], {Copy, Get}, {Foo}) }` failed at the following rule(s):
+                                      the rule "trait implied bound" failed at step #0 (src/file.rs:LL:CC) because
+                                        expression evaluated to an empty collection: `decls.trait_invariants()`"#]]);
+}
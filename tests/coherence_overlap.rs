@@ -51,7 +51,8 @@ fn test_overlap_normalize_alias_to_LocalType() {
         expect_test::expect![[r#"
             impls may overlap:
             impl <ty> LocalTrait for ^ty0_0 where ^ty0_0 : Iterator { }
-            impl LocalTrait for <LocalType as Mirror>::T { }"#]],
+            impl LocalTrait for <LocalType as Mirror>::T { }
+            both apply to `LocalType`"#]],
     );
 }
 
@@ -95,7 +96,8 @@ fn test_overlap_alias_not_normalizable() {
     test_program_ok(&gen_program("")).assert_err(expect_test::expect![[r#"
         impls may overlap:
         impl <ty> LocalTrait for ^ty0_0 where ^ty0_0 : Iterator { }
-        impl <ty> LocalTrait for <^ty0_0 as Mirror>::T where ^ty0_0 : Mirror { }"#]]);
+        impl <ty> LocalTrait for <^ty0_0 as Mirror>::T where ^ty0_0 : Mirror { }
+        both apply to `_`"#]]);
 
     // ...and if there is at least one Iterator impl, we also flag an error.
 
@@ -103,6 +105,7 @@ fn test_overlap_alias_not_normalizable() {
         r#"
         impls may overlap:
         impl <ty> LocalTrait for ^ty0_0 where ^ty0_0 : Iterator { }
-        impl <ty> LocalTrait for <^ty0_0 as Mirror>::T where ^ty0_0 : Mirror { }"#
+        impl <ty> LocalTrait for <^ty0_0 as Mirror>::T where ^ty0_0 : Mirror { }
+        both apply to `u32`"#
     ]]);
 }
@@ -0,0 +1,60 @@
+//! Upcasts and downcasts derived for grammar nodes defined outside `#[term]`.
+
+use formality_core::{Downcast, DowncastTo, Upcast};
+
+#[derive(Clone, Debug, PartialEq, Eq, Upcast, Downcast)]
+struct Name(String);
+
+#[derive(Clone, Debug, PartialEq, Eq, Upcast, Downcast)]
+struct Number(u32);
+
+#[derive(Clone, Debug, PartialEq, Eq, Upcast, Downcast)]
+enum Expr {
+    #[cast]
+    Name(Name),
+    #[cast]
+    Number(Number),
+    Pair(Box<Expr>, Box<Expr>),
+}
+
+#[test]
+fn enum_round_trips_through_cast_variants() {
+    let name = Name("x".to_string());
+    let expr: Expr = name.clone().upcast();
+    assert_eq!(expr, Expr::Name(name.clone()));
+    assert_eq!(expr.downcast::<Name>(), Some(name));
+    assert_eq!(expr.downcast::<Number>(), None);
+    assert_eq!(expr.as_name(), Some(&Name("x".to_string())));
+
+    let number: Expr = Number(1).upcast();
+    assert_eq!(number.downcast::<Number>(), Some(Number(1)));
+}
+
+#[test]
+fn variant_without_cast_is_not_a_downcast_target() {
+    let pair = Expr::Pair(
+        Box::new(Number(1).upcast()),
+        Box::new(Name("y".to_string()).upcast()),
+    );
+    assert_eq!(pair.downcast::<Name>(), None);
+    assert_eq!(pair.downcast_to(), Some(pair.clone()));
+}
+
+#[test]
+fn newtype_round_trips_through_its_field() {
+    let name: Name = "x".to_string().upcast();
+    assert_eq!(name, Name("x".to_string()));
+    assert_eq!(name.downcast::<String>(), Some("x".to_string()));
+}
+
+#[test]
+fn try_downcast_reports_what_was_found() {
+    let expr: Expr = Number(1).upcast();
+    assert_eq!(expr.try_downcast::<Number>(), Ok(Number(1)));
+
+    let error = expr.try_downcast::<Name>().unwrap_err();
+    expect_test::expect![
+        "expected a `derive_cast::Name`, found `Number(Number(1))` of type `derive_cast::Expr`"
+    ]
+    .assert_eq(&error.to_string());
+}
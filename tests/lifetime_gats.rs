@@ -0,0 +1,60 @@
+use a_mir_formality::test_where_clause;
+use formality_core::test_util::ResultTestExt;
+
+const LIFETIME_GAT: &str = "[
+    crate test {
+        trait Foo {
+            type Ref<lt a> : [];
+        }
+
+        struct Bar {}
+
+        struct Borrowed<lt a> {}
+
+        impl Foo for Bar {
+            type Ref<lt a> = Borrowed<a>;
+        }
+    }
+]";
+
+#[test]
+fn normalize_lifetime_gat() {
+    test_where_clause(
+        LIFETIME_GAT,
+        "forall<lt a> exists<ty U> {} => { <Bar as Foo>::Ref<a> = U }",
+    )
+    .assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [!lt_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_2 => Borrowed<!lt_1>} }, Constraints { env: Env { variables: [!lt_1, ?ty_2], bias: Soundness }, known_true: true, substitution: {?ty_2 => <Bar as Foo>::Ref<!lt_1>} }}"]);
+}
+
+#[test]
+fn lifetime_gat_wf_with_outlives() {
+    test_where_clause(
+        LIFETIME_GAT,
+        "forall<ty T, lt a> { Foo(T), T : a } => { @wf(<T as Foo>::Ref<a>) }",
+    )
+    .assert_ok(expect_test::expect!["{Constraints { env: Env { variables: [!ty_1, !lt_2], bias: Soundness }, known_true: true, substitution: {} }}"]);
+}
+
+#[test]
+fn lifetime_gat_wf_requires_outlives() {
+    test_where_clause(
+        LIFETIME_GAT,
+        "forall<ty T, lt a> { Foo(T) } => { @wf(<T as Foo>::Ref<a>) }",
+    )
+    .assert_err(expect_test::expect![[r#"
+        judgment `prove_wc_list { goal: {@ wf(<!ty_0 as Foo>::Ref<!lt_1>)}, assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+            judgment `prove_wc { goal: @ wf(<!ty_0 as Foo>::Ref<!lt_1>), assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+              the rule "assumption" failed at step #1 (src/file.rs:LL:CC) because
+                judgment had no applicable rules: `prove_via { goal: @ wf(<!ty_0 as Foo>::Ref<!lt_1>), via: Foo(!ty_0), assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }`
+              the rule "parameter well formed" failed at step #0 (src/file.rs:LL:CC) because
+                judgment `prove_wf { goal: <!ty_0 as Foo>::Ref<!lt_1>, assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+                  the rule "aliases" failed at step #1 (src/file.rs:LL:CC) because
+                    judgment `prove_after { constraints: Constraints { env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, known_true: true, substitution: {} }, goal: {!ty_0 : !lt_1}, assumptions: {Foo(!ty_0)}, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+                      the rule "prove_after" failed at step #1 (src/file.rs:LL:CC) because
+                        judgment `prove_wc_list { goal: {!ty_0 : !lt_1}, assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+                          the rule "some" failed at step #0 (src/file.rs:LL:CC) because
+                            judgment `prove_wc { goal: !ty_0 : !lt_1, assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }` failed at the following rule(s):
+                              the rule "assumption" failed at step #1 (src/file.rs:LL:CC) because
+                                judgment had no applicable rules: `prove_via { goal: !ty_0 : !lt_1, via: Foo(!ty_0), assumptions: {Foo(!ty_0)}, env: Env { variables: [!ty_0, !lt_1], bias: Soundness }, decls: decls(222, [trait Foo <ty> ], [impl Foo(Bar)], [], [alias <lt> <Bar as Foo>::Ref<^lt0_0> = Borrowed<^lt0_0>], [], [adt Bar , adt Borrowed <lt> ], {Foo}, {Bar, Borrowed}) }`"#]]);
+}
@@ -0,0 +1,22 @@
+use formality_core::{term, test};
+
+formality_core::id!(Id);
+
+#[term(lists $:a $,a $:b $,b ;)]
+pub struct Lists {
+    pub a: Vec<Id>,
+    pub b: Vec<Id>,
+}
+
+/// When the first guard keyword is absent, the second one still parses.
+#[test]
+fn second_guard_without_first() {
+    let term: Lists = crate::ptt::term("lists b x, y ;");
+    expect_test::expect!["lists b x, y ;"].assert_eq(&format!("{term:?}"));
+}
+
+#[test]
+fn both_guards() {
+    let term: Lists = crate::ptt::term("lists a x b y ;");
+    expect_test::expect!["lists a x b y ;"].assert_eq(&format!("{term:?}"));
+}
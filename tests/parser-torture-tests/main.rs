@@ -1,5 +1,6 @@
 mod ambiguity;
 mod grammar;
+mod guards;
 mod left_associative;
 mod none_associative;
 mod path;